@@ -0,0 +1,63 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::{Ok, Result};
+use clap::Parser;
+use dsi_progress_logger::*;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use std::hint::black_box;
+use sux::utils::{popcount_slice, popcount_slice_scalar};
+
+#[derive(Parser, Debug)]
+#[command(about = "Benchmarks population counting on slices of words of increasing size", long_about = None)]
+struct Args {
+    /// The maximum base-2 logarithm of the number of words
+    #[arg(short, long, default_value = "24")]
+    max_log2_words: u32,
+
+    /// The number of test repetitions
+    #[arg(short, long, default_value = "10")]
+    repeats: usize,
+}
+
+fn main() -> Result<()> {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .try_init()?;
+
+    let args = Args::parse();
+    let mut rng = SmallRng::seed_from_u64(0);
+    let words = (0..1_usize << args.max_log2_words)
+        .map(|_| rng.gen::<usize>())
+        .collect::<Vec<_>>();
+
+    for log2_words in (4..=args.max_log2_words).step_by(4) {
+        let slice = &words[..1 << log2_words];
+        // Keep the total number of words counted roughly constant
+        let iters = (1 << args.max_log2_words) >> log2_words;
+
+        for _ in 0..args.repeats {
+            let mut pl = ProgressLogger::default();
+            pl.item_name("word");
+
+            pl.start(format!("Scalar popcount on 2^{} words...", log2_words));
+            for _ in 0..iters {
+                black_box(popcount_slice_scalar(black_box(slice)));
+            }
+            pl.done_with_count(iters << log2_words);
+
+            pl.start(format!("Popcount on 2^{} words...", log2_words));
+            for _ in 0..iters {
+                black_box(popcount_slice(black_box(slice)));
+            }
+            pl.done_with_count(iters << log2_words);
+        }
+    }
+
+    Ok(())
+}
@@ -29,6 +29,10 @@ struct Args {
     #[arg(short, long, default_value = "10000")]
     /// How many iterations of random access speed test
     accesses: usize,
+
+    #[arg(short, long, default_value = "1000000")]
+    /// How many queries to perform in the parallel membership test
+    queries: usize,
 }
 
 pub fn main() -> Result<()> {
@@ -76,5 +80,65 @@ pub fn main() -> Result<()> {
         elapsed.as_nanos() as f64 / args.accesses as f64
     );
 
+    // Compare the throughput of contains between the two layouts of rear
+    // lengths; half of the queries are made absent
+    let strings = (0..args.accesses)
+        .map(|i| {
+            let mut s = rca.get(rand.gen::<usize>() % rca.len());
+            if i % 2 == 0 {
+                s.push('\u{1}');
+            }
+            s
+        })
+        .collect::<Vec<_>>();
+
+    let mut rcab = RearCodedListBuilder::new(args.k).separate_codes(true);
+    rcab.extend(rca.iter().into_lender());
+    let separate = rcab.build();
+
+    for (layout, rca) in [("interleaved", &rca), ("separate", &separate)] {
+        let start = std::time::Instant::now();
+        for s in &strings {
+            black_box(rca.contains(black_box(s.as_str())));
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "contains_speed ({}): {} ns/query",
+            layout,
+            elapsed.as_nanos() as f64 / args.accesses as f64
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        // Random strings of the list, half of which are made absent
+        let strings = (0..args.queries)
+            .map(|i| {
+                let mut s = rca.get(rand.gen::<usize>() % rca.len());
+                if i % 2 == 0 {
+                    s.push('\u{1}');
+                }
+                s
+            })
+            .collect::<Vec<_>>();
+        let queries = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+        let mut num_threads = 1;
+        while num_threads <= num_cpus::get() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()?;
+            let start = std::time::Instant::now();
+            pool.install(|| black_box(rca.par_contains(black_box(&queries))));
+            let elapsed = start.elapsed();
+            println!(
+                "par_contains_speed ({} threads): {} ns/query",
+                num_threads,
+                elapsed.as_nanos() as f64 / args.queries as f64
+            );
+            num_threads *= 2;
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,6 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sux::fuzz::rear_coded_list::{harness, Data};
+
+fuzz_target!(|data: Data| harness(data));
@@ -0,0 +1,187 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Fixed-size bit arrays stored inline.
+
+use crate::prelude::*;
+use ambassador::Delegate;
+use mem_dbg::*;
+use std::ops::Index;
+
+use crate::ambassador_impl_AsRef;
+use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_BitCount;
+use crate::traits::rank_sel::ambassador_impl_BitLength;
+use crate::traits::rank_sel::ambassador_impl_RankHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectZeroHinted;
+
+/// A bit array of `WORDS` × [`usize::BITS`] bits stored inline in an array of
+/// words.
+///
+/// This structure is a [`BitVec`] with an array backend and a length fixed
+/// by the number of words. It is useful for small bitmaps (e.g., sets of
+/// flags), as it can be built on the stack with [`BitArray::new`] and no
+/// allocation is ever performed.
+///
+/// Since the array is small, ranking and selection are implemented directly
+/// by scanning the words, without auxiliary structures. Nonetheless, a
+/// [`BitArray`] implements the same traits of a [`BitVec`] needed by the
+/// structures in [`rank_sel`](crate::rank_sel), so it can also be used as
+/// their backend.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::bits::BitArray;
+///
+/// let mut flags = BitArray::<4>::new();
+/// assert_eq!(flags.len(), 256);
+/// flags.set(3, true);
+/// flags.set(200, true);
+///
+/// assert!(flags[3]);
+/// assert!(!flags[4]);
+/// assert_eq!(flags.count_ones(), 2);
+/// assert_eq!(flags.rank(100), 1);
+/// assert_eq!(flags.select(1), Some(200));
+/// assert_eq!(flags.select(2), None);
+/// assert_eq!(flags.select_zero(3), Some(4));
+/// ```
+#[derive(Debug, Clone, PartialEq, MemDbg, MemSize, Delegate)]
+#[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(Index<usize>, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectZeroHinted, target = "bits")]
+pub struct BitArray<const WORDS: usize> {
+    bits: BitVec<[usize; WORDS]>,
+}
+
+impl<const WORDS: usize> BitArray<WORDS> {
+    /// The number of bits of the array.
+    pub const LEN: usize = WORDS * usize::BITS as usize;
+
+    /// Creates a new bit array with all bits set to zero.
+    pub fn new() -> Self {
+        Self::from([0; WORDS])
+    }
+
+    /// Returns the number of bits in the array.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    /// Returns the value of the bit of given position.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the
+    /// [length](BitArray::len) of the array.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> bool {
+        self.bits.get(index)
+    }
+
+    /// Sets the bit of given position to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the
+    /// [length](BitArray::len) of the array.
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.bits.set(index, value)
+    }
+
+    /// Sets all bits to the given value.
+    pub fn fill(&mut self, value: bool) {
+        self.bits.fill(value)
+    }
+
+    /// Returns the underlying bit vector.
+    #[inline(always)]
+    pub fn as_bit_vec(&self) -> &BitVec<[usize; WORDS]> {
+        &self.bits
+    }
+
+    /// Returns the underlying array of words.
+    pub fn into_inner(self) -> [usize; WORDS] {
+        self.bits.into_raw_parts().0
+    }
+}
+
+impl<const WORDS: usize> Default for BitArray<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> From<[usize; WORDS]> for BitArray<WORDS> {
+    fn from(words: [usize; WORDS]) -> Self {
+        Self {
+            // SAFETY: the length is the number of bits in the array
+            bits: unsafe { BitVec::from_raw_parts(words, Self::LEN) },
+        }
+    }
+}
+
+impl<const WORDS: usize> AsMut<[usize]> for BitArray<WORDS> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [usize] {
+        self.bits.as_mut()
+    }
+}
+
+impl<const WORDS: usize> NumBits for BitArray<WORDS> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.bits.count_ones()
+    }
+}
+
+impl<const WORDS: usize> RankUnchecked for BitArray<WORDS> {
+    #[inline(always)]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        let words = self.bits.as_ref();
+        let word_pos = pos / usize::BITS as usize;
+        let bit_pos = pos % usize::BITS as usize;
+        words
+            .get_unchecked(..word_pos)
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>()
+            + (words.get_unchecked(word_pos) & ((1 << bit_pos) - 1)).count_ones() as usize
+    }
+}
+
+impl<const WORDS: usize> Rank for BitArray<WORDS> {}
+impl<const WORDS: usize> RankZero for BitArray<WORDS> {}
+
+impl<const WORDS: usize> SelectUnchecked for BitArray<WORDS> {
+    #[inline(always)]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.bits.select_hinted(rank, 0, 0)
+    }
+}
+
+impl<const WORDS: usize> Select for BitArray<WORDS> {}
+
+impl<const WORDS: usize> SelectZeroUnchecked for BitArray<WORDS> {
+    #[inline(always)]
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        self.bits.select_zero_hinted(rank, 0, 0)
+    }
+}
+
+impl<const WORDS: usize> SelectZero for BitArray<WORDS> {}
@@ -82,7 +82,6 @@
 use crate::prelude::*;
 use crate::traits::bit_field_slice::{panic_if_out_of_bounds, panic_if_value};
 use crate::utils::{transmute_boxed_slice, transmute_vec};
-use anyhow::{bail, Result};
 use common_traits::*;
 use epserde::*;
 use mem_dbg::*;
@@ -181,6 +180,36 @@ fn mask<W: Word>(bit_width: usize) -> W {
     }
 }
 
+/// The error returned by fallible conversions between bit-field vectors.
+///
+/// Since this type implements [`std::error::Error`], it can be converted
+/// into an [`anyhow::Error`] using the `?` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The bit width of the values is larger than the number of bits
+    /// of the target word type.
+    BitWidthTooLarge {
+        /// The bit width of the values.
+        bit_width: usize,
+        /// The name of the target word type.
+        word: &'static str,
+    },
+}
+
+impl core::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConvertError::BitWidthTooLarge { bit_width, word } => write!(
+                f,
+                "Cannot convert a slice of bit width {} into a slice with W = {}",
+                bit_width, word
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
 impl<W: Word, B> BitFieldVec<W, B> {
     /// # Safety
     /// `len` * `bit_width` must be between 0 (included) the number of
@@ -260,6 +289,37 @@ impl<W: Word, B: AsRef<[W]>> BitFieldVec<W, B> {
     pub fn as_slice(&self) -> &[W] {
         self.bits.as_ref()
     }
+
+    /// Returns the words of the backend containing the values of the vector.
+    ///
+    /// Differently from [`as_slice`](BitFieldVec::as_slice), the returned
+    /// slice contains exactly the ⌈`len()` · `bit_width()` / `W::BITS`⌉
+    /// words storing values, so it can be handed to external code (e.g.,
+    /// SIMD kernels) decoding values in bulk.
+    ///
+    /// The packing layout is as follows: the value of index *i* occupies bits
+    /// from *i* · `bit_width()` (included) to (*i* + 1) · `bit_width()`
+    /// (excluded) of the slice, where bit *b* is bit *b* % `W::BITS` (counting
+    /// from the least significant bit) of word ⌊*b* / `W::BITS`⌋. Thus, a
+    /// value crossing a word boundary has its lowest bits at the top of a word
+    /// and its highest bits at the bottom of the next word. The content of the
+    /// bits of the last word following the last value is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let b = bit_field_vec![10; 4, 500, 2, 0, 1, 1023, 7];
+    /// let words = b.raw_words();
+    /// assert_eq!(words.len(), 2);
+    /// // The value of index 6 spans the two words
+    /// let value = (words[0] >> 60 | words[1] << 4) & 1023;
+    /// assert_eq!(value, 7);
+    /// ```
+    pub fn raw_words(&self) -> &[W] {
+        &self.bits.as_ref()[..(self.len * self.bit_width).div_ceil(W::BITS)]
+    }
 }
 
 impl<W: Word, B: AsMut<[W]>> BitFieldVec<W, B> {
@@ -326,9 +386,9 @@ impl<W: Word> BitFieldVec<W, Vec<W>> {
     /// Creates a new vector by copying a slice; the bit width will be the minimum
     /// width sufficient to hold all values in the slice.
     ///
-    /// Returns an error if the bit width of the values in `slice` is larger than
-    /// `W::BITS`.
-    pub fn from_slice<SW>(slice: &impl BitFieldSlice<SW>) -> Result<Self>
+    /// Returns [`ConvertError::BitWidthTooLarge`] if the bit width of the
+    /// values in `slice` is larger than `W::BITS`.
+    pub fn from_slice<SW>(slice: &impl BitFieldSlice<SW>) -> Result<Self, ConvertError>
     where
         SW: Word + CastableInto<W>,
     {
@@ -338,11 +398,10 @@ impl<W: Word> BitFieldVec<W, Vec<W>> {
         }
 
         if max_len > W::BITS {
-            bail!(
-                "Cannot convert a slice of bit width {} into a slice with W = {}",
-                max_len,
-                std::any::type_name::<W>()
-            );
+            return Err(ConvertError::BitWidthTooLarge {
+                bit_width: max_len,
+                word: std::any::type_name::<W>(),
+            });
         }
         let mut result = Self::new(max_len, slice.len());
         for i in 0..slice.len() {
@@ -355,7 +414,8 @@ impl<W: Word> BitFieldVec<W, Vec<W>> {
     /// Adds a value at the end of the vector.
     pub fn push(&mut self, value: W) {
         panic_if_value!(value, self.mask, self.bit_width);
-        if (self.len + 1) * self.bit_width > self.bits.len() * W::BITS {
+        // We need at least one word to handle the case of bit width zero.
+        if (self.len + 1) * self.bit_width > self.bits.len() * W::BITS || self.bits.is_empty() {
             self.bits.push(W::ZERO);
         }
         unsafe {
@@ -886,11 +946,22 @@ impl<'a, W: Word, B: AsRef<[W]>> IntoIterator for &'a BitFieldVec<W, B> {
     }
 }
 
+impl<'a, W: Word, B: AsRef<[W]>> IntoIteratorFrom for &'a BitFieldVec<W, B> {
+    type IntoIterFrom = BitFieldVecIterator<'a, W, B>;
+
+    fn into_iter_from(self, from: usize) -> Self::IntoIterFrom {
+        BitFieldVecIterator::new(self, from)
+    }
+}
+
 impl<W: Word, B: AsRef<[W]>> BitFieldVec<W, B> {
+    /// Returns an iterator over the values of the vector starting from
+    /// position `from`.
     pub fn iter_from(&self, from: usize) -> BitFieldVecIterator<W, B> {
         BitFieldVecIterator::new(self, from)
     }
 
+    /// Returns an iterator over the values of the vector.
     pub fn iter(&self) -> BitFieldVecIterator<W, B> {
         self.iter_from(0)
     }
@@ -1036,6 +1107,18 @@ where
     #[inline]
     unsafe fn set_atomic_unchecked(&self, index: usize, value: W, order: Ordering) {
         debug_assert!(self.bit_width != W::BITS);
+        debug_assert!(
+            index < self.len,
+            "Index out of bounds: {} >= {}",
+            index,
+            self.len
+        );
+        debug_assert!(
+            value & self.mask == value,
+            "Value {} does not fit in {} bits",
+            value,
+            self.bit_width
+        );
         let pos = index * self.bit_width;
         let word_index = pos / W::BITS;
         let bit_index = pos % W::BITS;
@@ -1131,28 +1214,101 @@ where
 
 // Conversions
 
+/// Returns true if `A` and `B` have the same size and alignment, so that
+/// a vector of `A` can be reinterpreted in place as a vector of `B`.
+#[inline(always)]
+const fn same_layout<A, B>() -> bool {
+    core::mem::size_of::<A>() == core::mem::size_of::<B>()
+        && core::mem::align_of::<A>() == core::mem::align_of::<B>()
+}
+
+impl<W: Word + IntoAtomic> AtomicBitFieldVec<W> {
+    /// Converts this vector into a non-atomic [`BitFieldVec`] with the same
+    /// content.
+    ///
+    /// Atomic integers have the same size and in-memory representation as
+    /// the corresponding plain integers, and taking ownership guarantees that
+    /// no other thread is accessing the vector, so in general the backend is
+    /// reinterpreted in place without copying.
+    ///
+    /// The alignment of an atomic integer might however be stricter than that
+    /// of the corresponding plain integer (e.g., `AtomicU64` and `u64` on
+    /// 32-bit x86), and in that case reinterpreting the allocation would
+    /// deallocate it with the wrong layout. Thus, when size or alignment
+    /// differ the words are copied into a new vector instead.
+    pub fn into_non_atomic(self) -> BitFieldVec<W, Vec<W>> {
+        let bits = if same_layout::<W::AtomicType, W>() {
+            // SAFETY: the two types have the same size, alignment, and bit
+            // validity, and we own the only reference to the vector.
+            unsafe { transmute_vec::<W::AtomicType, W>(self.bits) }
+        } else {
+            self.bits
+                .into_iter()
+                .map(|word| word.load(Ordering::Relaxed))
+                .collect()
+        };
+        BitFieldVec {
+            bits,
+            len: self.len,
+            bit_width: self.bit_width,
+            mask: self.mask,
+        }
+    }
+}
+
+impl<W: Word + IntoAtomic> BitFieldVec<W, Vec<W>> {
+    /// Converts this vector into an [`AtomicBitFieldVec`] with the same
+    /// content.
+    ///
+    /// This is the inverse of [`AtomicBitFieldVec::into_non_atomic`]: the
+    /// backend is reinterpreted in place if plain and atomic words have the
+    /// same size and alignment, and copied otherwise.
+    pub fn into_atomic(self) -> AtomicBitFieldVec<W> {
+        let bits = if same_layout::<W, W::AtomicType>() {
+            // SAFETY: the two types have the same size, alignment, and bit
+            // validity, and we own the only reference to the vector.
+            unsafe { transmute_vec::<W, W::AtomicType>(self.bits) }
+        } else {
+            self.bits.into_iter().map(W::AtomicType::new).collect()
+        };
+        AtomicBitFieldVec {
+            bits,
+            len: self.len,
+            bit_width: self.bit_width,
+            mask: self.mask,
+        }
+    }
+}
+
 impl<W: Word + IntoAtomic> From<AtomicBitFieldVec<W, Vec<W::AtomicType>>>
     for BitFieldVec<W, Vec<W>>
 {
+    /// See [`AtomicBitFieldVec::into_non_atomic`].
     #[inline]
     fn from(value: AtomicBitFieldVec<W, Vec<W::AtomicType>>) -> Self {
-        BitFieldVec {
-            bits: unsafe { transmute_vec::<W::AtomicType, W>(value.bits) },
-            len: value.len,
-            bit_width: value.bit_width,
-            mask: value.mask,
-        }
+        value.into_non_atomic()
     }
 }
 
 impl<W: Word + IntoAtomic> From<AtomicBitFieldVec<W, Box<[W::AtomicType]>>>
     for BitFieldVec<W, Box<[W]>>
 {
+    /// See [`AtomicBitFieldVec::into_non_atomic`].
     #[inline]
     fn from(value: AtomicBitFieldVec<W, Box<[W::AtomicType]>>) -> Self {
+        let bits = if same_layout::<W::AtomicType, W>() {
+            // SAFETY: the two types have the same size, alignment, and bit
+            // validity, and we own the only reference to the slice.
+            unsafe { transmute_boxed_slice::<W::AtomicType, W>(value.bits) }
+        } else {
+            value
+                .bits
+                .iter()
+                .map(|word| word.load(Ordering::Relaxed))
+                .collect()
+        };
         BitFieldVec {
-            bits: unsafe { transmute_boxed_slice::<W::AtomicType, W>(value.bits) },
-
+            bits,
             len: value.len,
             bit_width: value.bit_width,
             mask: value.mask,
@@ -1193,24 +1349,28 @@ impl<'a, W: Word + IntoAtomic> From<AtomicBitFieldVec<W, &'a mut [W::AtomicType]
 impl<W: Word + IntoAtomic> From<BitFieldVec<W, Vec<W>>>
     for AtomicBitFieldVec<W, Vec<W::AtomicType>>
 {
+    /// See [`BitFieldVec::into_atomic`].
     #[inline]
     fn from(value: BitFieldVec<W, Vec<W>>) -> Self {
-        AtomicBitFieldVec {
-            bits: unsafe { transmute_vec::<W, W::AtomicType>(value.bits) },
-            len: value.len,
-            bit_width: value.bit_width,
-            mask: value.mask,
-        }
+        value.into_atomic()
     }
 }
 
 impl<W: Word + IntoAtomic> From<BitFieldVec<W, Box<[W]>>>
     for AtomicBitFieldVec<W, Box<[W::AtomicType]>>
 {
+    /// See [`BitFieldVec::into_atomic`].
     #[inline]
     fn from(value: BitFieldVec<W, Box<[W]>>) -> Self {
+        let bits = if same_layout::<W, W::AtomicType>() {
+            // SAFETY: the two types have the same size, alignment, and bit
+            // validity, and we own the only reference to the slice.
+            unsafe { transmute_boxed_slice::<W, W::AtomicType>(value.bits) }
+        } else {
+            value.bits.iter().copied().map(W::AtomicType::new).collect()
+        };
         AtomicBitFieldVec {
-            bits: unsafe { transmute_boxed_slice::<W, W::AtomicType>(value.bits) },
+            bits,
             len: value.len,
             bit_width: value.bit_width,
             mask: value.mask,
@@ -26,6 +26,11 @@
 //!
 //! It is possible to juggle between the three flavors using [`From`]/[`Into`].
 //!
+//! Rank/select structures require backends made of `usize` words. On 64-bit
+//! platforms, a bit vector whose backend is made of `u64` words (a vector, a
+//! boxed slice, or a reference to a slice) can be turned into one with
+//! `usize` words using [`From`]/[`Into`] at no cost.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -75,13 +80,16 @@ use core::fmt;
 use epserde::*;
 use mem_dbg::*;
 use std::{
-    ops::Index,
-    sync::atomic::{AtomicUsize, Ordering},
+    ops::{Index, Range},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use crate::{
     traits::rank_sel::*,
-    utils::{transmute_boxed_slice, transmute_vec},
+    utils::{popcount_slice, transmute_boxed_slice, transmute_vec},
 };
 
 const BITS: usize = usize::BITS as usize;
@@ -218,6 +226,281 @@ impl<B: AsRef<[usize]>> BitVec<B> {
         let word = self.bits.as_ref().get_unchecked(word_index);
         (word >> (index % BITS)) & 1 != 0
     }
+
+    /// Returns a checksum of the content of the bit vector.
+    ///
+    /// The checksum is a word-wise [FNV-1a
+    /// hash](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+    /// of the length and of the words of the bit vector. Bits beyond the
+    /// length of the bit vector do not affect the result, and changing a
+    /// single bit always changes the result.
+    ///
+    /// The checksum can be stored alongside serialized data to detect
+    /// corruption using [`verify`](BitVec::verify).
+    pub fn checksum(&self) -> u64 {
+        let bits = self.bits.as_ref();
+        checksum(self.len, |i| bits[i])
+    }
+
+    /// Returns true if the [checksum](BitVec::checksum) of the bit vector is
+    /// equal to `expected`.
+    pub fn verify(&self, expected: u64) -> bool {
+        self.checksum() == expected
+    }
+
+    /// Returns the fraction of ones in the bit vector, or zero if the bit
+    /// vector is empty.
+    ///
+    /// This method uses [`count_ones`](BitCount::count_ones), and thus its
+    /// cost is linear in the length of the bit vector; see
+    /// [`AddNumBits::density`] for a constant-time version.
+    pub fn density(&self) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            self.count_ones() as f64 / self.len as f64
+        }
+    }
+
+    /// Returns the number of maximal runs of ones and the number of maximal
+    /// runs of zeros in the bit vector.
+    ///
+    /// The computation is performed word by word: the starts of runs are
+    /// detected by comparing each word with itself shifted by one, carrying
+    /// the last bit of the previous word.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let b = bit_vec![1, 1, 0, 1, 0, 0, 0, 1];
+    /// assert_eq!(b.count_runs(), (3, 2));
+    /// ```
+    pub fn count_runs(&self) -> (usize, usize) {
+        let bits = self.bits.as_ref();
+        let num_words = self.num_words();
+        let mut ones_runs = 0;
+        let mut zeros_runs = 0;
+        // The last bit of the previous word; before the first word, we
+        // pretend to have seen a bit different from the first one, so that
+        // the first run is counted.
+        let mut carry_ones = 0;
+        let mut carry_zeros = 0;
+
+        for (i, &word) in bits[..num_words].iter().enumerate() {
+            let residual = self.len - i * BITS;
+            let mask = if residual >= BITS {
+                usize::MAX
+            } else {
+                (1 << residual) - 1
+            };
+            let ones = word & mask;
+            let zeros = !word & mask;
+            // A run starts where a bit differs from the preceding one
+            ones_runs += (ones & !((ones << 1) | carry_ones)).count_ones() as usize;
+            zeros_runs += (zeros & !((zeros << 1) | carry_zeros)).count_ones() as usize;
+            carry_ones = ones >> (BITS - 1);
+            carry_zeros = zeros >> (BITS - 1);
+        }
+
+        (ones_runs, zeros_runs)
+    }
+
+    /// Returns the length of the longest run of bits equal to `value`, or
+    /// zero if there is no such bit.
+    ///
+    /// The computation skips whole runs inside each word using
+    /// [`trailing_ones`](usize::trailing_ones) and
+    /// [`trailing_zeros`](usize::trailing_zeros).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let b = bit_vec![1, 1, 0, 1, 0, 0, 0, 1];
+    /// assert_eq!(b.longest_run(true), 2);
+    /// assert_eq!(b.longest_run(false), 3);
+    /// ```
+    pub fn longest_run(&self, value: bool) -> usize {
+        let bits = self.bits.as_ref();
+        let num_words = self.num_words();
+        let mut longest = 0;
+        let mut current = 0;
+
+        for (i, &word) in bits[..num_words].iter().enumerate() {
+            let mut word = if value { word } else { !word };
+            let mut left = Ord::min(BITS, self.len - i * BITS);
+            while left != 0 {
+                let run = Ord::min(word.trailing_ones() as usize, left);
+                current += run;
+                if run == left {
+                    break;
+                }
+                longest = Ord::max(longest, current);
+                current = 0;
+                // The bit at position run is different from value
+                let gap = Ord::min((word >> run).trailing_zeros() as usize, left - run);
+                left -= run + gap;
+                word = word.checked_shr((run + gap) as u32).unwrap_or(0);
+            }
+        }
+
+        Ord::max(longest, current)
+    }
+
+    /// Returns the run-length encoding of this bit vector.
+    ///
+    /// The result contains the lengths of the maximal runs of equal bits,
+    /// alternating between zeros and ones and starting with the length of the
+    /// initial run of zeros, which is zero if the bit vector starts with a
+    /// one. All other lengths are positive, and the encoding of an empty bit
+    /// vector is empty. The original bit vector can be rebuilt using
+    /// [`BitVec::from_rle`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let b = bit_vec![1, 1, 0, 1, 0, 0, 0];
+    /// assert_eq!(b.to_rle(), vec![0, 2, 1, 1, 3]);
+    /// ```
+    pub fn to_rle(&self) -> Vec<usize> {
+        let bits = self.bits.as_ref();
+        let num_words = self.num_words();
+        let mut runs = Vec::new();
+        let mut value = false;
+        let mut current = 0;
+
+        for (i, &word) in bits[..num_words].iter().enumerate() {
+            let mut word = word;
+            let mut left = Ord::min(BITS, self.len - i * BITS);
+            while left != 0 {
+                let masked = if value { word } else { !word };
+                let run = Ord::min(masked.trailing_ones() as usize, left);
+                current += run;
+                left -= run;
+                if left == 0 {
+                    break;
+                }
+                // The bit at position run is different from value
+                runs.push(current);
+                current = 0;
+                value = !value;
+                word >>= run;
+            }
+        }
+
+        if self.len != 0 {
+            runs.push(current);
+        }
+        runs
+    }
+
+    /// Returns the words of this bit vector that differ from the
+    /// corresponding words of `base`, as pairs given by the index of a word and
+    /// by its value in this bit vector.
+    ///
+    /// The pairs are returned in increasing word order, and bits beyond the
+    /// length are not considered (in the last word they are always zero).
+    /// Storing a base bit vector and the difference of subsequent versions
+    /// makes it possible to save space when bit vectors change slowly; the
+    /// difference can be applied using [`apply_diff`](BitVec::apply_diff).
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the two bit vectors have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let base = bit_vec![0; 200];
+    /// let mut b = base.clone();
+    /// b.set(1, true);
+    /// b.set(130, true);
+    /// assert_eq!(b.diff(&base), vec![(0, 2), (2, 4)]);
+    /// ```
+    pub fn diff<C: AsRef<[usize]>>(&self, base: &BitVec<C>) -> Vec<(usize, usize)> {
+        assert_eq!(self.len, base.len, "The bit vectors have different lengths");
+        let num_words = self.num_words();
+        let padding_bits = self.padding_bits();
+        let bits = &self.bits.as_ref()[..num_words];
+        let base = &base.bits.as_ref()[..num_words];
+        bits.iter()
+            .zip(base)
+            .enumerate()
+            .filter_map(|(i, (&word, &base_word))| {
+                let mask = if i == num_words - 1 {
+                    usize::MAX >> padding_bits
+                } else {
+                    usize::MAX
+                };
+                ((word ^ base_word) & mask != 0).then_some((i, word & mask))
+            })
+            .collect()
+    }
+
+    /// Returns the number of bits that change from zero to one and from one
+    /// to zero going from this bit vector to `other`.
+    ///
+    /// The counts are computed word by word, and bits beyond the length are
+    /// not considered. This method is useful to monitor the churn of a set
+    /// represented by a bit vector across snapshots.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the two bit vectors have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let old = bit_vec![1, 1, 0, 0, 1];
+    /// let new = bit_vec![0, 1, 1, 1, 1];
+    /// assert_eq!(old.count_flips(&new), (2, 1));
+    /// assert_eq!(new.count_flips(&old), (1, 2));
+    /// ```
+    pub fn count_flips<C: AsRef<[usize]>>(&self, other: &BitVec<C>) -> (usize, usize) {
+        assert_eq!(
+            self.len, other.len,
+            "The bit vectors have different lengths"
+        );
+        let num_words = self.num_words();
+        let padding_bits = self.padding_bits();
+        let bits = &self.bits.as_ref()[..num_words];
+        let other = &other.bits.as_ref()[..num_words];
+        let mut zeros_to_ones = 0;
+        let mut ones_to_zeros = 0;
+        for (i, (&word, &other_word)) in bits.iter().zip(other).enumerate() {
+            let mask = if i == num_words - 1 {
+                usize::MAX >> padding_bits
+            } else {
+                usize::MAX
+            };
+            zeros_to_ones += (!word & other_word & mask).count_ones() as usize;
+            ones_to_zeros += (word & !other_word & mask).count_ones() as usize;
+        }
+        (zeros_to_ones, ones_to_zeros)
+    }
+}
+
+/// Computes a word-wise FNV-1a hash of a length and of the words of a bit
+/// vector of that length, masking the bits beyond the length.
+fn checksum(len: usize, word: impl Fn(usize) -> usize) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let full_words = len / BITS;
+    let residual = len % BITS;
+    let mut hash = (FNV_OFFSET_BASIS ^ len as u64).wrapping_mul(FNV_PRIME);
+    for i in 0..full_words {
+        hash = (hash ^ word(i) as u64).wrapping_mul(FNV_PRIME);
+    }
+    if residual != 0 {
+        let last = word(full_words) & ((1 << residual) - 1);
+        hash = (hash ^ last as u64).wrapping_mul(FNV_PRIME);
+    }
+    hash
 }
 
 impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
@@ -243,6 +526,55 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
         }
     }
 
+    /// Sets to one the bits at the given positions, which must be sorted in
+    /// nondecreasing order.
+    ///
+    /// Positions falling in the same word are combined into a mask, so each
+    /// word is modified once. This method is thus significantly faster than
+    /// calling [`set`](BitVec::set) for each position when positions are
+    /// dense.
+    ///
+    /// Sortedness and bounds are checked only in debug mode: in release mode,
+    /// unsorted positions will lead to multiple writes to the same word, and
+    /// out-of-bounds positions will modify the bits of the backend beyond the
+    /// end of the bit vector, or cause a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::bits::BitVec;
+    ///
+    /// let mut b = BitVec::new(200);
+    /// b.set_ones_sorted(&[1, 3, 64, 65, 199]);
+    /// assert_eq!(b.iter_ones().collect::<Vec<_>>(), vec![1, 3, 64, 65, 199]);
+    /// ```
+    pub fn set_ones_sorted(&mut self, positions: &[usize]) {
+        debug_assert!(
+            positions.windows(2).all(|w| w[0] <= w[1]),
+            "The positions are not sorted"
+        );
+        debug_assert!(
+            positions.last().copied().unwrap_or(0) < self.len || positions.is_empty(),
+            "Bit index out of bounds: {} >= {}",
+            positions.last().unwrap(),
+            self.len
+        );
+        let bits = self.bits.as_mut();
+        let mut positions = positions.iter().peekable();
+        while let Some(&pos) = positions.next() {
+            let word_index = pos / BITS;
+            let mut mask = 1 << (pos % BITS);
+            while let Some(&&next) = positions.peek() {
+                if next / BITS != word_index {
+                    break;
+                }
+                mask |= 1 << (next % BITS);
+                positions.next();
+            }
+            bits[word_index] |= mask;
+        }
+    }
+
     /// Set all bits to the given value.
     ///
     /// If the feature "rayon" is enabled, this method is computed in parallel.
@@ -280,6 +612,68 @@ impl<B: AsRef<[usize]> + AsMut<[usize]>> BitVec<B> {
             bits[full_words] = (bits[full_words] & !mask) | (!bits[full_words] & mask);
         }
     }
+
+    /// Zeroes the bits of the last word of the backend that lie beyond the
+    /// length of the bit vector.
+    ///
+    /// Methods of this structure never read such bits, but they are visible
+    /// through the backend (e.g., after [`into_raw_parts`](BitVec::into_raw_parts)
+    /// or serialization), and they might be set after editing directly the
+    /// words returned by [`as_mut`](AsMut::as_mut). This method restores the
+    /// invariant that all padding bits are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let mut b = bit_vec![0; 10];
+    /// b.as_mut()[0] = usize::MAX;
+    /// b.sanitize();
+    /// assert_eq!(b.as_ref()[0], (1 << 10) - 1);
+    /// ```
+    pub fn sanitize(&mut self) {
+        let num_words = self.num_words();
+        let padding_bits = self.padding_bits();
+        if padding_bits != 0 {
+            self.bits.as_mut()[num_words - 1] &= usize::MAX >> padding_bits;
+        }
+    }
+
+    /// Applies a difference returned by [`diff`](BitVec::diff).
+    ///
+    /// If `diff` was computed on a bit vector `b` using this bit vector as a
+    /// base, after this call this bit vector will be equal to `b`. The bits of
+    /// the backend beyond the length are not modified.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if a word index is not smaller than the number
+    /// of words of the bit vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// let mut base = bit_vec![0; 200];
+    /// let mut b = base.clone();
+    /// b.set(1, true);
+    /// b.set(130, true);
+    /// base.apply_diff(&b.diff(&base));
+    /// assert_eq!(base, b);
+    /// ```
+    pub fn apply_diff(&mut self, diff: &[(usize, usize)]) {
+        let num_words = self.num_words();
+        let padding_bits = self.padding_bits();
+        let bits = &mut self.bits.as_mut()[..num_words];
+        for &(i, word) in diff {
+            if i == num_words - 1 {
+                let mask = usize::MAX >> padding_bits;
+                bits[i] = (bits[i] & !mask) | (word & mask);
+            } else {
+                bits[i] = word;
+            }
+        }
+    }
 }
 
 impl BitVec<Vec<usize>> {
@@ -290,15 +684,51 @@ impl BitVec<Vec<usize>> {
 
     /// Creates a new bit vector of length `len` initialized to `value`.
     pub fn with_value(len: usize, value: bool) -> Self {
-        let n_of_words = (len + BITS - 1) / BITS;
-        let extra_bits = (n_of_words * BITS) - len;
         let word_value = if value { !0 } else { 0 };
-        let mut bits = vec![word_value; n_of_words];
-        if extra_bits > 0 {
-            let last_word_value = word_value >> extra_bits;
-            bits[n_of_words - 1] = last_word_value;
+        let mut bit_vec = Self {
+            bits: vec![word_value; len.div_ceil(BITS)],
+            len,
+        };
+        let padding_bits = bit_vec.padding_bits();
+        if padding_bits > 0 {
+            let num_words = bit_vec.num_words();
+            bit_vec.bits[num_words - 1] = word_value >> padding_bits;
         }
-        Self { bits, len }
+        bit_vec
+    }
+
+    /// Creates a new bit vector from its run-length encoding.
+    ///
+    /// The run lengths alternate between zeros and ones, starting with the
+    /// length of the initial run of zeros, as returned by
+    /// [`to_rle`](BitVec::to_rle). Runs of length zero are allowed anywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bits::BitVec;
+    /// # use sux::bit_vec;
+    /// let b = BitVec::from_rle(&[0, 2, 1, 1, 3]);
+    /// assert_eq!(b, bit_vec![1, 1, 0, 1, 0, 0, 0]);
+    /// ```
+    pub fn from_rle(runs: &[usize]) -> Self {
+        let mut bit_vec = Self::new(runs.iter().sum());
+        let mut pos = 0;
+        for (i, &run) in runs.iter().enumerate() {
+            let end = pos + run;
+            if i % 2 == 1 {
+                // Set a run of ones word by word
+                while pos < end {
+                    let bit = pos % BITS;
+                    let n = Ord::min(BITS - bit, end - pos);
+                    let mask = if n == BITS { !0 } else { ((1 << n) - 1) << bit };
+                    bit_vec.bits[pos / BITS] |= mask;
+                    pos += n;
+                }
+            }
+            pos = end;
+        }
+        bit_vec
     }
 
     /// Creates a new zero-length bit vector of given capacity.
@@ -337,14 +767,16 @@ impl BitVec<Vec<usize>> {
         self.len -= 1;
         let word_index = self.len / BITS;
         let bit_index = self.len % BITS;
-        Some((self.bits[word_index] >> bit_index) & 1 != 0)
+        let bit = (self.bits[word_index] >> bit_index) & 1 != 0;
+        self.sanitize();
+        Some(bit)
     }
 
     pub fn resize(&mut self, new_len: usize, value: bool) {
         // TODO: rewrite by word
         if new_len > self.len {
             if new_len > self.bits.len() * BITS {
-                self.bits.resize((new_len + BITS - 1) / BITS, 0);
+                self.bits.resize(new_len.div_ceil(BITS), 0);
             }
             for i in self.len..new_len {
                 unsafe {
@@ -353,6 +785,7 @@ impl BitVec<Vec<usize>> {
             }
         }
         self.len = new_len;
+        self.sanitize();
     }
 }
 
@@ -370,12 +803,7 @@ impl<B: AsRef<[usize]>> BitCount for BitVec<B> {
         let full_words = self.len() / BITS;
         let residual = self.len() % BITS;
         let bits = self.bits.as_ref();
-        let mut num_ones;
-
-        num_ones = bits[..full_words]
-            .iter()
-            .map(|x| x.count_ones() as usize)
-            .sum();
+        let mut num_ones = popcount_slice(&bits[..full_words]);
 
         if residual != 0 {
             num_ones += (self.as_ref()[full_words] << (BITS - residual)).count_ones() as usize
@@ -566,6 +994,25 @@ impl<'a, B: AsRef<[usize]>> OnesIterator<'a, B> {
             word,
         }
     }
+
+    /// Creates an iterator over the positions of the ones starting from bit
+    /// position `from`.
+    ///
+    /// Each word of `bits` is read at most once during the iteration.
+    pub fn new_from(bits: &'a B, len: usize, from: usize) -> Self {
+        let word_idx = from / BITS;
+        let word = if word_idx < bits.as_ref().len() {
+            unsafe { *bits.as_ref().get_unchecked(word_idx) & (usize::MAX << (from % BITS)) }
+        } else {
+            0
+        };
+        Self {
+            bits,
+            len,
+            word_idx,
+            word,
+        }
+    }
 }
 
 impl<'a, B: AsRef<[usize]>> Iterator for OnesIterator<'a, B> {
@@ -575,7 +1022,7 @@ impl<'a, B: AsRef<[usize]>> Iterator for OnesIterator<'a, B> {
         // find the next word with ones
         while self.word == 0 {
             self.word_idx += 1;
-            if self.word_idx == self.bits.as_ref().len() {
+            if self.word_idx >= self.bits.as_ref().len() {
                 return None;
             }
             self.word = unsafe { *self.bits.as_ref().get_unchecked(self.word_idx) };
@@ -662,6 +1109,257 @@ impl<B: AsRef<[usize]>> BitVec<B> {
     pub fn iter_zeros(&self) -> ZerosIterator<B> {
         ZerosIterator::new(&self.bits, self.len)
     }
+
+    /// Returns an immutable view on the bits in the given range.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the range is decreasing or if its end is
+    /// greater than the length of the bit vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let b = bit_vec![0, 1, 0, 1, 1, 0, 1, 0];
+    /// let s = b.slice(3..7);
+    /// assert_eq!(s.len(), 4);
+    /// assert_eq!(s[0], true);
+    /// assert_eq!(s[2], false);
+    /// assert_eq!(s.count_ones(), 3);
+    /// assert_eq!(s.iter().collect::<Vec<_>>(), vec![true, true, false, true]);
+    /// ```
+    pub fn slice(&self, range: Range<usize>) -> BitVecSlice<'_, B> {
+        if range.start > range.end {
+            panic!("Decreasing range: {} > {}", range.start, range.end);
+        }
+        if range.end > self.len {
+            panic!("Range end out of bounds: {} > {}", range.end, self.len);
+        }
+        BitVecSlice {
+            bits: &self.bits,
+            start: range.start,
+            len: range.end - range.start,
+        }
+    }
+
+    /// Builds a lightweight selection index on this bit vector recording the
+    /// position of every 2<sup>`log2_ones_per_sample`</sup>-th one.
+    ///
+    /// [`select`](BitVecSelect::select) jumps to the closest preceding sample
+    /// and then scans the bit vector, so the index provides ad hoc selection
+    /// without turning the bit vector into a dedicated structure such as
+    /// [`SelectAdapt`](crate::rank_sel::SelectAdapt). Larger values of
+    /// `log2_ones_per_sample` yield a smaller index but slower selection.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `log2_ones_per_sample` is not smaller than
+    /// [`usize::BITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let b = bit_vec![0, 1, 0, 1, 1, 0, 1, 0];
+    /// let select = b.build_select_index(1);
+    /// assert_eq!(select.num_ones(), 4);
+    /// assert_eq!(select.select(0), Some(1));
+    /// assert_eq!(select.select(3), Some(6));
+    /// assert_eq!(select.select(4), None);
+    /// ```
+    pub fn build_select_index(&self, log2_ones_per_sample: usize) -> BitVecSelect<'_, B> {
+        assert!(
+            log2_ones_per_sample < usize::BITS as usize,
+            "The base-2 logarithm of the number of ones per sample ({}) must be smaller than {}",
+            log2_ones_per_sample,
+            usize::BITS
+        );
+        let mask = (1 << log2_ones_per_sample) - 1;
+        let mut samples = Vec::new();
+        let mut num_ones = 0;
+        for pos in self.iter_ones() {
+            if num_ones & mask == 0 {
+                samples.push(pos);
+            }
+            num_ones += 1;
+        }
+        BitVecSelect {
+            bits: self,
+            log2_ones_per_sample,
+            samples,
+            num_ones,
+        }
+    }
+}
+
+/// A lightweight selection index on a [`BitVec`].
+///
+/// Instances are returned by [`BitVec::build_select_index`].
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct BitVecSelect<'a, B> {
+    bits: &'a BitVec<B>,
+    log2_ones_per_sample: usize,
+    /// The position of the ones of rank multiple of
+    /// 2<sup>`log2_ones_per_sample`</sup>.
+    samples: Vec<usize>,
+    num_ones: usize,
+}
+
+impl<B: AsRef<[usize]>> BitVecSelect<'_, B> {
+    /// Returns the number of ones in the underlying bit vector.
+    #[inline(always)]
+    pub fn num_ones(&self) -> usize {
+        self.num_ones
+    }
+
+    /// Returns the position of the one of given rank, or `None` if no such
+    /// bit exists.
+    pub fn select(&self, rank: usize) -> Option<usize> {
+        if rank >= self.num_ones {
+            return None;
+        }
+        let sample = rank >> self.log2_ones_per_sample;
+        // SAFETY: the sample is the position of the one of rank
+        // sample << log2_ones_per_sample, which is at most rank
+        Some(unsafe {
+            self.bits.select_hinted(
+                rank,
+                self.samples[sample],
+                sample << self.log2_ones_per_sample,
+            )
+        })
+    }
+}
+
+/// An immutable view on a range of bits of a [`BitVec`].
+///
+/// Positions are relative to the start of the range, so `slice(a..b)[i]` is
+/// the same as `bit_vec[a + i]`. Instances are returned by
+/// [`BitVec::slice`].
+#[derive(Debug, MemDbg, MemSize)]
+pub struct BitVecSlice<'a, B> {
+    bits: &'a B,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, B> Clone for BitVecSlice<'a, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, B> Copy for BitVecSlice<'a, B> {}
+
+impl<'a, B: AsRef<[usize]>> BitVecSlice<'a, B> {
+    /// Returns the number of bits in the view.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        panic_if_out_of_bounds!(index, self.len);
+        unsafe { self.get_unchecked(index) }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be between 0 (included) and [`BitVecSlice::len`]
+    /// (excluded).
+    pub unsafe fn get_unchecked(&self, index: usize) -> bool {
+        let index = self.start + index;
+        let word = self.bits.as_ref().get_unchecked(index / BITS);
+        (word >> (index % BITS)) & 1 != 0
+    }
+
+    /// Returns an iterator over the bits of the view.
+    pub fn iter(&self) -> BitIterator<'a, B> {
+        BitIterator {
+            bits: self.bits,
+            len: self.start + self.len,
+            next_bit_pos: self.start,
+        }
+    }
+
+    /// Returns an immutable view on the bits in the given range of this
+    /// view.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the range is decreasing or if its end is
+    /// greater than the length of this view.
+    pub fn slice(&self, range: Range<usize>) -> BitVecSlice<'a, B> {
+        if range.start > range.end {
+            panic!("Decreasing range: {} > {}", range.start, range.end);
+        }
+        if range.end > self.len {
+            panic!("Range end out of bounds: {} > {}", range.end, self.len);
+        }
+        BitVecSlice {
+            bits: self.bits,
+            start: self.start + range.start,
+            len: range.end - range.start,
+        }
+    }
+}
+
+impl<'a, B> BitLength for BitVecSlice<'a, B> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, B: AsRef<[usize]>> BitCount for BitVecSlice<'a, B> {
+    fn count_ones(&self) -> usize {
+        if self.len == 0 {
+            return 0;
+        }
+        let bits = self.bits.as_ref();
+        let end = self.start + self.len;
+        let (start_word, start_bit) = (self.start / BITS, self.start % BITS);
+        let (end_word, end_bit) = (end / BITS, end % BITS);
+
+        if start_word == end_word {
+            // Here 0 < end_bit - start_bit < BITS
+            return ((bits[start_word] >> start_bit) & ((1 << (end_bit - start_bit)) - 1))
+                .count_ones() as usize;
+        }
+
+        let mut num_ones = (bits[start_word] >> start_bit).count_ones() as usize
+            + popcount_slice(&bits[start_word + 1..end_word]);
+        if end_bit != 0 {
+            num_ones += (bits[end_word] << (BITS - end_bit)).count_ones() as usize;
+        }
+        num_ones
+    }
+}
+
+impl<'a, B: AsRef<[usize]>> Index<usize> for BitVecSlice<'a, B> {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            false => &false,
+            true => &true,
+        }
+    }
+}
+
+impl<'a, B: AsRef<[usize]>> IntoIterator for &BitVecSlice<'a, B> {
+    type IntoIter = BitIterator<'a, B>;
+    type Item = bool;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 #[derive(Debug, Clone, MemDbg, MemSize)]
@@ -775,6 +1473,169 @@ impl<B: AsRef<[AtomicUsize]>> AtomicBitVec<B> {
         }
     }
 
+    /// Set all bits in the given range to the given value.
+    ///
+    /// Differently from [`fill`](AtomicBitVec::fill), this method can be
+    /// called concurrently. Words entirely contained in the range are
+    /// stored directly, as a whole-word write cannot interfere with
+    /// concurrent modifications of other words; the (at most two) words
+    /// containing the boundaries of the range are instead modified using an
+    /// atomic read-modify-write operation, so concurrent modifications of
+    /// the bits outside the range are preserved.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the range is not contained in the bit vector.
+    pub fn set_range_atomic(&self, range: Range<usize>, value: bool, ordering: Ordering) {
+        assert!(
+            range.start <= range.end,
+            "Range start {} is greater than range end {}",
+            range.start,
+            range.end
+        );
+        if range.end > self.len {
+            panic!("Range end out of bounds: {} > {}", range.end, self.len);
+        }
+        if range.is_empty() {
+            return;
+        }
+
+        let bits = self.bits.as_ref();
+        let start_word = range.start / BITS;
+        let start_bit = range.start % BITS;
+        let end_word = range.end / BITS;
+        let end_bit = range.end % BITS;
+
+        let update = |word: &AtomicUsize, mask: usize| {
+            if value {
+                word.fetch_or(mask, ordering);
+            } else {
+                word.fetch_and(!mask, ordering);
+            }
+        };
+
+        if start_word == end_word {
+            // The range is strictly contained in a word
+            update(
+                &bits[start_word],
+                ((1 << (end_bit - start_bit)) - 1) << start_bit,
+            );
+            return;
+        }
+
+        let mut first_full_word = start_word;
+        if start_bit != 0 {
+            update(&bits[start_word], !0 << start_bit);
+            first_full_word += 1;
+        }
+
+        let word_value = if value { !0 } else { 0 };
+        bits[first_full_word..end_word]
+            .iter()
+            .for_each(|x| x.store(word_value, ordering));
+
+        if end_bit != 0 {
+            update(&bits[end_word], (1 << end_bit) - 1);
+        }
+    }
+
+    /// Returns a checksum of the content of the bit vector.
+    ///
+    /// The result is the same as that of
+    /// [`BitVec::checksum`](BitVec::checksum) on the same content.
+    pub fn checksum(&self, ordering: Ordering) -> u64 {
+        let bits = self.bits.as_ref();
+        checksum(self.len, |i| bits[i].load(ordering))
+    }
+
+    /// Returns true if the [checksum](AtomicBitVec::checksum) of the bit
+    /// vector is equal to `expected`.
+    pub fn verify(&self, expected: u64, ordering: Ordering) -> bool {
+        self.checksum(ordering) == expected
+    }
+
+    /// Returns the number of ones preceding the specified position.
+    ///
+    /// This method scans the words of the bit vector up to `pos`, so it takes
+    /// linear time, but it does not require to convert the bit vector to a
+    /// [`BitVec`]. All words are loaded using `ordering`: if the bit vector is
+    /// modified concurrently, the result is the rank in some interleaving of
+    /// the word loads and of the modifications, but which one is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is greater than the length of the bit vector.
+    pub fn rank_atomic(&self, pos: usize, ordering: Ordering) -> usize {
+        if pos > self.len {
+            panic!("Bit index out of bounds: {} > {}", pos, self.len);
+        }
+        let word_pos = pos / BITS;
+        let bit_pos = pos % BITS;
+        let bits = self.bits.as_ref();
+
+        let mut rank = bits[..word_pos]
+            .iter()
+            .map(|x| x.load(ordering).count_ones() as usize)
+            .sum();
+
+        if bit_pos != 0 {
+            rank += (bits[word_pos].load(ordering) << (BITS - bit_pos)).count_ones() as usize;
+        }
+
+        rank
+    }
+
+    /// Returns an estimate of the number of ones computed by sampling
+    /// `samples` words.
+    ///
+    /// The sampled words are evenly spaced, and they are loaded using
+    /// [`Ordering::Relaxed`] without fences, so this method is much cheaper
+    /// than [`count_ones`](BitCount::count_ones) and it is suitable, for
+    /// example, for progress reporting while the bit vector is modified
+    /// concurrently. If `samples` is at least the number of words the result
+    /// is exact (in the absence of concurrent modifications).
+    ///
+    /// The estimate is obtained by multiplying the density of ones in the
+    /// sampled words by the length. If the ones are uniformly distributed
+    /// with density *p*, the standard error of the estimate is about
+    /// `len()` · √(*p*(1 − *p*) / (64 · `samples`)), that is, the relative
+    /// standard error is √((1 − *p*) / (64 · *p* · `samples`)). For bit vectors
+    /// whose ones are clustered the error can be much larger.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `samples` is zero.
+    pub fn estimate_count_ones(&self, samples: usize) -> usize {
+        assert!(samples != 0, "The number of samples must be positive");
+        let bits = self.bits.as_ref();
+        let num_words = self.len.div_ceil(BITS);
+        if num_words == 0 {
+            return 0;
+        }
+        let residual = self.len % BITS;
+        let samples = samples.min(num_words);
+
+        let mut sampled_ones = 0;
+        let mut sampled_bits = 0;
+        for i in 0..samples {
+            let word_pos = (i as u128 * num_words as u128 / samples as u128) as usize;
+            let word = bits[word_pos].load(Ordering::Relaxed);
+            if word_pos == num_words - 1 && residual != 0 {
+                sampled_ones += (word << (BITS - residual)).count_ones() as usize;
+                sampled_bits += residual;
+            } else {
+                sampled_ones += word.count_ones() as usize;
+                sampled_bits += BITS;
+            }
+        }
+
+        if samples == num_words {
+            sampled_ones
+        } else {
+            (sampled_ones as f64 * self.len as f64 / sampled_bits as f64).round() as usize
+        }
+    }
+
     /// Set all bits to zero.
     pub fn reset(&mut self, ordering: Ordering) {
         self.fill(false, ordering);
@@ -812,17 +1673,19 @@ impl AtomicBitVec<Vec<AtomicUsize>> {
 
     /// Creates a new atomic bit vector of length `len` initialized to `value`.
     pub fn with_value(len: usize, value: bool) -> Self {
-        let n_of_words = (len + BITS - 1) / BITS;
-        let extra_bits = (n_of_words * BITS) - len;
         let word_value = if value { !0 } else { 0 };
-        let mut bits = (0..n_of_words)
-            .map(|_| AtomicUsize::new(word_value))
-            .collect::<Vec<_>>();
-        if extra_bits > 0 {
-            let last_word_value = word_value >> extra_bits;
-            bits[n_of_words - 1] = AtomicUsize::new(last_word_value);
+        let mut bit_vec = Self {
+            bits: (0..len.div_ceil(BITS))
+                .map(|_| AtomicUsize::new(word_value))
+                .collect::<Vec<_>>(),
+            len,
+        };
+        let padding_bits = bit_vec.padding_bits();
+        if padding_bits > 0 {
+            let num_words = bit_vec.num_words();
+            bit_vec.bits[num_words - 1] = AtomicUsize::new(word_value >> padding_bits);
         }
-        Self { bits, len }
+        bit_vec
     }
 }
 
@@ -968,6 +1831,104 @@ impl<W> From<BitVec<Box<[W]>>> for BitVec<Vec<W>> {
     }
 }
 
+// Bit vectors backed by reference-counted slices can be cloned at no cost, so
+// several structures (e.g., a ranking structure and a selection structure)
+// can be built over the same bits without copying them.
+
+/// Converts a bit vector into one backed by a reference-counted slice, so
+/// that clones share the bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use sux::prelude::*;
+///
+/// let bits: BitVec<Arc<[usize]>> = bit_vec![1, 0, 1, 1].into();
+/// let rank9 = Rank9::new(bits.clone());
+/// let select = SelectAdaptConst::<_, _>::new(AddNumBits::from(bits.clone()));
+/// assert_eq!(rank9.rank(2), 1);
+/// assert_eq!(select.select(2), Some(3));
+/// ```
+impl<W> From<BitVec<Vec<W>>> for BitVec<Arc<[W]>> {
+    fn from(value: BitVec<Vec<W>>) -> Self {
+        BitVec {
+            bits: value.bits.into(),
+            len: value.len,
+        }
+    }
+}
+
+/// Converts a bit vector into one backed by a reference-counted slice, so
+/// that clones share the bits.
+impl<W> From<BitVec<Box<[W]>>> for BitVec<Arc<[W]>> {
+    fn from(value: BitVec<Box<[W]>>) -> Self {
+        BitVec {
+            bits: value.bits.into(),
+            len: value.len,
+        }
+    }
+}
+
+// On 64-bit platforms, bit vectors backed by u64 words (e.g., coming from
+// other libraries or from files) can be converted at no cost to bit vectors
+// backed by usize words, which are used by all rank/select structures.
+
+#[cfg(target_pointer_width = "64")]
+impl From<BitVec<Vec<u64>>> for BitVec<Vec<usize>> {
+    fn from(value: BitVec<Vec<u64>>) -> Self {
+        BitVec {
+            // In-place collection: no allocation takes place
+            bits: value.bits.into_iter().map(|w| w as usize).collect(),
+            len: value.len,
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl From<BitVec<Box<[u64]>>> for BitVec<Box<[usize]>> {
+    fn from(value: BitVec<Box<[u64]>>) -> Self {
+        BitVec {
+            bits: value
+                .bits
+                .into_vec()
+                .into_iter()
+                .map(|w| w as usize)
+                .collect(),
+            len: value.len,
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl<'a> From<BitVec<&'a [u64]>> for BitVec<&'a [usize]> {
+    fn from(value: BitVec<&'a [u64]>) -> Self {
+        BitVec {
+            // SAFETY: u64 and usize have the same size and alignment
+            bits: unsafe {
+                core::slice::from_raw_parts(value.bits.as_ptr() as *const usize, value.bits.len())
+            },
+            len: value.len,
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl<'a> From<BitVec<&'a mut [u64]>> for BitVec<&'a mut [usize]> {
+    fn from(value: BitVec<&'a mut [u64]>) -> Self {
+        BitVec {
+            // SAFETY: u64 and usize have the same size and alignment
+            bits: unsafe {
+                core::slice::from_raw_parts_mut(
+                    value.bits.as_mut_ptr() as *mut usize,
+                    value.bits.len(),
+                )
+            },
+            len: value.len,
+        }
+    }
+}
+
 impl<W, B: AsRef<[W]>> AsRef<[W]> for BitVec<B> {
     #[inline(always)]
     fn as_ref(&self) -> &[W] {
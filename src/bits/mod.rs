@@ -5,8 +5,12 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
-//! Structures for [bit vectors](`bit_vec`) and
-//! [vectors of values of bounded bit width](`bit_field_vec`).
+//! Structures for [bit vectors](`bit_vec`), [fixed-size bit
+//! arrays](`bit_array`), and [vectors of values of bounded bit
+//! width](`bit_field_vec`).
+
+pub mod bit_array;
+pub use bit_array::*;
 
 pub mod bit_field_vec;
 pub use bit_field_vec::*;
@@ -81,8 +81,11 @@ pub type EfSeqDict = EliasFano<
 
 use crate::prelude::*;
 use crate::traits::bit_field_slice::*;
+use crate::utils::{decode_int, encode_int};
+use anyhow::{ensure, Context};
 use core::sync::atomic::Ordering;
 use epserde::*;
+use lender::{for_, IntoLender, Lending};
 use mem_dbg::*;
 use std::borrow::Borrow;
 
@@ -235,6 +238,38 @@ impl<H, L> EliasFano<H, L> {
         self.n
     }
 
+    /// Returns the upper bound on the values used to build the sequence.
+    #[inline(always)]
+    pub fn u(&self) -> usize {
+        self.u
+    }
+
+    /// Returns the number of lower bits of each value stored explicitly in
+    /// the [low bits](EliasFano::low_bits).
+    ///
+    /// This number is zero when the upper bound is smaller than twice the
+    /// number of values: in this case the low bits have width zero, and
+    /// values are represented by their high bits only.
+    #[inline(always)]
+    pub fn l(&self) -> usize {
+        self.l
+    }
+
+    /// Returns a reference to the high-bits array, which stores the upper
+    /// bits of the `i`-th value in unary code by setting the bit of position
+    /// (`value` >> [`l`](EliasFano::l)) + `i`.
+    #[inline(always)]
+    pub fn high_bits(&self) -> &H {
+        &self.high_bits
+    }
+
+    /// Returns a reference to the low-bits array, which stores the lower
+    /// [`l`](EliasFano::l) bits of each value.
+    #[inline(always)]
+    pub fn low_bits(&self) -> &L {
+        &self.low_bits
+    }
+
     /// Replaces the high bits.
     ///
     /// # Safety
@@ -279,6 +314,270 @@ impl<H: AsRef<[usize]>, L: BitFieldSlice<usize>> Types for EliasFano<H, L> {
     type Input = usize;
 }
 
+impl<H: SelectUnchecked, L: BitFieldSlice<usize>> EliasFano<H, L> {
+    /// Returns the upper and lower part of the value of given index, that is,
+    /// a pair `(high, low)` such that the value is `(high <<`
+    /// [`l`](EliasFano::l)`) | low`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the length of
+    /// the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let mut efb = EliasFanoBuilder::new(4, 100);
+    /// efb.extend([3, 5, 42, 100]);
+    /// let ef = efb.build_with_seq();
+    /// let (high, low) = ef.decompose(2);
+    /// assert_eq!((high << ef.l()) | low, 42);
+    /// ```
+    pub fn decompose(&self, index: usize) -> (usize, usize) {
+        if index >= self.n {
+            panic!("Index out of bounds: {} >= {}", index, self.n);
+        }
+        // SAFETY: index is within bounds
+        unsafe {
+            (
+                self.high_bits.select_unchecked(index) - index,
+                self.low_bits.get_unchecked(index),
+            )
+        }
+    }
+}
+
+impl<H: SelectZeroUnchecked, L> EliasFano<H, L> {
+    /// Returns the position of the `k`-th zero (starting from zero) of the
+    /// [high bits](EliasFano::high_bits), or `None` if there is no such zero.
+    ///
+    /// The high bits contain a one for each value, followed by a zero for
+    /// each possible value of the upper part (from 0 to
+    /// [`u`](EliasFano::u)` >> `[`l`](EliasFano::l), included), so the ones
+    /// between the `(k − 1)`-th zero and the `k`-th zero correspond to the
+    /// values whose upper part is `k`. In particular, the number of values
+    /// whose upper part is at most `k` is the result minus `k`.
+    ///
+    /// Since the number of zeros is known, this method needs just a
+    /// [`SelectZeroUnchecked`] implementation on the high bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let mut efb = EliasFanoBuilder::new(4, 15);
+    /// efb.extend([1, 2, 9, 15]);
+    /// let ef = efb.build_with_dict();
+    /// assert_eq!(ef.l(), 1);
+    /// // Upper parts are 0, 1, 4, 7: the high bits are 10 10 0 0 10 0 0 10
+    /// assert_eq!(ef.high_select_zero(0), Some(1));
+    /// assert_eq!(ef.high_select_zero(1), Some(3));
+    /// assert_eq!(ef.high_select_zero(4), Some(7));
+    /// assert_eq!(ef.high_select_zero(7), Some(11));
+    /// assert_eq!(ef.high_select_zero(8), None);
+    /// ```
+    pub fn high_select_zero(&self, k: usize) -> Option<usize> {
+        if k > self.u >> self.l {
+            None
+        } else {
+            // SAFETY: the high bits contain (u >> l) + 1 zeros
+            Some(unsafe { self.high_bits.select_zero_unchecked(k) })
+        }
+    }
+}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFano<H, L> {
+    /// Returns the `k`-th integer (starting from zero) in [0..[`u`](EliasFano::u))
+    /// that is not a value of the sequence, or `None` if there is no such
+    /// integer.
+    ///
+    /// The sequence must be strictly increasing (i.e., it must represent a
+    /// set); otherwise, the result is unspecified.
+    ///
+    /// Since the number of values smaller than the `i`-th value is `i`, the
+    /// number of integers not in the sequence that are smaller than the
+    /// `i`-th value is the value minus `i`, which is nondecreasing in `i`.
+    /// The result is thus computed by a binary search using [`get`](IndexedSeq::get).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let mut efb = EliasFanoBuilder::new(2, 6);
+    /// efb.extend([1, 3]);
+    /// let ef = efb.build_with_seq();
+    /// assert_eq!(ef.select_complement(0), Some(0));
+    /// assert_eq!(ef.select_complement(1), Some(2));
+    /// assert_eq!(ef.select_complement(2), Some(4));
+    /// assert_eq!(ef.select_complement(3), Some(5));
+    /// assert_eq!(ef.select_complement(4), None);
+    /// ```
+    pub fn select_complement(&self, k: usize) -> Option<usize> {
+        // The only value that can be outside [0..u) is the last one
+        let n = if self.n != 0 && unsafe { self.get_unchecked(self.n - 1) } >= self.u {
+            self.n - 1
+        } else {
+            self.n
+        };
+        if k >= self.u - n {
+            return None;
+        }
+        // Find the number of values smaller than the result, that is, the
+        // number of values preceded by at most k missing integers
+        let (mut lo, mut hi) = (0, n);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if unsafe { self.get_unchecked(mid) } - mid <= k {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(k + lo)
+    }
+}
+
+/// A view of a strictly increasing Elias–Fano sequence as the bit vector of
+/// length [`u`](EliasFano::u)` + 1` having ones exactly at the positions
+/// given by the values.
+///
+/// Selection on ones is access, and selection on zeros is selection in the
+/// complement of the set of values. This makes it possible to use an
+/// Elias–Fano representation wherever a [`Select`] or [`SelectZero`]
+/// structure is expected.
+///
+/// Since an [`EliasFano`] structure may contain repeated values, which do not
+/// correspond to any bit vector, [`new`](EliasFanoBits::new) checks that the
+/// sequence is strictly increasing.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::dict::elias_fano::EliasFanoBits;
+///
+/// let mut efb = EliasFanoBuilder::new(3, 7);
+/// efb.extend([1, 4, 5]);
+/// let bits = EliasFanoBits::new(efb.build_with_seq())?;
+/// assert_eq!(BitLength::len(&bits), 8);
+/// assert_eq!(bits.select(1), Some(4));
+/// assert_eq!(bits.select(3), None);
+/// assert_eq!(bits.select_zero(2), Some(3));
+/// assert_eq!(bits.select_zero(4), Some(7));
+/// assert_eq!(bits.select_zero(5), None);
+///
+/// // Repeated values are rejected
+/// let mut efb = EliasFanoBuilder::new(3, 7);
+/// efb.extend([1, 4, 4]);
+/// assert!(EliasFanoBits::new(efb.build_with_seq()).is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct EliasFanoBits<
+    H = SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>,
+    L = BitFieldVec<usize, Box<[usize]>>,
+> {
+    ef: EliasFano<H, L>,
+}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> EliasFanoBits<H, L> {
+    /// Creates a bit-vector view of the given sequence, returning an error if
+    /// the sequence is not strictly increasing.
+    ///
+    /// This method scans the whole sequence.
+    pub fn new(ef: EliasFano<H, L>) -> anyhow::Result<Self> {
+        if ef.n != 0 {
+            let mut prev = unsafe { ef.get_unchecked(0) };
+            for index in 1..ef.n {
+                let value = unsafe { ef.get_unchecked(index) };
+                ensure!(
+                    prev < value,
+                    "The sequence is not strictly increasing: value {} at index {} is not larger than the previous one",
+                    value,
+                    index
+                );
+                prev = value;
+            }
+        }
+        Ok(Self { ef })
+    }
+}
+
+impl<H, L> EliasFanoBits<H, L> {
+    /// Returns the underlying Elias–Fano structure.
+    pub fn into_inner(self) -> EliasFano<H, L> {
+        self.ef
+    }
+}
+
+impl<H, L> AsRef<EliasFano<H, L>> for EliasFanoBits<H, L> {
+    #[inline(always)]
+    fn as_ref(&self) -> &EliasFano<H, L> {
+        &self.ef
+    }
+}
+
+impl<H, L> BitLength for EliasFanoBits<H, L> {
+    /// Returns the [upper bound](EliasFano::u) plus one, that is, the length
+    /// of the bit vector represented by the sequence.
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.ef.u + 1
+    }
+}
+
+impl<H, L> NumBits for EliasFanoBits<H, L> {
+    /// Returns the number of values in the sequence.
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.ef.n
+    }
+}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> SelectUnchecked
+    for EliasFanoBits<H, L>
+{
+    #[inline(always)]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.ef.get_unchecked(rank)
+    }
+}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> Select for EliasFanoBits<H, L> {}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> SelectZeroUnchecked
+    for EliasFanoBits<H, L>
+{
+    /// Returns the `rank`-th integer in [0..[`u`](EliasFano::u)] that is not
+    /// a value of the sequence.
+    ///
+    /// Since the sequence is strictly increasing, the number of integers not
+    /// in the sequence that are smaller than the `i`-th value is the value
+    /// minus `i`, which is nondecreasing in `i`, so the result is computed by
+    /// a binary search.
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        let (mut lo, mut hi) = (0, self.ef.n);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.ef.get_unchecked(mid) - mid <= rank {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        rank + lo
+    }
+}
+
+impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> SelectZero
+    for EliasFanoBits<H, L>
+{
+}
+
 impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> IndexedSeq for EliasFano<H, L> {
     #[inline]
     fn len(&self) -> usize {
@@ -291,6 +590,66 @@ impl<H: AsRef<[usize]> + SelectUnchecked, L: BitFieldSlice<usize>> IndexedSeq fo
         let low_bits = self.low_bits.get_unchecked(index);
         (high_bits << self.l) | low_bits
     }
+
+    /// Samples the values with a single sequential scan of the high bits,
+    /// without using the selection structure.
+    fn sample(&self, step: usize) -> Vec<usize> {
+        assert!(step != 0, "The sampling step must be positive");
+        let mut result = Vec::with_capacity(self.n.div_ceil(step));
+        let high_bits = self.high_bits.as_ref();
+        let mut index = 0;
+        let mut next = 0;
+        for (word_idx, &word) in high_bits.iter().enumerate() {
+            if next >= self.n {
+                break;
+            }
+            let ones = word.count_ones() as usize;
+            if index + ones <= next {
+                index += ones;
+                continue;
+            }
+            let mut window = word;
+            while window != 0 && next < self.n {
+                let bit_pos = word_idx * usize::BITS as usize + window.trailing_zeros() as usize;
+                if index == next {
+                    let low_bits = unsafe { self.low_bits.get_unchecked(index) };
+                    result.push(((bit_pos - index) << self.l) | low_bits);
+                    next += step;
+                }
+                window &= window - 1;
+                index += 1;
+            }
+        }
+        result
+    }
+
+    /// Enumerates the values with a single sequential scan of the high bits,
+    /// without using the selection structure.
+    fn enumerate_values(&self) -> impl Iterator<Item = (usize, Self::Output)> + '_
+    where
+        Self: Sized,
+    {
+        let high_bits = self.high_bits.as_ref();
+        (0..high_bits.len())
+            .flat_map(move |word_idx| {
+                let mut window = high_bits[word_idx];
+                core::iter::from_fn(move || {
+                    if window == 0 {
+                        return None;
+                    }
+                    let bit_pos =
+                        word_idx * usize::BITS as usize + window.trailing_zeros() as usize;
+                    window &= window - 1;
+                    Some(bit_pos)
+                })
+            })
+            .take(self.n)
+            .enumerate()
+            .map(move |(index, bit_pos)| {
+                let low_bits = unsafe { self.low_bits.get_unchecked(index) };
+                (index, ((bit_pos - index) << self.l) | low_bits)
+            })
+    }
 }
 
 impl<H: AsRef<[usize]> + SelectZeroUnchecked, L: BitFieldSlice<usize>> IndexedDict
@@ -460,11 +819,11 @@ where
             }
 
             if STRICT {
-                if lower_bits < value & ((1 << self.l) - 1) {
+                if lower_bits < value & low_mask(self.l) {
                     return (rank, (bit_pos - rank) << self.l | lower_bits);
                 }
             } else {
-                if lower_bits <= value & ((1 << self.l) - 1) {
+                if lower_bits <= value & low_mask(self.l) {
                     return (rank, (bit_pos - rank) << self.l | lower_bits);
                 }
             }
@@ -482,6 +841,149 @@ where
 {
 }
 
+impl<H: AsRef<[usize]> + SelectUnchecked + SelectZeroUnchecked, L: BitFieldSlice<usize>>
+    EliasFano<H, L>
+where
+    for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
+{
+    /// Returns the [predecessor](Pred::pred) and the [successor](Succ::succ)
+    /// of the given value, that is, the greatest value in the sequence that is
+    /// less than or equal to `value` and the least value in the sequence that
+    /// is greater than or equal to `value`.
+    ///
+    /// If `value` is in the sequence, both elements of the pair are equal to
+    /// `value`. Differently from calling [`pred`](Pred::pred) and
+    /// [`succ`](Succ::succ), this method performs at most a single selection
+    /// on the high bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::prelude::*;
+    /// let mut efb = EliasFanoBuilder::new(4, 100);
+    /// for x in [10, 20, 30, 40] {
+    ///     efb.push(x);
+    /// }
+    /// let ef = efb.build_with_seq_and_dict();
+    /// assert_eq!(ef.neighbors(25), (Some(20), Some(30)));
+    /// assert_eq!(ef.neighbors(30), (Some(30), Some(30)));
+    /// assert_eq!(ef.neighbors(5), (None, Some(10)));
+    /// assert_eq!(ef.neighbors(50), (Some(40), None));
+    /// ```
+    pub fn neighbors(&self, value: usize) -> (Option<usize>, Option<usize>) {
+        let zeros_to_skip = value >> self.l;
+        // The high bits contain (u >> l) + 1 zeros, the last of which is
+        // their last bit, so if the high bits of value are larger than those
+        // of u all values are smaller than value.
+        let (bit_pos, first_rank) = if zeros_to_skip > self.u >> self.l {
+            (self.n + (self.u >> self.l) + 1, self.n)
+        } else if zeros_to_skip == 0 {
+            (0, 0)
+        } else {
+            let bit_pos = unsafe { self.high_bits.select_zero_unchecked(zeros_to_skip - 1) + 1 };
+            (bit_pos, bit_pos - zeros_to_skip)
+        };
+
+        let mut rank = first_rank;
+        let mut iter = self.low_bits.into_unchecked_iter_from(rank);
+        let mut word_idx = bit_pos / (usize::BITS as usize);
+        let mut prev = None;
+
+        // SAFETY: we read the high bits only when rank < n, so there is a one
+        // at or after the current position, and we are certainly iterating
+        // within the length of the arrays and within the range of the iterator.
+
+        let mut window = if rank < self.n {
+            let word = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
+            word & (usize::MAX << (bit_pos % (usize::BITS as usize)))
+        } else {
+            0
+        };
+
+        let succ = loop {
+            if rank == self.n {
+                break None;
+            }
+            while window == 0 {
+                word_idx += 1;
+                debug_assert!(word_idx < self.high_bits.as_ref().len());
+                window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
+            }
+            let bit_idx = window.trailing_zeros() as usize;
+            let high_bits = (word_idx * usize::BITS as usize) + bit_idx - rank;
+            let res = (high_bits << self.l) | unsafe { iter.next_unchecked() };
+            if res >= value {
+                break Some(res);
+            }
+            prev = Some(res);
+            window &= window - 1;
+            rank += 1;
+        };
+
+        if succ == Some(value) {
+            return (succ, succ);
+        }
+
+        let pred = prev.or_else(|| {
+            if first_rank == 0 {
+                return None;
+            }
+            // The predecessor is the last element with high bits smaller than
+            // those of value, so we scan backwards from bit_pos to find its
+            // high bits.
+            let mut word_idx = bit_pos / (usize::BITS as usize);
+            let bits_to_keep = bit_pos % (usize::BITS as usize);
+            let mut window = if bits_to_keep == 0 {
+                0
+            } else {
+                let word = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
+                word & !(usize::MAX << bits_to_keep)
+            };
+            while window == 0 {
+                word_idx -= 1;
+                window = unsafe { *self.high_bits.as_ref().get_unchecked(word_idx) };
+            }
+            let pos = word_idx * usize::BITS as usize + (usize::BITS as usize - 1)
+                - window.leading_zeros() as usize;
+            Some(
+                ((pos - (first_rank - 1)) << self.l)
+                    | unsafe { self.low_bits.get_unchecked(first_rank - 1) },
+            )
+        });
+
+        (pred, succ)
+    }
+
+    /// Returns an iterator on the values of the sequence contained in the range
+    /// `[lo..hi)`, in increasing order.
+    ///
+    /// The first value is found using a [successor](Succ::succ) query, and the
+    /// iteration stops at the first value greater than or equal to `hi`. The
+    /// iterator is empty if `lo` is greater than or equal to `hi`, or if
+    /// there are no values in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::prelude::*;
+    /// let mut efb = EliasFanoBuilder::new(5, 100);
+    /// for x in [10, 20, 30, 40, 50] {
+    ///     efb.push(x);
+    /// }
+    /// let ef = efb.build_with_seq_and_dict();
+    /// assert_eq!(ef.iter_range_values(15, 40).collect::<Vec<_>>(), vec![20, 30]);
+    /// assert_eq!(ef.iter_range_values(40, 1000).collect::<Vec<_>>(), vec![40, 50]);
+    /// assert_eq!(ef.iter_range_values(51, 1000).count(), 0);
+    /// ```
+    pub fn iter_range_values(&self, lo: usize, hi: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = if lo < hi { self.succ(lo) } else { None };
+        start
+            .into_iter()
+            .flat_map(move |(index, _)| self.iter_from(index))
+            .take_while(move |&x| x < hi)
+    }
+}
+
 impl<H: AsRef<[usize]>, L: BitFieldSlice<usize>> EliasFano<H, L>
 where
     for<'b> &'b L: IntoUncheckedIterator<Item = usize>,
@@ -490,6 +992,207 @@ where
     pub fn iter(&self) -> EliasFanoIterator<'_, H, L> {
         EliasFanoIterator::new(self)
     }
+
+    /// Returns an iterator on pairs given by the values of the sequence and
+    /// their gap from the previous value.
+    ///
+    /// The gap of the first value is the value itself, that is, the sequence
+    /// is treated as if it were preceded by a zero. Thus, the sum of the gaps
+    /// of the first `i` pairs is always the `i`-th value.
+    ///
+    /// Values and gaps are computed in a single scan of the high bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let ef: EliasFano = vec![3, 5, 5, 12].into();
+    /// let pairs = ef.iter_with_gaps().collect::<Vec<_>>();
+    /// assert_eq!(pairs, vec![(3, 3), (5, 2), (5, 0), (12, 7)]);
+    ///
+    /// let max_gap = ef.iter_with_gaps().map(|(_, gap)| gap).max();
+    /// assert_eq!(max_gap, Some(7));
+    /// ```
+    pub fn iter_with_gaps(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.iter().scan(0, |prev, value| {
+            let gap = value - *prev;
+            *prev = value;
+            Some((value, gap))
+        })
+    }
+
+    /// Returns an iterator on the values that appear in exactly one of two
+    /// sequences, in increasing order.
+    ///
+    /// The result is computed by a single linear merge of the iterators of the
+    /// two sequences. Since Elias–Fano sequences may contain repeated values,
+    /// they are treated as multisets: a value appearing `m` times in `a` and
+    /// `n` times in `b` is returned |`m` − `n`| times. In particular, if both
+    /// sequences are strictly increasing the result is the usual symmetric
+    /// difference of sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let a: EliasFano = vec![1, 3, 5, 5, 8].into();
+    /// let b: EliasFano = vec![3, 4, 5, 9].into();
+    /// let diff = EliasFano::symmetric_difference(&a, &b).collect::<Vec<_>>();
+    /// assert_eq!(diff, vec![1, 4, 5, 8, 9]);
+    /// ```
+    pub fn symmetric_difference<'a>(a: &'a Self, b: &'a Self) -> impl Iterator<Item = usize> + 'a {
+        let mut a = a.iter().peekable();
+        let mut b = b.iter().peekable();
+        core::iter::from_fn(move || loop {
+            match (a.peek().copied(), b.peek().copied()) {
+                (Some(x), Some(y)) => match x.cmp(&y) {
+                    core::cmp::Ordering::Less => return a.next(),
+                    core::cmp::Ordering::Greater => return b.next(),
+                    core::cmp::Ordering::Equal => {
+                        a.next();
+                        b.next();
+                    }
+                },
+                (Some(_), None) => return a.next(),
+                (None, _) => return b.next(),
+            }
+        })
+    }
+
+    /// Stores in `out`, for each value of `a`, the index in `b` of its
+    /// predecessor, that is, of the last value of `b` smaller than or equal to
+    /// it, or `None` if there is no such value.
+    ///
+    /// Since both sequences are monotone, the result is computed by a single
+    /// linear merge of the iterators of the two sequences, which is much
+    /// faster than |`a`| independent [predecessor queries](crate::traits::Pred)
+    /// when the sequences have similar lengths. Moreover, no selection
+    /// structure is needed. The previous content of `out` is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let a: EliasFano = vec![0, 3, 5, 9].into();
+    /// let b: EliasFano = vec![1, 3, 3, 7].into();
+    /// let mut out = vec![];
+    /// EliasFano::predecessor_join(&a, &b, &mut out);
+    /// assert_eq!(out, vec![None, Some(2), Some(2), Some(3)]);
+    /// ```
+    pub fn predecessor_join(a: &Self, b: &Self, out: &mut Vec<Option<usize>>) {
+        out.clear();
+        out.reserve(a.len());
+        let mut b = b.iter().peekable();
+        let mut index: usize = 0;
+        for x in a.iter() {
+            while b.next_if(|&y| y <= x).is_some() {
+                index += 1;
+            }
+            out.push(index.checked_sub(1));
+        }
+    }
+
+    /// Returns an iterator on the sorted merge of the values of this sequence
+    /// with those of a sorted iterator.
+    ///
+    /// No new structure is built: the merge is computed lazily, so the result
+    /// can be streamed, or encoded again into a different structure. Values
+    /// appearing in both sources are returned as many times as they appear
+    /// overall; in case of ties, values of this sequence come first.
+    ///
+    /// `other` must return its values in nondecreasing order, or the output
+    /// will not be sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let ef: EliasFano = vec![1, 4, 4, 10].into();
+    /// let merged = ef.merge_iter(vec![0, 4, 7, 12].into_iter());
+    /// assert_eq!(merged.collect::<Vec<_>>(), vec![0, 1, 4, 4, 4, 7, 10, 12]);
+    /// ```
+    pub fn merge_iter<'a>(
+        &'a self,
+        other: impl Iterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut a = self.iter().peekable();
+        let mut b = other.peekable();
+        core::iter::from_fn(move || match (a.peek(), b.peek()) {
+            (Some(&x), Some(&y)) => {
+                if x <= y {
+                    a.next()
+                } else {
+                    b.next()
+                }
+            }
+            (Some(_), None) => a.next(),
+            (None, _) => b.next(),
+        })
+    }
+
+    /// Returns the delta-VByte representation of the sequence, that is, the
+    /// concatenation of the [VByte](crate::utils::vbyte) codes of the gaps
+    /// between consecutive values (the first value is the gap from zero).
+    ///
+    /// This representation is a compact interchange format independent of
+    /// the memory layout of this structure: the sequence can be rebuilt by
+    /// [`EliasFano::from_delta_vbyte`] given the number of values and the
+    /// upper bound, which are not stored. Gaps smaller than 128 use a single
+    /// byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let ef: EliasFano = vec![1, 5, 5, 300].into();
+    /// assert_eq!(ef.to_delta_vbyte().len(), 5);
+    /// ```
+    pub fn to_delta_vbyte(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.n);
+        for (_, gap) in self.iter_with_gaps() {
+            encode_int(gap, &mut bytes);
+        }
+        bytes
+    }
+
+    /// Consumes this structure and returns a new one containing the values
+    /// transformed by a monotone function.
+    ///
+    /// Since `f` is assumed to be monotone (i.e., nondecreasing), the
+    /// transformed values are still monotone and can be encoded again with
+    /// upper bound `new_u`. Typical use cases are unit conversions or
+    /// offsetting.
+    ///
+    /// The result has no selection structure attached: see
+    /// [`EliasFano::map_high_bits`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the transformed values are not monotone or
+    /// if some transformed value is larger than `new_u`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let ef: EliasFano = vec![0, 2, 8, 10].into();
+    /// let ef = ef.map_monotone(|x| x * 2 + 5, 25);
+    /// assert_eq!(ef.u(), 25);
+    /// assert_eq!(ef.iter().collect::<Vec<_>>(), vec![5, 9, 21, 25]);
+    /// ```
+    pub fn map_monotone(self, f: impl Fn(usize) -> usize, new_u: usize) -> EliasFano {
+        let mut builder = EliasFanoBuilder::new(self.len(), new_u);
+        for value in self.iter() {
+            builder.push(f(value));
+        }
+        builder.build()
+    }
 }
 
 impl<'a, H: AsRef<[usize]>, L: BitFieldSlice<usize>> IntoIterator for &'a EliasFano<H, L>
@@ -671,6 +1374,150 @@ impl<A: AsRef<[usize]>> From<A> for EliasFano {
     }
 }
 
+impl EliasFano {
+    /// Builds an Elias–Fano representation of the `n` values smaller than or
+    /// equal to `u` lent by a [`Lender`](lender::Lender) of strings, such as a
+    /// [`LineLender`](crate::utils::LineLender), parsing each string as a
+    /// `usize`.
+    ///
+    /// Leading and trailing whitespace is ignored. The values are pushed
+    /// directly into an [`EliasFanoBuilder`], so no intermediate vector is
+    /// necessary. As in the case of [`EliasFanoBuilder::build`], the result
+    /// has no selection structure attached.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the lender returns an error, if a
+    /// string cannot be parsed, if the values are not monotone or larger than
+    /// `u`, or if the number of values is not `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    /// use sux::utils::LineLender;
+    ///
+    /// let lines = std::io::Cursor::new("1\n5\n5\n10\n");
+    /// let ef = EliasFano::from_lender(LineLender::new(lines), 4, 10)?;
+    /// assert_eq!(ef.iter().collect::<Vec<_>>(), vec![1, 5, 5, 10]);
+    ///
+    /// let lines = std::io::Cursor::new("1\n5\nfive\n10\n");
+    /// assert!(EliasFano::from_lender(LineLender::new(lines), 4, 10).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_lender<E, L: IntoLender>(into_lender: L, n: usize, u: usize) -> anyhow::Result<Self>
+    where
+        L::Lender: for<'lend> Lending<'lend, Lend = Result<&'lend str, E>>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut builder = EliasFanoBuilder::new(n, u);
+        let mut count = 0;
+        let mut last = 0;
+        for_!(line in into_lender {
+            let line = line?;
+            let value: usize = line
+                .trim()
+                .parse()
+                .with_context(|| format!("Cannot parse line {} ({:?})", count + 1, line))?;
+            ensure!(count < n, "Too many values (expected {})", n);
+            ensure!(value <= u, "Value too large at line {}: {} > {}", count + 1, value, u);
+            ensure!(
+                value >= last,
+                "The values provided are not monotone at line {}: {} < {}",
+                count + 1,
+                value,
+                last
+            );
+            // SAFETY: we checked the number of values, the upper bound, and monotonicity
+            unsafe { builder.push_unchecked(value) };
+            last = value;
+            count += 1;
+        });
+        ensure!(count == n, "Too few values: {} < {}", count, n);
+        Ok(builder.build())
+    }
+
+    /// Builds an Elias–Fano representation of the `n` values smaller than or
+    /// equal to `u` from their [delta-VByte
+    /// representation](EliasFano::to_delta_vbyte).
+    ///
+    /// As in the case of [`EliasFanoBuilder::build`], the result has no
+    /// selection structure attached.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if `bytes` does not contain exactly
+    /// `n` [VByte](crate::utils::vbyte) codes, or if some value is larger
+    /// than `u`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let ef: EliasFano = vec![1, 5, 5, 300].into();
+    /// let bytes = ef.to_delta_vbyte();
+    /// let ef = EliasFano::from_delta_vbyte(&bytes, 4, 300)?;
+    /// assert_eq!(ef.iter().collect::<Vec<_>>(), vec![1, 5, 5, 300]);
+    ///
+    /// assert!(EliasFano::from_delta_vbyte(&bytes, 4, 299).is_err());
+    /// assert!(EliasFano::from_delta_vbyte(&bytes, 5, 300).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_delta_vbyte(mut bytes: &[u8], n: usize, u: usize) -> anyhow::Result<Self> {
+        let mut builder = EliasFanoBuilder::new(n, u);
+        let mut last = 0_usize;
+        for i in 0..n {
+            ensure!(
+                !bytes.is_empty() && bytes.len() > bytes[0].leading_ones() as usize,
+                "Truncated code for value {} (expected {} values)",
+                i,
+                n
+            );
+            let (gap, tmp) = decode_int(bytes);
+            bytes = tmp;
+            let value = last.checked_add(gap);
+            ensure!(
+                value.is_some_and(|value| value <= u),
+                "Value too large at index {}: {} + {} > {}",
+                i,
+                last,
+                gap,
+                u
+            );
+            last = value.unwrap();
+            // SAFETY: we checked the number of values and the upper bound, and
+            // values are monotone by construction
+            unsafe { builder.push_unchecked(last) };
+        }
+        ensure!(
+            bytes.is_empty(),
+            "Spurious {} bytes after {} values",
+            bytes.len(),
+            n
+        );
+        Ok(builder.build())
+    }
+}
+
+/// Returns a mask with the lowest `l` bits set.
+///
+/// Unlike `(1 << l) - 1`, this function is well defined for `l` equal to the
+/// number of bits of a `usize`. When `l` is zero, the result is zero.
+#[inline(always)]
+fn low_mask(l: usize) -> usize {
+    if l == 0 {
+        0
+    } else {
+        usize::MAX >> (usize::BITS as usize - l)
+    }
+}
+
+/// The selection structure on the high bits built by
+/// [`EliasFanoBuilder::build_indexed`], whose first-level inventory is
+/// sampled by the builder while pushing values.
+type IndexedSelect = SelectAdaptConst<BitVec<Box<[usize]>>, Box<[usize]>, 12, 3>;
+
 /// A sequential builder for [`EliasFano`].
 ///
 /// After creating an instance, you can use [`EliasFanoBuilder::push`] to add
@@ -705,18 +1552,67 @@ pub struct EliasFanoBuilder {
     high_bits: BitVec,
     last_value: usize,
     count: usize,
+    /// The positions in the high bits of the ones whose rank is a multiple of
+    /// the number of ones per inventory entry of [`IndexedSelect`], which
+    /// are used by [`build_indexed`](EliasFanoBuilder::build_indexed).
+    positions: Vec<usize>,
 }
 
 impl EliasFanoBuilder {
     /// Creates a builder for an [`EliasFano`] containing
     /// `n` numbers smaller than or equal to `u`.
     pub fn new(n: usize, u: usize) -> Self {
-        let l = if u >= n {
-            (u as f64 / n as f64).log2().floor() as usize
+        // For an empty sequence there are no low bits to store. Note that
+        // ⌊log₂(u/n)⌋ = ⌊log₂⌊u/n⌋⌋, so l is always smaller than usize::BITS.
+        let l = if n != 0 && u >= n {
+            (u / n).ilog2() as usize
         } else {
             0
         };
 
+        Self::with_l(n, u, l)
+    }
+
+    /// Creates a builder for an [`EliasFano`] containing `n` numbers smaller
+    /// than or equal to `u` using `l` lower bits.
+    ///
+    /// The default choice of [`new`](EliasFanoBuilder::new), that is,
+    /// ⌊log₂(`u`/`n`)⌋, minimizes the worst-case space occupancy, but for
+    /// some distributions a different choice can compress better. A smaller
+    /// `l` yields fewer lower bits, but a longer high-bits array, which
+    /// contains `n` + ⌊`u`/2<sup>`l`</sup>⌋ + 1 bits; a larger `l` has the
+    /// opposite effect.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `l` is not smaller than [`usize::BITS`], or
+    /// if it is larger than the number of bits necessary to represent `u`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let mut efb = EliasFanoBuilder::with_l(4, 100, 0);
+    /// efb.extend([1, 10, 20, 100]);
+    /// let ef = efb.build_with_seq();
+    /// assert_eq!(ef.l(), 0);
+    /// assert_eq!(ef.get(2), 20);
+    /// ```
+    pub fn with_l(n: usize, u: usize, l: usize) -> Self {
+        assert!(
+            l < usize::BITS as usize,
+            "The number of lower bits ({}) must be smaller than {}",
+            l,
+            usize::BITS
+        );
+        assert!(
+            l <= (usize::BITS - u.leading_zeros()) as usize,
+            "The number of lower bits ({}) is larger than the number of bits of the upper bound {}",
+            l,
+            u
+        );
+
         Self {
             n,
             u,
@@ -725,6 +1621,7 @@ impl EliasFanoBuilder {
             high_bits: BitVec::new(n + (u >> l) + 1),
             last_value: 0,
             count: 0,
+            positions: Vec::with_capacity(n.div_ceil(IndexedSelect::ONES_PER_INVENTORY)),
         }
     }
 
@@ -756,11 +1653,14 @@ impl EliasFanoBuilder {
     /// Values passed to this function must be smaller than or equal `u` and must be monotone.
     /// Moreover, the function should not be called more than `n` times.
     pub unsafe fn push_unchecked(&mut self, value: usize) {
-        let low = value & ((1 << self.l) - 1);
+        let low = value & low_mask(self.l);
         self.low_bits.set(self.count, low);
 
         let high = (value >> self.l) + self.count;
         self.high_bits.set(high, true);
+        if self.count % IndexedSelect::ONES_PER_INVENTORY == 0 {
+            self.positions.push(high);
+        }
 
         self.count += 1;
         self.last_value = value;
@@ -799,6 +1699,39 @@ impl EliasFanoBuilder {
         unsafe { ef.map_high_bits(SelectAdaptConst::<_, _, 12, 3>::new) }
     }
 
+    /// Builds an Elias-Fano structure with constant-time access, using
+    /// default values, without scanning the high bits to find the positions
+    /// of the first-level inventory of the selection structure.
+    ///
+    /// The result is identical to that of
+    /// [`build_with_seq`](EliasFanoBuilder::build_with_seq), but the positions
+    /// of the ones indexed by the first-level inventory of the
+    /// [`SelectAdaptConst`] structure on the high bits are recorded while
+    /// pushing values, saving a full pass over the high bits (see
+    /// [`SelectAdaptConst::from_positions`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let mut efb = EliasFanoBuilder::new(4, 10);
+    /// efb.extend([0, 2, 8, 10]);
+    /// let ef = efb.build_indexed();
+    /// assert_eq!(ef.get(2), 8);
+    /// ```
+    pub fn build_indexed(mut self) -> EfSeq {
+        let num_ones = self.count;
+        let positions = core::mem::take(&mut self.positions);
+        let ef = self.build();
+        // SAFETY: the positions have been recorded while setting the high bits
+        unsafe {
+            ef.map_high_bits(|high_bits| {
+                IndexedSelect::from_positions(high_bits, num_ones, &positions)
+            })
+        }
+    }
+
     /// Builds an Elias-Fano structure with constant-time indexing, using
     /// default values.
     ///
@@ -872,8 +1805,10 @@ impl EliasFanoConcurrentBuilder {
     /// Creates a concurrent builder for a sequence containing `n` nonnegative
     /// numbers smaller than or equal to `u`.
     pub fn new(n: usize, u: usize) -> Self {
-        let l = if u >= n {
-            (u as f64 / n as f64).log2().floor() as usize
+        // For an empty sequence there are no low bits to store. Note that
+        // ⌊log₂(u/n)⌋ = ⌊log₂⌊u/n⌋⌋, so l is always smaller than usize::BITS.
+        let l = if n != 0 && u >= n {
+            (u / n).ilog2() as usize
         } else {
             0
         };
@@ -894,8 +1829,22 @@ impl EliasFanoConcurrentBuilder {
     /// - All values must be smaller than or equal to `u`.
     /// - All indices must be smaller than `n`.
     /// - You must call this function exactly `n` times.
+    ///
+    /// In debug mode, the second and third conditions are checked.
     pub unsafe fn set(&self, index: usize, value: usize) {
-        let low = value & ((1 << self.l) - 1);
+        debug_assert!(
+            index < self.n,
+            "Index out of bounds: {} >= {}",
+            index,
+            self.n
+        );
+        debug_assert!(
+            value <= self.u,
+            "Value {} is larger than the upper bound {}",
+            value,
+            self.u
+        );
+        let low = value & low_mask(self.l);
         // Note that the concurrency guarantees of BitFieldVec
         // are sufficient for us.
         self.low_bits
@@ -919,8 +1868,7 @@ impl EliasFanoConcurrentBuilder {
     pub fn build(self) -> EliasFano {
         let high_bits: BitVec<Vec<usize>> = self.high_bits.into();
         let high_bits: BitVec<Box<[usize]>> = high_bits.into();
-        let low_bits: BitFieldVec<usize, Vec<usize>> = self.low_bits.into();
-        let low_bits: BitFieldVec<usize, Box<[usize]>> = low_bits.into();
+        let low_bits: BitFieldVec<usize, Box<[usize]>> = self.low_bits.into_non_atomic().into();
         EliasFano {
             n: self.n,
             u: self.u,
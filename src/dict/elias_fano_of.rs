@@ -0,0 +1,230 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Elias–Fano representations of sequences of values of a given integer type.
+
+use crate::dict::elias_fano::{EfSeqDict, EliasFano, EliasFanoBuilder};
+use crate::traits::{IndexedDict, IndexedSeq, Types};
+use mem_dbg::*;
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+/// The error returned when a value cannot be converted between the value
+/// type of an [`EliasFanoOf`] and `usize`.
+///
+/// Since this type implements [`std::error::Error`], it can be converted
+/// into an [`anyhow::Error`] using the `?` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueRangeError {
+    /// A value of the value type cannot be represented as a `usize` (e.g.,
+    /// it is negative).
+    NotUsize {
+        /// The name of the value type.
+        value_type: &'static str,
+    },
+    /// The upper bound of an Elias–Fano representation cannot be represented
+    /// by the value type.
+    UpperBoundTooLarge {
+        /// The upper bound.
+        u: usize,
+        /// The name of the value type.
+        value_type: &'static str,
+    },
+}
+
+impl core::fmt::Display for ValueRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValueRangeError::NotUsize { value_type } => {
+                write!(f, "A value of type {} is not a valid usize", value_type)
+            }
+            ValueRangeError::UpperBoundTooLarge { u, value_type } => write!(
+                f,
+                "The upper bound {} cannot be represented by type {}",
+                u, value_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValueRangeError {}
+
+/// Converts a value of type `T` into a `usize`.
+#[inline(always)]
+fn to_usize<T: TryInto<usize>>(value: T) -> Result<usize, ValueRangeError> {
+    value.try_into().map_err(|_| ValueRangeError::NotUsize {
+        value_type: std::any::type_name::<T>(),
+    })
+}
+
+/// Converts a `usize` that is known to be representable into a `T`.
+#[inline(always)]
+fn from_usize<T: TryFrom<usize>>(value: usize) -> T {
+    // All values are bounded by an upper bound representable by T
+    T::try_from(value).unwrap_or_else(|_| unreachable!())
+}
+
+/// A thin wrapper around an [Elias–Fano representation](EliasFano) whose
+/// values are of type `T` instead of `usize`.
+///
+/// Values are stored as `usize` in the underlying structure, but they are
+/// accepted and returned as `T` (e.g., `u32`). Conversions are validated
+/// when the structure is built, so that all values returned by the
+/// structure fit `T`: values that cannot be represented as a `usize` are
+/// rejected by the [builder](EliasFanoOfBuilder), and a structure can be
+/// built from an [`EliasFano`] only if its upper bound can be represented
+/// by `T`.
+///
+/// Note that the bounds on `T` are [`TryInto<usize>`] and
+/// [`TryFrom<usize>`], as the standard library does not implement
+/// [`Into<usize>`] for, say, `u32` or `u64`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::dict::elias_fano_of::EliasFanoOfBuilder;
+///
+/// let mut efb = EliasFanoOfBuilder::<u32>::new(4, 1_000_000)?;
+/// for value in [0, 10, 100_000, 1_000_000] {
+///     efb.push(value)?;
+/// }
+/// let ef = efb.build();
+///
+/// let value: u32 = ef.get(2);
+/// assert_eq!(value, 100_000);
+/// assert_eq!(ef.index_of(1_000_000_u32), Some(3));
+/// assert_eq!(ef.iter().collect::<Vec<u32>>(), vec![0, 10, 100_000, 1_000_000]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct EliasFanoOf<T, E = EfSeqDict> {
+    ef: E,
+    _marker: PhantomData<T>,
+}
+
+impl<T, E> EliasFanoOf<T, E> {
+    /// Returns the underlying Elias–Fano representation.
+    pub fn into_inner(self) -> E {
+        self.ef
+    }
+}
+
+impl<T: TryFrom<usize>, H, L> TryFrom<EliasFano<H, L>> for EliasFanoOf<T, EliasFano<H, L>> {
+    type Error = ValueRangeError;
+
+    /// Wraps an Elias–Fano representation, checking that its [upper
+    /// bound](EliasFano::u) can be represented by `T`.
+    fn try_from(ef: EliasFano<H, L>) -> Result<Self, Self::Error> {
+        if T::try_from(ef.u()).is_err() {
+            return Err(ValueRangeError::UpperBoundTooLarge {
+                u: ef.u(),
+                value_type: std::any::type_name::<T>(),
+            });
+        }
+        Ok(Self {
+            ef,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<
+        T: TryFrom<usize> + TryInto<usize> + PartialEq,
+        E: IndexedSeq<Input = usize, Output = usize>,
+    > EliasFanoOf<T, E>
+{
+    /// Returns the number of values.
+    ///
+    /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.ef.len()
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.ef.len()).map(|i| from_usize(unsafe { self.ef.get_unchecked(i) }))
+    }
+}
+
+impl<T: TryFrom<usize> + TryInto<usize> + PartialEq, E> Types for EliasFanoOf<T, E> {
+    type Input = T;
+    type Output = T;
+}
+
+impl<
+        T: TryFrom<usize> + TryInto<usize> + PartialEq,
+        E: IndexedSeq<Input = usize, Output = usize>,
+    > IndexedSeq for EliasFanoOf<T, E>
+{
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> T {
+        from_usize(self.ef.get_unchecked(index))
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.ef.len()
+    }
+}
+
+impl<
+        T: TryFrom<usize> + TryInto<usize> + PartialEq + Copy,
+        E: IndexedDict<Input = usize, Output = usize>,
+    > IndexedDict for EliasFanoOf<T, E>
+{
+    /// Returns the index of the given value, or `None` if the value is
+    /// not in the dictionary or it cannot be represented as a `usize`.
+    fn index_of(&self, value: impl Borrow<T>) -> Option<usize> {
+        self.ef.index_of(to_usize(*value.borrow()).ok()?)
+    }
+}
+
+/// A builder for an [`EliasFanoOf`].
+///
+/// The builder delegates to an [`EliasFanoBuilder`], but accepts values of
+/// type `T`, returning an error if they cannot be represented as a `usize`.
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct EliasFanoOfBuilder<T> {
+    builder: EliasFanoBuilder,
+    _marker: PhantomData<T>,
+}
+
+impl<T: TryFrom<usize> + TryInto<usize>> EliasFanoOfBuilder<T> {
+    /// Creates a builder for an [`EliasFanoOf`] containing `n` values smaller
+    /// than or equal to `u`.
+    ///
+    /// Returns an error if `u` cannot be represented as a `usize`.
+    pub fn new(n: usize, u: T) -> Result<Self, ValueRangeError> {
+        Ok(Self {
+            builder: EliasFanoBuilder::new(n, to_usize(u)?),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Adds a new value to the builder.
+    ///
+    /// Returns an error if `value` cannot be represented as a `usize`.
+    ///
+    /// # Panics
+    ///
+    /// See [`EliasFanoBuilder::push`].
+    pub fn push(&mut self, value: T) -> Result<(), ValueRangeError> {
+        self.builder.push(to_usize(value)?);
+        Ok(())
+    }
+
+    /// Builds an [`EliasFanoOf`] with constant-time access and indexing.
+    pub fn build(self) -> EliasFanoOf<T> {
+        // All values are bounded by u, which comes from a T
+        EliasFanoOf {
+            ef: self.builder.build_with_seq_and_dict(),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! String interning based on [rear-coded lists](crate::dict::rear_coded_list).
+
+use crate::bits::BitFieldVec;
+use crate::dict::rear_coded_list::{RearCodedList, RearCodedListBuilder};
+use crate::traits::{BitFieldSlice, BitFieldSliceMut, IndexedDict, IndexedSeq};
+use epserde::*;
+use mem_dbg::*;
+use std::collections::HashMap;
+
+/// An immutable string interner assigning to distinct strings ascending
+/// identifiers in order of first appearance.
+///
+/// The strings are stored in a sorted [`RearCodedList`], so they are
+/// compressed and [`id_of`](Interner::id_of) is performed by binary search;
+/// two permutations, stored in [bit-field vectors](BitFieldVec), map
+/// positions in the list to identifiers and vice versa. The list is
+/// [length-prefixed](RearCodedListBuilder::length_prefixed), so strings can
+/// contain `\0` characters.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::dict::Interner;
+///
+/// let interner = Interner::new(["pear", "apple", "pear", "fig", "apple"]);
+/// assert_eq!(interner.len(), 3);
+/// assert_eq!(interner.id_of("pear"), Some(0));
+/// assert_eq!(interner.id_of("apple"), Some(1));
+/// assert_eq!(interner.id_of("fig"), Some(2));
+/// assert_eq!(interner.id_of("kiwi"), None);
+/// assert_eq!(interner.string_of(1), "apple");
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct Interner {
+    /// The distinct strings, sorted.
+    strings: RearCodedList,
+    /// The identifier of the string of given position in `strings`.
+    ids: BitFieldVec<usize, Box<[usize]>>,
+    /// The position in `strings` of the string of given identifier.
+    positions: BitFieldVec<usize, Box<[usize]>>,
+}
+
+impl Interner {
+    /// Creates an interner from a sequence of strings, possibly with
+    /// repetitions, using a block size of 8 for the underlying
+    /// [`RearCodedList`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if there are more than 2³² distinct strings.
+    pub fn new<S: AsRef<str>>(strings: impl IntoIterator<Item = S>) -> Self {
+        Self::with_block_size(strings, 8)
+    }
+
+    /// Creates an interner from a sequence of strings, possibly with
+    /// repetitions, using the given block size for the underlying
+    /// [`RearCodedList`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if there are more than 2³² distinct strings.
+    pub fn with_block_size<S: AsRef<str>>(strings: impl IntoIterator<Item = S>, k: usize) -> Self {
+        let mut ids = HashMap::<String, usize>::new();
+        let mut distinct = Vec::new();
+        for string in strings {
+            let string = string.as_ref();
+            if !ids.contains_key(string) {
+                assert!(
+                    distinct.len() <= u32::MAX as usize,
+                    "Too many distinct strings"
+                );
+                ids.insert(string.to_owned(), distinct.len());
+                distinct.push(string.to_owned());
+            }
+        }
+        drop(ids);
+
+        let n = distinct.len();
+        let mut sorted = (0..n).collect::<Vec<_>>();
+        sorted.sort_unstable_by(|&a, &b| distinct[a].cmp(&distinct[b]));
+
+        let bit_width = (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize;
+        let mut rclb = RearCodedListBuilder::new(k).length_prefixed(true);
+        let mut id_of_pos = BitFieldVec::<usize>::new(bit_width, n);
+        let mut pos_of_id = BitFieldVec::<usize>::new(bit_width, n);
+        for (pos, &id) in sorted.iter().enumerate() {
+            rclb.push(&distinct[id]);
+            id_of_pos.set(pos, id);
+            pos_of_id.set(id, pos);
+        }
+
+        Self {
+            strings: rclb.build(),
+            ids: id_of_pos.into(),
+            positions: pos_of_id.into(),
+        }
+    }
+
+    /// Returns the number of distinct strings.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if there are no strings.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the identifier of the given string, or `None` if the string
+    /// was not interned.
+    pub fn id_of(&self, string: impl AsRef<str>) -> Option<u32> {
+        self.strings
+            .index_of(string.as_ref())
+            .map(|pos| self.ids.get(pos) as u32)
+    }
+
+    /// Returns the string of given identifier.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `id` is not smaller than the
+    /// [number of strings](Interner::len).
+    pub fn string_of(&self, id: u32) -> String {
+        self.strings.get(self.positions.get(id as usize))
+    }
+
+    /// Returns the underlying sorted list of distinct strings.
+    pub fn strings(&self) -> &RearCodedList {
+        &self.strings
+    }
+}
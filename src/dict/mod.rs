@@ -10,8 +10,23 @@
 pub mod elias_fano;
 pub use elias_fano::{EliasFano, EliasFanoBuilder, EliasFanoConcurrentBuilder};
 
+pub mod elias_fano_of;
+pub use elias_fano_of::EliasFanoOf;
+
+pub mod interner;
+pub use interner::Interner;
+
+pub mod ones_dict;
+pub use ones_dict::OnesDict;
+
+pub mod prefix_sum;
+pub use prefix_sum::PrefixSum;
+
 pub mod rear_coded_list;
-pub use rear_coded_list::{RearCodedList, RearCodedListBuilder};
+pub use rear_coded_list::{RearCodedList, RearCodedListBuilder, VersionMismatch};
 
 pub mod slice_seq;
 pub use slice_seq::SliceSeq;
+
+pub mod string_int_map;
+pub use string_int_map::{StringIntMap, StringIntMapBuilder};
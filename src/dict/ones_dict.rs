@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! An [indexed dictionary](crate::traits::indexed_dict) of the positions of
+//! the ones of a bit vector.
+
+use crate::prelude::*;
+use epserde::*;
+use mem_dbg::*;
+use std::borrow::Borrow;
+use std::ops::Index;
+
+/// A newtype exhibiting the positions of the ones of a bit vector as an
+/// [indexed sequence](IndexedSeq) and an [indexed dictionary](IndexedDict).
+///
+/// The underlying structure must provide selection, which is used to
+/// implement [`IndexedSeq::get_unchecked`], and, to implement
+/// [`IndexedDict`], ranking, which is used to compute the index of a
+/// position. The default type is a [`SelectAdaptConst`] with default
+/// parameters on top of a [`Rank9`], which is a good choice for dense bit
+/// vectors; for sparse sets of positions, an [Elias–Fano
+/// representation](crate::dict::elias_fano::EliasFano) is a better choice.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::dict::OnesDict;
+///
+/// let ones = OnesDict::new(bit_vec![0, 1, 0, 1, 1, 0, 1, 0]);
+///
+/// assert_eq!(ones.len(), 4);
+/// assert_eq!(ones.get(0), 1);
+/// assert_eq!(ones.get(3), 6);
+///
+/// assert_eq!(ones.index_of(4), Some(2));
+/// assert_eq!(ones.index_of(5), None);
+/// assert!(ones.contains(6));
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct OnesDict<S = SelectAdaptConst<Rank9>> {
+    select: S,
+}
+
+impl OnesDict {
+    /// Creates a new dictionary of the positions of the ones of the given bit
+    /// vector using the default structure.
+    pub fn new(bits: BitVec) -> Self {
+        Self {
+            select: SelectAdaptConst::<_, _>::new(Rank9::new(bits)),
+        }
+    }
+}
+
+impl<S> OnesDict<S> {
+    /// Returns the underlying structure.
+    pub fn into_inner(self) -> S {
+        self.select
+    }
+}
+
+impl<S: SelectUnchecked + NumBits> From<S> for OnesDict<S> {
+    fn from(select: S) -> Self {
+        Self { select }
+    }
+}
+
+impl<S> Types for OnesDict<S> {
+    type Input = usize;
+    type Output = usize;
+}
+
+impl<S: SelectUnchecked + NumBits> IndexedSeq for OnesDict<S> {
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, index: usize) -> usize {
+        self.select.select_unchecked(index)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.select.num_ones()
+    }
+}
+
+impl<S: Rank + Index<usize, Output = bool>> IndexedDict for OnesDict<S> {
+    fn index_of(&self, value: impl Borrow<usize>) -> Option<usize> {
+        let value = *value.borrow();
+        if value < BitLength::len(&self.select) && self.select[value] {
+            // SAFETY: value is within bounds
+            Some(unsafe { self.select.rank_unchecked(value) })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Prefix sums based on the Elias–Fano representation of monotone sequences.
+
+use crate::dict::elias_fano::{EfSeqDict, EliasFanoBuilder};
+use crate::traits::Succ;
+use epserde::*;
+use mem_dbg::*;
+
+/// A structure storing a sequence of nonnegative integers using the
+/// [Elias–Fano representation](crate::dict::elias_fano) of its prefix sums.
+///
+/// The underlying structure contains the `n` + 1 prefix sums of the `n`
+/// elements of the sequence: the first sum is zero, and the last sum is
+/// [the sum of all elements](PrefixSum::total). Besides accessing
+/// [elements](PrefixSum::element) and [prefix sums](PrefixSum::prefix_sum),
+/// it is possible to [search](PrefixSum::search) for the first position at
+/// which a prefix sum reaches a given value.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::dict::PrefixSum;
+///
+/// let ps = PrefixSum::new([3, 0, 2, 5]);
+/// assert_eq!(ps.len(), 4);
+/// assert_eq!(ps.total(), 10);
+/// assert_eq!(ps.element(1), 0);
+/// assert_eq!(ps.prefix_sum(3), 5);
+///
+/// assert_eq!(ps.search(0), Some(0));
+/// assert_eq!(ps.search(3), Some(1));
+/// assert_eq!(ps.search(4), Some(3));
+/// assert_eq!(ps.search(10), Some(4));
+/// assert_eq!(ps.search(11), None);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct PrefixSum<E = EfSeqDict> {
+    sums: E,
+}
+
+impl PrefixSum {
+    /// Creates a new structure from a sequence of nonnegative integers.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the sum of the integers overflows a `usize`.
+    pub fn new(values: impl AsRef<[usize]>) -> Self {
+        let values = values.as_ref();
+        let total = values.iter().fold(0_usize, |sum, &x| {
+            sum.checked_add(x).expect("The sum of the values overflows")
+        });
+
+        let mut efb = EliasFanoBuilder::new(values.len() + 1, total);
+        let mut sum = 0;
+        // SAFETY: the sums are monotone and bounded by total
+        unsafe {
+            efb.push_unchecked(0);
+            for &x in values {
+                sum += x;
+                efb.push_unchecked(sum);
+            }
+        }
+
+        Self {
+            sums: efb.build_with_seq_and_dict(),
+        }
+    }
+}
+
+impl<E> PrefixSum<E> {
+    /// Creates a new structure from a dictionary of prefix sums.
+    ///
+    /// # Safety
+    ///
+    /// `sums` must be nonempty and its first value must be zero.
+    pub unsafe fn from_raw_parts(sums: E) -> Self {
+        Self { sums }
+    }
+
+    /// Returns the underlying dictionary of prefix sums.
+    pub fn into_raw_parts(self) -> E {
+        self.sums
+    }
+}
+
+impl<E: Succ<Input = usize, Output = usize>> PrefixSum<E> {
+    /// Returns the number of elements in the sequence.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    /// Returns true if the sequence is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the sum of all the elements of the sequence.
+    #[inline(always)]
+    pub fn total(&self) -> usize {
+        // SAFETY: there is always at least one prefix sum
+        unsafe { self.sums.get_unchecked(self.len()) }
+    }
+
+    /// Returns the element of given index.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the
+    /// [length](PrefixSum::len) of the sequence.
+    #[inline]
+    pub fn element(&self, index: usize) -> usize {
+        if index >= self.len() {
+            panic!("Index out of bounds: {} >= {}", index, self.len());
+        }
+        // SAFETY: index + 1 is at most the length of the sequence
+        unsafe { self.sums.get_unchecked(index + 1) - self.sums.get_unchecked(index) }
+    }
+
+    /// Returns the sum of the first `index` elements of the sequence.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is greater than the
+    /// [length](PrefixSum::len) of the sequence.
+    #[inline]
+    pub fn prefix_sum(&self, index: usize) -> usize {
+        self.sums.get(index)
+    }
+
+    /// Returns the smallest index `i` such that the sum of the first `i`
+    /// elements of the sequence is greater than or equal to `value`, or
+    /// `None` if `value` is greater than [the sum of all
+    /// elements](PrefixSum::total).
+    ///
+    /// In particular, if `value` is positive and not greater than the total,
+    /// the `value`-th unit of the cumulative sum (counting from one) falls in
+    /// the element of index `i` - 1.
+    ///
+    /// Note that when some elements are zero the result is correct only if
+    /// the [successor](Succ::succ) of the underlying dictionary returns the
+    /// smallest index among equal values, as it happens for
+    /// [`EliasFano`](crate::dict::EliasFano).
+    #[inline]
+    pub fn search(&self, value: usize) -> Option<usize> {
+        self.sums.succ(value).map(|(index, _)| index)
+    }
+}
@@ -10,6 +10,7 @@
 use std::borrow::Borrow;
 
 use crate::traits::{IndexedDict, IndexedSeq, IntoIteratorFrom, Types};
+use crate::utils::{decode_int, encode_int, encode_int_len};
 use epserde::*;
 use lender::for_;
 use lender::{ExactSizeLender, IntoLender, Lender, Lending};
@@ -35,6 +36,8 @@ struct Stats {
 
     /// The number of bytes used to store the rear lengths in data.
     pub code_bytes: usize,
+    /// The number of rear lengths encoded with `i` + 1 bytes, for each `i`.
+    pub code_len_histogram: [usize; 9],
     /// The number of bytes used to store the suffixes in data.
     pub suffixes_bytes: usize,
 
@@ -42,6 +45,70 @@ struct Stats {
     pub redundancy: isize,
 }
 
+/// Differences in space usage between two [rear-coded lists](RearCodedList),
+/// as returned by [`RearCodedList::compare_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatsDiff {
+    /// The difference in bytes of the encoded strings.
+    pub data_bytes: isize,
+    /// The difference in bytes of the pointers to blocks.
+    pub pointers_bytes: isize,
+    /// The difference between the [compression
+    /// ratios](RearCodedList::compression_ratio).
+    pub compression_ratio: f64,
+}
+
+impl core::fmt::Display for StatsDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "{:>20}: {:>+10}", "data_bytes", self.data_bytes)?;
+        writeln!(f, "{:>20}: {:>+10}", "ptrs_bytes", self.pointers_bytes)?;
+        write!(
+            f,
+            "{:>20}: {:>+10.3}",
+            "compression_ratio", self.compression_ratio
+        )
+    }
+}
+
+/// The physical layout of a block of a [rear-coded list](RearCodedList), as
+/// returned by [`RearCodedList::debug_blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+    /// The index of the block.
+    pub index: usize,
+    /// The offset in bytes of the block in the encoded data.
+    pub offset: usize,
+    /// The length in bytes of the encoded block.
+    pub bytes: usize,
+    /// The number of strings in the block.
+    pub strings: usize,
+}
+
+/// The error returned when a [`RearCodedList`] has been serialized with a
+/// layout version different from [`RearCodedList::VERSION`].
+///
+/// Since this type implements [`std::error::Error`], it can be converted
+/// into an [`anyhow::Error`] using the `?` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionMismatch {
+    /// The version found in the serialized instance.
+    pub found: u32,
+    /// The version supported by this version of the crate.
+    pub expected: u32,
+}
+
+impl core::fmt::Display for VersionMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Unsupported rear-coded list version: found {}, expected {}",
+            self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for VersionMismatch {}
+
 /// Immutable lists of strings compressed by rear-coded prefix omission.
 ///
 /// Prefix omission compresses a list of strings omitting the common prefixes of
@@ -60,6 +127,33 @@ struct Stats {
 ///
 /// To build a [`RearCodedList`] you use a [`RearCodedListBuilder`].
 ///
+/// All queries take `&self` and use only local buffers, so a list can be
+/// shared among threads whenever its backends are [`Sync`] (e.g., the default
+/// boxed slices, or memory-mapped data). Methods returning [`String`]s
+/// allocate them, and most methods allocate a scratch buffer for each call:
+/// to avoid allocations, use [`get_in_place`](RearCodedList::get_in_place)
+/// with a per-thread buffer or, for membership queries in batch,
+/// [`par_contains`](RearCodedList::par_contains).
+///
+/// By default, suffixes are `\0`-terminated, so strings cannot contain `\0`
+/// characters. If you need to store arbitrary strings, you can build a
+/// [length-prefixed](RearCodedListBuilder::length_prefixed) list, in which
+/// every suffix is preceded by its length. Depending on the distribution of
+/// the suffix lengths, this mode can also save space, as lengths smaller than
+/// 128 take a single byte.
+///
+/// By default, the rear lengths are interleaved with the suffixes. If you
+/// [store them separately](RearCodedListBuilder::separate_codes), they are
+/// written in a second array, so that suffixes are packed more densely; the
+/// two layouts answer the same queries, but
+/// [raw blocks](RearCodedList::raw_block) are available only in the
+/// interleaved layout.
+///
+/// Serialized lists carry the [version](RearCodedList::VERSION) of the layout
+/// of the encoded strings: use
+/// [`load_full_checked`](RearCodedList::load_full_checked) or
+/// [`mmap_checked`](RearCodedList::mmap_checked) to load them.
+///
 /// # Examples
 ///
 /// ```rust
@@ -89,13 +183,67 @@ pub struct RearCodedList<D: AsRef<[u8]> = Box<[u8]>, P: AsRef<[usize]> = Box<[us
     len: usize,
     /// Whether the strings are sorted.
     is_sorted: bool,
-    /// The encoded strings, `\0`-terminated.
+    /// Whether the suffixes are preceded by their length instead of being
+    /// `\0`-terminated.
+    length_prefixed: bool,
+    /// The version of the layout of the encoded strings.
+    version: u32,
+    /// Whether the rear lengths are stored in `codes` instead of being
+    /// interleaved with the suffixes in `data`.
+    separate_codes: bool,
+    /// The number of zero bytes at the end of `data` that do not belong to
+    /// the encoded strings.
+    padding: usize,
+    /// The encoded strings.
     data: D,
+    /// The rear lengths, if they are stored separately; empty otherwise.
+    codes: D,
     /// The pointer to the starting string of each block.
     pointers: P,
+    /// The pointer to the first rear length of each block in `codes`, if
+    /// rear lengths are stored separately; empty otherwise.
+    code_pointers: P,
+}
+
+/// A position in the encoded data of a [`RearCodedList`], abstracting over
+/// the interleaved and separate layouts of the rear lengths.
+#[derive(Debug, Clone, Copy, MemDbg, MemSize)]
+struct Cursor<'a> {
+    /// The remaining suffixes (and rear lengths, if interleaved).
+    data: &'a [u8],
+    /// The remaining rear lengths, if they are stored separately.
+    codes: Option<&'a [u8]>,
+}
+
+impl Cursor<'_> {
+    /// Decodes the next rear length.
+    #[inline(always)]
+    fn rear_length(&mut self) -> usize {
+        match &mut self.codes {
+            Some(codes) => {
+                let (len, tmp) = decode_int(codes);
+                *codes = tmp;
+                len
+            }
+            None => {
+                let (len, tmp) = decode_int(self.data);
+                self.data = tmp;
+                len
+            }
+        }
+    }
 }
 
 impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
+    /// The version of the layout of the encoded strings written by this
+    /// version of the crate.
+    ///
+    /// The version is stored in each serialized instance, and it is checked
+    /// by [`load_full_checked`](RearCodedList::load_full_checked) and
+    /// [`mmap_checked`](RearCodedList::mmap_checked). It must be increased
+    /// whenever the way strings are encoded changes.
+    pub const VERSION: u32 = 0;
+
     /// Returns the number of strings.
     ///
     /// This method is equivalent to [`IndexedSeq::len`], but it is provided to
@@ -131,6 +279,191 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
         self.lend_from(0)
     }
 
+    /// Returns a [`Lender`] over the bytes of the strings starting from the
+    /// given position.
+    #[inline(always)]
+    pub fn lend_bytes_from(&self, from: usize) -> LendBytes<'_, D, P> {
+        LendBytes {
+            lend: Lend::new_from(self, from),
+        }
+    }
+
+    /// Returns a [`Lender`] over the bytes of the strings.
+    ///
+    /// The bytes are lent from an inner buffer, so there is no allocation
+    /// for each string, and no conversion to `str`. This is the fastest way
+    /// to scan the list when you do not need the strings to be `str`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lender::Lender;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// rclb.push("a");
+    /// rclb.push("ab");
+    /// let rcl = rclb.build();
+    /// let mut lender = rcl.lend_bytes();
+    /// assert_eq!(lender.next(), Some(&b"a"[..]));
+    /// assert_eq!(lender.next(), Some(&b"ab"[..]));
+    /// assert_eq!(lender.next(), None);
+    /// ```
+    #[inline(always)]
+    pub fn lend_bytes(&self) -> LendBytes<'_, D, P> {
+        self.lend_bytes_from(0)
+    }
+
+    /// Consumes the list and returns an [`Iterator`] over the bytes of the
+    /// strings.
+    ///
+    /// The bytes are returned as owned vectors, without any UTF-8 validation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4).length_prefixed(true);
+    /// rclb.push("a\0b");
+    /// rclb.push("a\0c");
+    /// let rcl = rclb.build();
+    /// let strings = rcl.into_byte_iter().collect::<Vec<_>>();
+    /// assert_eq!(strings, vec![b"a\0b".to_vec(), b"a\0c".to_vec()]);
+    /// ```
+    pub fn into_byte_iter(self) -> IntoByteIter<D, P> {
+        IntoByteIter {
+            rca: self,
+            buffer: Vec::with_capacity(128),
+            data_pos: 0,
+            codes_pos: 0,
+            index: 0,
+        }
+    }
+
+    /// Returns whether the suffixes are preceded by their length instead of
+    /// being `\0`-terminated.
+    ///
+    /// See [`RearCodedListBuilder::length_prefixed`].
+    #[inline(always)]
+    pub fn is_length_prefixed(&self) -> bool {
+        self.length_prefixed
+    }
+
+    /// Returns whether the rear lengths are stored separately from the
+    /// suffixes.
+    ///
+    /// See [`RearCodedListBuilder::separate_codes`].
+    #[inline(always)]
+    pub fn has_separate_codes(&self) -> bool {
+        self.separate_codes
+    }
+
+    /// Returns the number of zero bytes [padding](RearCodedListBuilder::padding)
+    /// the encoded strings.
+    #[inline(always)]
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+
+    /// Returns whether the strings are sorted in lexicographical byte order.
+    ///
+    /// If the strings are sorted [`IndexedDict::index_of`] and
+    /// [`IndexedDict::contains`] use a binary search, otherwise they use a
+    /// linear scan.
+    ///
+    /// See [`RearCodedListBuilder::is_sorted`].
+    #[inline(always)]
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted
+    }
+
+    /// Returns the version of the layout of the encoded strings.
+    ///
+    /// See [`RearCodedList::VERSION`].
+    #[inline(always)]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Checks that the version of the layout of the encoded strings is the
+    /// one supported by this version of the crate.
+    pub fn check_version(&self) -> Result<(), VersionMismatch> {
+        if self.version == Self::VERSION {
+            Ok(())
+        } else {
+            Err(VersionMismatch {
+                found: self.version,
+                expected: Self::VERSION,
+            })
+        }
+    }
+
+    /// Splits `data`, which must start with an encoded suffix, into the
+    /// suffix and the remaining data.
+    #[inline(always)]
+    fn split_suffix<'a>(&self, data: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        split_suffix(data, self.length_prefixed)
+    }
+
+    /// Returns a cursor on the first string of the block of given index.
+    #[inline(always)]
+    fn cursor(&self, block: usize) -> Cursor<'_> {
+        Cursor {
+            data: &self.data.as_ref()[self.pointers.as_ref()[block]..],
+            codes: self
+                .separate_codes
+                .then(|| &self.codes.as_ref()[self.code_pointers.as_ref()[block]..]),
+        }
+    }
+
+    /// Returns a cursor on the first string of the list, which can be
+    /// used to scan all strings.
+    #[inline(always)]
+    fn full_cursor(&self) -> Cursor<'_> {
+        Cursor {
+            data: self.data.as_ref(),
+            codes: self.separate_codes.then(|| self.codes.as_ref()),
+        }
+    }
+
+    /// Returns the encoded bytes of the block of given index.
+    ///
+    /// The result can be appended to a builder using
+    /// [`RearCodedListBuilder::append_raw_block`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the number of
+    /// blocks, or if the rear lengths are [stored
+    /// separately](RearCodedListBuilder::separate_codes).
+    pub fn raw_block(&self, index: usize) -> &[u8] {
+        assert!(
+            !self.separate_codes,
+            "Raw blocks are not available if rear lengths are stored separately"
+        );
+        let pointers = self.pointers.as_ref();
+        let data = self.data.as_ref();
+        let end = pointers
+            .get(index + 1)
+            .copied()
+            .unwrap_or(data.len() - self.padding);
+        &data[pointers[index]..end]
+    }
+
+    /// Appends the suffix at the start of `data` to `result` and returns the
+    /// remaining data.
+    #[inline(always)]
+    fn copy_suffix<'a>(&self, data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
+        if self.length_prefixed {
+            let (suffix, data) = self.split_suffix(data);
+            result.extend_from_slice(suffix);
+            data
+        } else {
+            strcpy(data, result)
+        }
+    }
+
     /// Writes the index-th string to `result` as bytes. This is useful to avoid
     /// allocating a new string for every query and skipping the UTF-8 validity
     /// check.
@@ -140,21 +473,481 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
         let block = index / self.k;
         let offset = index % self.k;
 
-        let start = self.pointers.as_ref()[block];
-        let data = &self.data.as_ref()[start..];
+        let mut cursor = self.cursor(block);
 
         // decode the first string in the block
-        let mut data = strcpy(data, result);
+        cursor.data = self.copy_suffix(cursor.data, result);
 
         for _ in 0..offset {
             // get how much data to throw away
-            let (len, tmp) = decode_int(data);
+            let len = cursor.rear_length();
             // throw away the data
             result.resize(result.len() - len, 0);
             // copy the new suffix
-            let tmp = strcpy(tmp, result);
-            data = tmp;
+            cursor.data = self.copy_suffix(cursor.data, result);
+        }
+    }
+
+    /// Returns the rear length stored for the string of given index, that is,
+    /// the number of bytes of the previous string that are not shared with
+    /// the string, or zero if the string is the first of its block.
+    ///
+    /// For a string that is not the first of its block, the length of the
+    /// longest common prefix with the previous string is the length of the
+    /// previous string minus the rear length. Only rear lengths and the
+    /// lengths of suffixes are decoded, so this method is faster than
+    /// decoding the strings.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is not smaller than the number of
+    /// strings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// for s in ["abcd", "abe", "b", "bcd", "bce"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.rear_length_at(0), 0);
+    /// assert_eq!(rcl.rear_length_at(1), 2);
+    /// assert_eq!(rcl.rear_length_at(2), 3);
+    /// // "bcd" extends "b"
+    /// assert_eq!(rcl.rear_length_at(3), 0);
+    /// // first string of the second block
+    /// assert_eq!(rcl.rear_length_at(4), 0);
+    /// ```
+    pub fn rear_length_at(&self, index: usize) -> usize {
+        assert!(
+            index < self.len,
+            "Index out of bounds: {} >= {}",
+            index,
+            self.len
+        );
+        let offset = index % self.k;
+        if offset == 0 {
+            return 0;
+        }
+        let mut cursor = self.cursor(index / self.k);
+        // skip the first string in the block
+        cursor.data = self.split_suffix(cursor.data).1;
+        for _ in 1..offset {
+            cursor.rear_length();
+            cursor.data = self.split_suffix(cursor.data).1;
+        }
+        cursor.rear_length()
+    }
+
+    /// Appends the strings of a block to `buffer` as bytes, and their
+    /// starting positions in `buffer` to `offsets`.
+    ///
+    /// The block is decoded just once, so this method is more efficient
+    /// than calling [`get_in_place`](RearCodedList::get_in_place) on each
+    /// string of the block. The `i`-th string of the block is
+    /// `buffer[offsets[i]..offsets[i + 1]]`, or `buffer[offsets[i]..]` for
+    /// the last string, assuming that both vectors were empty.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `block` is not smaller than the number of
+    /// blocks.
+    pub fn get_block_in_place(&self, block: usize, buffer: &mut Vec<u8>, offsets: &mut Vec<usize>) {
+        let num_strings = Ord::min(self.k, self.len - block * self.k);
+        let mut cursor = self.cursor(block);
+
+        // decode the first string in the block
+        offsets.push(buffer.len());
+        cursor.data = self.copy_suffix(cursor.data, buffer);
+
+        for _ in 1..num_strings {
+            let prev_start = *offsets.last().unwrap();
+            // get how much data to throw away from the previous string
+            let len = cursor.rear_length();
+            let prefix_len = buffer.len() - prev_start - len;
+            offsets.push(buffer.len());
+            // copy the common prefix and the new suffix
+            buffer.extend_from_within(prev_start..prev_start + prefix_len);
+            cursor.data = self.copy_suffix(cursor.data, buffer);
+        }
+    }
+
+    /// Returns a histogram of the number of strings per block.
+    ///
+    /// The returned vector has length `k` + 1, and its element of index `i`
+    /// is the number of blocks containing exactly `i` strings. All blocks are
+    /// full, except possibly for the last one.
+    pub fn block_fill_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![0; self.k + 1];
+        histogram[self.k] = self.len / self.k;
+        if self.len % self.k != 0 {
+            histogram[self.len % self.k] += 1;
+        }
+        histogram
+    }
+
+    /// Returns a histogram of the lengths of the common prefixes omitted by
+    /// rear coding.
+    ///
+    /// The element of index `i` of the returned vector is the number of
+    /// strings sharing a prefix of exactly `i` bytes with the previous
+    /// string. The first string of each block is encoded in full, and it is
+    /// thus not counted, so the sum of the histogram is [the number of
+    /// strings](RearCodedList::len) minus the number of blocks.
+    ///
+    /// The strings are not decoded, so this method is significantly faster
+    /// than a full iteration.
+    pub fn lcp_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![];
+        let mut cursor = self.full_cursor();
+        let mut last_len = 0;
+        for index in 0..self.len {
+            let lcp = if index % self.k == 0 {
+                0
+            } else {
+                let lcp = last_len - cursor.rear_length();
+                if lcp >= histogram.len() {
+                    histogram.resize(lcp + 1, 0);
+                }
+                histogram[lcp] += 1;
+                lcp
+            };
+            // skip the suffix
+            let (suffix, tmp) = self.split_suffix(cursor.data);
+            cursor.data = tmp;
+            last_len = lcp + suffix.len();
+        }
+        histogram
+    }
+
+    /// Returns an iterator over the physical layout of the blocks.
+    ///
+    /// This method is useful to debug the encoding, as it makes it possible
+    /// to inspect the layout of the encoded data without parsing it: the
+    /// byte lengths of the blocks sum to the size of the encoded data, and
+    /// their numbers of strings sum to [the number of
+    /// strings](RearCodedList::len). If the rear lengths are [stored
+    /// separately](RearCodedListBuilder::separate_codes), offsets and lengths
+    /// refer to the suffixes only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// for s in ["aa", "aab", "abc", "abdd", "abde", "abdf"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// let blocks = rcl.debug_blocks().collect::<Vec<_>>();
+    /// assert_eq!(blocks.len(), 2);
+    /// assert_eq!(blocks[0].offset, 0);
+    /// assert_eq!(blocks[0].strings, 4);
+    /// assert_eq!(blocks[1].offset, blocks[0].bytes);
+    /// assert_eq!(blocks[1].strings, 2);
+    /// ```
+    pub fn debug_blocks(&self) -> impl Iterator<Item = BlockInfo> + '_ {
+        let pointers = self.pointers.as_ref();
+        pointers.iter().enumerate().map(move |(index, &offset)| {
+            let end = pointers
+                .get(index + 1)
+                .copied()
+                .unwrap_or(self.data.as_ref().len() - self.padding);
+            BlockInfo {
+                index,
+                offset,
+                bytes: end - offset,
+                strings: self.k.min(self.len - index * self.k),
+            }
+        })
+    }
+
+    /// Returns the index of the block whose encoded bytes contain the given
+    /// offset in the encoded strings, or `None` if the offset is past the end
+    /// of the encoded strings.
+    ///
+    /// This method can be used to map a position in the encoded strings back
+    /// to the strings it belongs to: the result is the block `b` such that
+    /// the offset lies in the byte range of the block, as returned by
+    /// [`debug_blocks`](RearCodedList::debug_blocks). If the rear lengths are
+    /// [stored separately](RearCodedListBuilder::separate_codes), offsets
+    /// refer to the suffixes only. The [padding](RearCodedListBuilder::padding)
+    /// does not belong to any block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(2);
+    /// for s in ["a", "ab", "b", "bc"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// let blocks = rcl.debug_blocks().collect::<Vec<_>>();
+    /// assert_eq!(rcl.block_of_offset(0), Some(0));
+    /// assert_eq!(rcl.block_of_offset(blocks[1].offset - 1), Some(0));
+    /// assert_eq!(rcl.block_of_offset(blocks[1].offset), Some(1));
+    /// assert_eq!(rcl.block_of_offset(blocks[1].offset + blocks[1].bytes), None);
+    /// ```
+    pub fn block_of_offset(&self, byte_offset: usize) -> Option<usize> {
+        if byte_offset >= self.data.as_ref().len() - self.padding {
+            return None;
+        }
+        // the first pointer is always zero, so the result is positive
+        Some(
+            self.pointers
+                .as_ref()
+                .partition_point(|&ptr| ptr <= byte_offset)
+                - 1,
+        )
+    }
+
+    /// Returns the ratio between the size in bytes of the encoded data,
+    /// including pointers to blocks, and the sum of the lengths in bytes of
+    /// the strings.
+    ///
+    /// The strings are not decoded, but the method takes time linear in the
+    /// size of the encoded data. If the sum of the lengths is zero, the
+    /// result is not finite.
+    pub fn compression_ratio(&self) -> f64 {
+        let mut cursor = self.full_cursor();
+        let mut last_len = 0;
+        let mut uncompressed_bytes = 0;
+        for index in 0..self.len {
+            let lcp = if index % self.k == 0 {
+                0
+            } else {
+                last_len - cursor.rear_length()
+            };
+            // skip the suffix
+            let (suffix, tmp) = self.split_suffix(cursor.data);
+            cursor.data = tmp;
+            last_len = lcp + suffix.len();
+            uncompressed_bytes += last_len;
+        }
+        self.compressed_bytes() as f64 / uncompressed_bytes as f64
+    }
+
+    /// Returns the size in bytes of the encoded data and of the pointers
+    /// to blocks.
+    fn compressed_bytes(&self) -> usize {
+        self.data_bytes() + self.pointers_bytes()
+    }
+
+    /// Returns the size in bytes of the encoded data, including separately
+    /// stored rear lengths.
+    fn data_bytes(&self) -> usize {
+        self.data.as_ref().len() + self.codes.as_ref().len()
+    }
+
+    /// Returns the size in bytes of the pointers to blocks, including the
+    /// pointers to separately stored rear lengths.
+    fn pointers_bytes(&self) -> usize {
+        core::mem::size_of_val(self.pointers.as_ref())
+            + core::mem::size_of_val(self.code_pointers.as_ref())
+    }
+
+    /// Compares the space usage of this list with that of another list,
+    /// usually containing the same strings but built with a different
+    /// [block size](RearCodedListBuilder::new) or from strings in a different
+    /// order.
+    ///
+    /// The fields of the returned [`StatsDiff`] are the values for `other`
+    /// minus the values for this list, so negative values mean that `other`
+    /// uses less space.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let words = ["aa", "aab", "abc", "abdd", "abde", "abdf", "b", "ba"];
+    /// let mut rclb = RearCodedListBuilder::new(1);
+    /// words.iter().for_each(|s| rclb.push(s));
+    /// let rcl1 = rclb.build();
+    /// let mut rclb = RearCodedListBuilder::new(8);
+    /// words.iter().for_each(|s| rclb.push(s));
+    /// let rcl8 = rclb.build();
+    ///
+    /// let diff = rcl1.compare_stats(&rcl8);
+    /// assert!(diff.data_bytes < 0);
+    /// assert!(diff.pointers_bytes < 0);
+    /// println!("{}", diff);
+    /// ```
+    pub fn compare_stats<D2: AsRef<[u8]>, P2: AsRef<[usize]>>(
+        &self,
+        other: &RearCodedList<D2, P2>,
+    ) -> StatsDiff {
+        StatsDiff {
+            data_bytes: other.data_bytes() as isize - self.data_bytes() as isize,
+            pointers_bytes: other.pointers_bytes() as isize - self.pointers_bytes() as isize,
+            compression_ratio: other.compression_ratio() - self.compression_ratio(),
+        }
+    }
+
+    /// Binary searches the list with a comparator function.
+    ///
+    /// The comparator function must return an ordering indicating whether
+    /// its argument, the bytes of a string of the list, is less than, equal
+    /// to, or greater than the desired target. The list must be sorted in an
+    /// order consistent with the comparator (for example, case-insensitive
+    /// order for a case-insensitive comparator), which need not be the
+    /// lexicographical order of bytes used by
+    /// [`index_of`](IndexedDict::index_of).
+    ///
+    /// As in the case of
+    /// [`slice::binary_search_by`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by),
+    /// if the target is found then [`Result::Ok`] is returned, containing the
+    /// index of a matching string (if there are several matches, any of them
+    /// can be returned); otherwise, [`Result::Err`] is returned, containing
+    /// the index where a matching string could be inserted while maintaining
+    /// sorted order.
+    ///
+    /// The search is performed first on the first strings of the blocks, and
+    /// then linearly inside a block, so the comparator is called on at most
+    /// log₂(`len` / `k`) + `k` strings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(2);
+    /// // Sorted case-insensitively, but not lexicographically
+    /// for s in ["alpha", "Beta", "Delta", "gamma"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// let search = |key: &str| {
+    ///     rcl.binary_search_by(|s| s.to_ascii_lowercase().cmp(&key.as_bytes().to_ascii_lowercase()))
+    /// };
+    /// assert_eq!(search("BETA"), Ok(1));
+    /// assert_eq!(search("delta"), Ok(2));
+    /// assert_eq!(search("Charlie"), Err(2));
+    /// assert_eq!(search("zeta"), Err(4));
+    /// ```
+    pub fn binary_search_by(
+        &self,
+        f: impl Fn(&[u8]) -> core::cmp::Ordering,
+    ) -> Result<usize, usize> {
+        let data = self.data.as_ref();
+        // first to a binary search on the first strings of the blocks
+        let block_idx = self
+            .pointers
+            .as_ref()
+            .binary_search_by(|&block_ptr| f(self.split_suffix(&data[block_ptr..]).0));
+
+        let block_idx = match block_idx {
+            Ok(block_idx) => return Ok(block_idx * self.k),
+            // the target is before the first string
+            Err(0) => return Err(0),
+            Err(block_idx) => block_idx - 1,
+        };
+
+        // finish by a linear search on the block
+        let mut result = Vec::with_capacity(128);
+        let mut cursor = self.cursor(block_idx);
+        cursor.data = self.copy_suffix(cursor.data, &mut result);
+        let in_block = (self.k - 1).min(self.len - block_idx * self.k - 1);
+        for idx in 1..=in_block {
+            let len = cursor.rear_length();
+            result.resize(result.len() - len, 0);
+            cursor.data = self.copy_suffix(cursor.data, &mut result);
+
+            match f(&result) {
+                core::cmp::Ordering::Less => {}
+                core::cmp::Ordering::Equal => return Ok(block_idx * self.k + idx),
+                core::cmp::Ordering::Greater => return Err(block_idx * self.k + idx),
+            }
         }
+        Err(block_idx * self.k + in_block + 1)
+    }
+
+    /// Returns the indices of many strings, or `None` for strings that are
+    /// not in the list.
+    ///
+    /// The result is the same as that of calling
+    /// [`index_of`](IndexedDict::index_of) on each query, but the queries are
+    /// sorted and then resolved in a single forward scan of the list, which is
+    /// much faster than independent searches for large batches of queries.
+    /// If the list is sorted, blocks preceding the block containing the next
+    /// query are skipped without being decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// for s in ["alpha", "beta", "delta", "gamma", "omega"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// assert_eq!(
+    ///     rcl.index_of_many(&["omega", "epsilon", "alpha", "omega"]),
+    ///     vec![Some(4), None, Some(0), Some(4)]
+    /// );
+    /// ```
+    pub fn index_of_many(&self, queries: &[&str]) -> Vec<Option<usize>> {
+        let mut order = (0..queries.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&i| queries[i].as_bytes());
+        let mut result = vec![None; queries.len()];
+        let mut lend = self.lend();
+
+        if !self.is_sorted {
+            // for each string, resolve all queries equal to it
+            let mut index = 0;
+            while let Some(string) = lend.next() {
+                let string = string.as_bytes();
+                let start = order.partition_point(|&i| queries[i].as_bytes() < string);
+                for &i in order[start..]
+                    .iter()
+                    .take_while(|&&i| queries[i].as_bytes() == string)
+                {
+                    result[i].get_or_insert(index);
+                }
+                index += 1;
+            }
+            return result;
+        }
+
+        for i in order {
+            let query = queries[i].as_bytes();
+            let block = match self.search_blocks(query) {
+                Ok(block) => {
+                    result[i] = Some(block * self.k);
+                    continue;
+                }
+                // the query is before the first block
+                Err(0) => continue,
+                Err(block) => block - 1,
+            };
+            // skip the blocks preceding the block of the query
+            let position = self.len - lend.len();
+            if block * self.k > position {
+                lend.seek(block * self.k);
+            }
+            // queries are sorted, so we never consume a string that is
+            // greater than or equal to a query
+            while let Some(string) = lend.peek() {
+                match string.as_bytes().cmp(query) {
+                    core::cmp::Ordering::Less => {
+                        lend.next();
+                    }
+                    core::cmp::Ordering::Equal => {
+                        result[i] = Some(self.len - lend.len());
+                        break;
+                    }
+                    core::cmp::Ordering::Greater => break,
+                }
+            }
+        }
+        result
     }
 
     fn index_of_unsorted(&self, value: impl Borrow<<Self as Types>::Input>) -> Option<usize> {
@@ -172,42 +965,61 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
     }
 
     fn index_of_sorted(&self, value: impl Borrow<<Self as Types>::Input>) -> Option<usize> {
-        let string = value.borrow().as_bytes();
+        self.index_of_sorted_in(value.borrow().as_bytes(), &mut Vec::with_capacity(128))
+    }
+
+    /// Binary searches `string` among the first strings of the blocks,
+    /// returning the index of the block, as in
+    /// [`slice::binary_search`].
+    fn search_blocks(&self, string: &[u8]) -> Result<usize, usize> {
+        self.pointers.as_ref().binary_search_by(|block_ptr| {
+            let data = &self.data.as_ref()[*block_ptr..];
+            if self.length_prefixed {
+                self.split_suffix(data).0.cmp(string)
+            } else {
+                strcmp(string, data).reverse()
+            }
+        })
+    }
+
+    /// Like [`index_of_sorted`](RearCodedList::index_of_sorted), but uses the
+    /// given buffer to decode strings.
+    fn index_of_sorted_in(&self, string: &[u8], result: &mut Vec<u8>) -> Option<usize> {
+        debug_assert!(self.is_sorted, "Binary search on an unsorted list");
         // first to a binary search on the blocks to find the block
-        let block_idx = self.pointers.as_ref().binary_search_by(|block_ptr| {
-            strcmp(string, &self.data.as_ref()[*block_ptr..]).reverse()
-        });
+        let block_idx = self.search_blocks(string);
 
         if let Ok(block_idx) = block_idx {
             return Some(block_idx * self.k);
         }
 
+        // The insertion point is at most the number of blocks: if it is equal
+        // to it, the string follows the first string of the last block, and
+        // we still have to scan the last block.
         let mut block_idx = block_idx.unwrap_err();
-        if block_idx == 0 || block_idx > self.pointers.as_ref().len() {
+        if block_idx == 0 {
             // the string is before the first block
             return None;
         }
         block_idx -= 1;
         // finish by a linear search on the block
-        let mut result = Vec::with_capacity(128);
-        let start = self.pointers.as_ref()[block_idx];
-        let data = &self.data.as_ref()[start..];
+        result.clear();
+        let mut cursor = self.cursor(block_idx);
 
         // decode the first string in the block
-        let mut data = strcpy(data, &mut result);
+        cursor.data = self.copy_suffix(cursor.data, result);
         let in_block = (self.k - 1).min(self.len - block_idx * self.k - 1);
         for idx in 0..in_block {
             // get how much data to throw away
-            let (len, tmp) = decode_int(data);
+            let len = cursor.rear_length();
             let lcp = result.len() - len;
             // throw away the data
             result.resize(lcp, 0);
             // copy the new suffix
-            let tmp = strcpy(tmp, &mut result);
-            data = tmp;
+            cursor.data = self.copy_suffix(cursor.data, result);
 
             // TODO!: this can be optimized to avoid the copy
-            match strcmp_rust(string, &result) {
+            match strcmp_rust(string, result) {
                 core::cmp::Ordering::Less => {}
                 core::cmp::Ordering::Equal => return Some(block_idx * self.k + idx + 1),
                 core::cmp::Ordering::Greater => return None,
@@ -217,6 +1029,133 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> RearCodedList<D, P> {
     }
 }
 
+/// The serialized layout of [`RearCodedList`] before the
+/// [version](RearCodedList::VERSION) was stored.
+///
+/// The name of the structure and of its fields must not change, as they are
+/// part of the type hash checked by ε-serde.
+mod legacy {
+    use epserde::*;
+
+    #[derive(Debug, Epserde)]
+    pub struct RearCodedList<D: AsRef<[u8]> = Box<[u8]>, P: AsRef<[usize]> = Box<[usize]>> {
+        pub k: usize,
+        pub len: usize,
+        pub is_sorted: bool,
+        pub data: D,
+        pub pointers: P,
+    }
+
+    impl From<RearCodedList> for super::RearCodedList {
+        fn from(rcl: RearCodedList) -> Self {
+            Self {
+                k: rcl.k,
+                len: rcl.len,
+                is_sorted: rcl.is_sorted,
+                length_prefixed: false,
+                version: Self::VERSION,
+                separate_codes: false,
+                padding: 0,
+                data: rcl.data,
+                codes: Box::default(),
+                pointers: rcl.pointers,
+                code_pointers: Box::default(),
+            }
+        }
+    }
+}
+
+impl RearCodedList<Box<[u8]>, Box<[usize]>> {
+    /// Loads a rear-coded list fully into memory, checking its
+    /// [version](RearCodedList::VERSION).
+    ///
+    /// This method should be preferred to
+    /// [`load_full`](deser::Deserialize::load_full), as a list with an
+    /// unsupported layout would otherwise be decoded into garbage.
+    ///
+    /// Lists serialized before the version was stored are migrated to the
+    /// current layout.
+    pub fn load_full_checked(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let rcl = match <Self as deser::Deserialize>::load_full(path) {
+            Ok(rcl) => rcl,
+            Err(err) => {
+                if let Ok(rcl) = <legacy::RearCodedList as deser::Deserialize>::load_full(path) {
+                    rcl.into()
+                } else {
+                    return Err(err);
+                }
+            }
+        };
+        rcl.check_version()?;
+        Ok(rcl)
+    }
+
+    /// Memory-maps a rear-coded list, checking its
+    /// [version](RearCodedList::VERSION).
+    ///
+    /// This method should be preferred to [`mmap`](deser::Deserialize::mmap),
+    /// as a list with an unsupported layout would otherwise be decoded into
+    /// garbage.
+    ///
+    /// Lists serialized with a previous layout cannot be memory-mapped: load
+    /// them with [`load_full_checked`](RearCodedList::load_full_checked) and
+    /// serialize them again.
+    pub fn mmap_checked<'a>(
+        path: impl AsRef<std::path::Path>,
+        flags: deser::Flags,
+    ) -> anyhow::Result<deser::MemCase<deser::DeserType<'a, Self>>> {
+        let rcl = <Self as deser::Deserialize>::mmap(path, flags)?;
+        rcl.check_version()?;
+        Ok(rcl)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<D: AsRef<[u8]> + Sync, P: AsRef<[usize]> + Sync> RearCodedList<D, P> {
+    /// Returns, for each query, whether the list contains it, performing the
+    /// queries in parallel.
+    ///
+    /// The queries are partitioned among the threads of the current
+    /// [Rayon](rayon) thread pool. If the list is sorted, each thread uses
+    /// a single scratch buffer for all its queries, so no allocation is
+    /// performed per query; otherwise, queries are answered by a linear
+    /// scan, as in [`contains`](IndexedDict::contains).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// for s in ["aa", "aab", "abc", "abdd", "abde", "abdf"] {
+    ///     rclb.push(s);
+    /// }
+    /// let rcl = rclb.build();
+    /// assert_eq!(
+    ///     rcl.par_contains(&["abc", "abd", "abdf"]),
+    ///     vec![true, false, true]
+    /// );
+    /// ```
+    pub fn par_contains(&self, queries: &[&str]) -> Vec<bool> {
+        use rayon::prelude::*;
+        if self.is_sorted {
+            queries
+                .par_iter()
+                .map_init(
+                    || Vec::with_capacity(128),
+                    |buffer, query| self.index_of_sorted_in(query.as_bytes(), buffer).is_some(),
+                )
+                .collect()
+        } else {
+            queries
+                .par_iter()
+                .map(|query| self.index_of_unsorted(*query).is_some())
+                .collect()
+        }
+    }
+}
+
 impl<D: AsRef<[u8]>, P: AsRef<[usize]>> Types for RearCodedList<D, P> {
     type Output = String;
     type Input = str;
@@ -234,6 +1173,30 @@ impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedSeq for RearCodedList<D, P> {
     fn len(&self) -> usize {
         self.len
     }
+
+    /// Samples the strings by decoding the list sequentially, allocating
+    /// only the strings that are returned.
+    fn sample(&self, step: usize) -> Vec<String> {
+        assert!(step != 0, "The sampling step must be positive");
+        let mut result = Vec::with_capacity(self.len.div_ceil(step));
+        let mut lender = self.lend();
+        let mut index = 0;
+        while let Some(s) = lender.next() {
+            if index % step == 0 {
+                result.push(s.to_owned());
+            }
+            index += 1;
+        }
+        result
+    }
+
+    /// Enumerates the strings by decoding the list sequentially.
+    fn enumerate_values(&self) -> impl Iterator<Item = (usize, Self::Output)> + '_
+    where
+        Self: Sized,
+    {
+        self.iter().enumerate()
+    }
 }
 
 impl<D: AsRef<[u8]>, P: AsRef<[usize]>> IndexedDict for RearCodedList<D, P> {
@@ -267,6 +1230,31 @@ pub struct Iter<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> {
     iter: Lend<'a, D, P>,
 }
 
+impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Iter<'a, D, P> {
+    /// Returns the next string without consuming it, or `None` if there are
+    /// no more strings.
+    ///
+    /// See [`Lend::peek`].
+    #[inline(always)]
+    pub fn peek(&mut self) -> Option<&str> {
+        self.iter.peek()
+    }
+
+    /// Moves forward so that the next string returned will be the one of
+    /// given index.
+    ///
+    /// See [`Lend::seek`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is smaller than the index of the
+    /// next string.
+    #[inline(always)]
+    pub fn seek(&mut self, index: usize) {
+        self.iter.seek(index)
+    }
+}
+
 impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::ExactSizeIterator for Iter<'a, D, P> {
     #[inline(always)]
     fn len(&self) -> usize {
@@ -307,40 +1295,194 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> IntoIteratorFrom for &'a RearCodedLi
     }
 }
 
+/// Consuming [`Iterator`] over the bytes of the strings.
+///
+/// See [`RearCodedList::into_byte_iter`].
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct IntoByteIter<D: AsRef<[u8]>, P: AsRef<[usize]>> {
+    rca: RearCodedList<D, P>,
+    buffer: Vec<u8>,
+    /// The position of the next suffix in the encoded strings.
+    data_pos: usize,
+    /// The position of the next rear length, if rear lengths are stored
+    /// separately.
+    codes_pos: usize,
+    index: usize,
+}
+
+impl<D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::Iterator for IntoByteIter<D, P> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.rca.len {
+            return None;
+        }
+        let data = &self.rca.data.as_ref()[self.data_pos..];
+        let codes = &self.rca.codes.as_ref()[self.codes_pos..];
+        let mut cursor = Cursor {
+            data,
+            codes: self.rca.separate_codes.then_some(codes),
+        };
+
+        if self.index % self.rca.k == 0 {
+            self.buffer.clear();
+        } else {
+            let len = cursor.rear_length();
+            self.buffer.truncate(self.buffer.len() - len);
+        }
+        cursor.data = self.rca.copy_suffix(cursor.data, &mut self.buffer);
+
+        self.data_pos += data.len() - cursor.data.len();
+        self.codes_pos += codes.len() - cursor.codes.map_or(codes.len(), <[u8]>::len);
+        self.index += 1;
+        Some(self.buffer.clone())
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<D: AsRef<[u8]>, P: AsRef<[usize]>> std::iter::ExactSizeIterator for IntoByteIter<D, P> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.rca.len - self.index
+    }
+}
+
 /// Sequential [`Lender`] over the strings.
 #[derive(Debug, Clone, MemDbg, MemSize)]
 pub struct Lend<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> {
     rca: &'a RearCodedList<D, P>,
     buffer: Vec<u8>,
-    data: &'a [u8],
+    cursor: Cursor<'a>,
     index: usize,
+    /// Whether the buffer contains a string returned by [`Lend::peek`] that
+    /// has not been returned by [`Lender::next`] yet.
+    peeked: bool,
 }
 
-impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lend<'a, D, P> {
-    pub fn new(rca: &'a RearCodedList<D, P>) -> Self {
-        Self {
-            rca,
-            buffer: Vec::with_capacity(128),
-            data: rca.data.as_ref(),
-            index: 0,
+impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lend<'a, D, P> {
+    pub fn new(rca: &'a RearCodedList<D, P>) -> Self {
+        Self {
+            rca,
+            buffer: Vec::with_capacity(128),
+            cursor: rca.full_cursor(),
+            index: 0,
+            peeked: false,
+        }
+    }
+
+    pub fn new_from(rca: &'a RearCodedList<D, P>, from: usize) -> Self {
+        let block = from / rca.k;
+        let offset = from % rca.k;
+
+        let mut res = Lend {
+            rca,
+            index: block * rca.k,
+            cursor: rca.cursor(block),
+            buffer: Vec::with_capacity(128),
+            peeked: false,
+        };
+        for _ in 0..offset {
+            res.next();
+        }
+        res
+    }
+
+    /// Returns the next string without consuming it, or `None` if there are
+    /// no more strings.
+    ///
+    /// The string is decoded in the inner buffer, and it will be returned by
+    /// the following call to [`next`](Lender::next) without decoding it again.
+    /// This method makes it possible to perform merge-like operations between
+    /// lists in a streaming fashion.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lender::Lender;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// rclb.push("a");
+    /// rclb.push("b");
+    /// let rcl = rclb.build();
+    /// let mut lender = rcl.lend();
+    /// assert_eq!(lender.peek(), Some("a"));
+    /// assert_eq!(lender.next(), Some("a"));
+    /// assert_eq!(lender.next(), Some("b"));
+    /// assert_eq!(lender.peek(), None);
+    /// ```
+    pub fn peek(&mut self) -> Option<&str> {
+        if !self.peeked {
+            if !self.advance() {
+                return None;
+            }
+            self.peeked = true;
+        }
+        Some(unsafe { std::str::from_utf8_unchecked(&self.buffer) })
+    }
+
+    /// Moves forward so that the next string returned will be the one of
+    /// given index.
+    ///
+    /// If `index` is in a later block, the strings preceding it in the
+    /// current block are not decoded. Seeking to the current position (or
+    /// past the end of the list) is allowed.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is smaller than the index of the
+    /// next string, as repositioning is possible only forward.
+    pub fn seek(&mut self, index: usize) {
+        let index = index.min(self.rca.len());
+        let position = self.index - self.peeked as usize;
+        assert!(
+            index >= position,
+            "Cannot seek backwards from {} to {}",
+            position,
+            index
+        );
+        if index == position {
+            return;
+        }
+        self.peeked = false;
+        if index == self.rca.len() {
+            self.index = index;
+            return;
+        }
+        let k = self.rca.k;
+        if index / k > self.index / k {
+            let block = index / k;
+            self.cursor = self.rca.cursor(block);
+            self.index = block * k;
+        }
+        while self.index < index {
+            self.advance();
         }
     }
 
-    pub fn new_from(rca: &'a RearCodedList<D, P>, from: usize) -> Self {
-        let block = from / rca.k;
-        let offset = from % rca.k;
+    /// Decodes the next string in the inner buffer, returning false if there
+    /// are no more strings.
+    #[inline]
+    fn advance(&mut self) -> bool {
+        if self.index >= self.rca.len() {
+            return false;
+        }
 
-        let start = rca.pointers.as_ref()[block];
-        let mut res = Lend {
-            rca,
-            index: block * rca.k,
-            data: &rca.data.as_ref()[start..],
-            buffer: Vec::with_capacity(128),
-        };
-        for _ in 0..offset {
-            res.next();
+        if self.index % self.rca.k == 0 {
+            // just copy the data
+            self.buffer.clear();
+            self.cursor.data = self.rca.copy_suffix(self.cursor.data, &mut self.buffer);
+        } else {
+            let len = self.cursor.rear_length();
+            self.buffer.resize(self.buffer.len() - len, 0);
+            self.cursor.data = self.rca.copy_suffix(self.cursor.data, &mut self.buffer);
         }
-        res
+        self.index += 1;
+        true
     }
 }
 
@@ -354,21 +1496,12 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender for Lend<'a, D, P> {
     /// This is useful to avoid allocating a new string for every query if you
     /// don't need to keep the string around.
     fn next(&mut self) -> Option<&'_ str> {
-        if self.index >= self.rca.len() {
+        if self.peeked {
+            self.peeked = false;
+        } else if !self.advance() {
             return None;
         }
 
-        if self.index % self.rca.k == 0 {
-            // just copy the data
-            self.buffer.clear();
-            self.data = strcpy(self.data, &mut self.buffer);
-        } else {
-            let (len, tmp) = decode_int(self.data);
-            self.buffer.resize(self.buffer.len() - len, 0);
-            self.data = strcpy(tmp, &mut self.buffer);
-        }
-        self.index += 1;
-
         Some(unsafe { std::str::from_utf8_unchecked(&self.buffer) })
     }
 
@@ -381,7 +1514,63 @@ impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender for Lend<'a, D, P> {
 impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> ExactSizeLender for Lend<'a, D, P> {
     #[inline(always)]
     fn len(&self) -> usize {
-        self.rca.len() - self.index
+        self.rca.len() - self.index + self.peeked as usize
+    }
+}
+
+/// Sequential [`Lender`] over the bytes of the strings.
+///
+/// See [`RearCodedList::lend_bytes`].
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct LendBytes<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> {
+    lend: Lend<'a, D, P>,
+}
+
+impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> LendBytes<'a, D, P> {
+    /// Returns the bytes of the next string without consuming it, or `None`
+    /// if there are no more strings.
+    ///
+    /// See [`Lend::peek`].
+    #[inline(always)]
+    pub fn peek(&mut self) -> Option<&[u8]> {
+        self.lend.peek().map(str::as_bytes)
+    }
+
+    /// Moves forward so that the next string returned will be the one of
+    /// given index.
+    ///
+    /// See [`Lend::seek`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is smaller than the index of the
+    /// next string.
+    #[inline(always)]
+    pub fn seek(&mut self, index: usize) {
+        self.lend.seek(index)
+    }
+}
+
+impl<'a, 'b, D: AsRef<[u8]>, P: AsRef<[usize]>> Lending<'a> for LendBytes<'b, D, P> {
+    type Lend = &'a [u8];
+}
+
+impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> Lender for LendBytes<'a, D, P> {
+    #[inline(always)]
+    fn next(&mut self) -> Option<&'_ [u8]> {
+        self.lend.next().map(str::as_bytes)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lend.size_hint()
+    }
+}
+
+impl<'a, D: AsRef<[u8]>, P: AsRef<[usize]>> ExactSizeLender for LendBytes<'a, D, P> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.lend.len()
     }
 }
 
@@ -394,23 +1583,51 @@ pub struct RearCodedListBuilder {
     len: usize,
     /// Whether the strings are sorted.
     is_sorted: bool,
-    /// The encoded strings, `\0`-terminated.
+    /// Whether the suffixes are preceded by their length instead of being
+    /// `\0`-terminated.
+    length_prefixed: bool,
+    /// Whether the rear lengths are stored separately from the suffixes.
+    separate_codes: bool,
+    /// Whether the last `\0`-terminated suffix keeps its terminator.
+    trailing_terminator: bool,
+    /// The number of zero bytes appended to the encoded strings.
+    padding: usize,
+    /// The encoded strings.
     data: Vec<u8>,
+    /// The rear lengths, if they are stored separately.
+    codes: Vec<u8>,
     /// The pointer to the starting string of each block.
     pointers: Vec<usize>,
+    /// The pointer to the first rear length of each block, if rear lengths
+    /// are stored separately.
+    code_pointers: Vec<usize>,
     /// Statistics of the encoded data.
     stats: Stats,
     /// Cache of the last encoded string for incremental encoding.
     last_str: Vec<u8>,
 }
 
-/// Copies a string until the first `\0` from `data` to `result` and return the
+/// Splits `data`, which must start with an encoded suffix, into the suffix and
+/// the remaining data.
+#[inline(always)]
+fn split_suffix(data: &[u8], length_prefixed: bool) -> (&[u8], &[u8]) {
+    if length_prefixed {
+        let (len, data) = decode_int(data);
+        data.split_at(len)
+    } else {
+        // the last string might not be terminated
+        let len = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        (&data[..len], &data[(len + 1).min(data.len())..])
+    }
+}
+
+/// Copies a string until the first `\0` (or the end of `data`, as the last
+/// string might not be terminated) from `data` to `result` and return the
 /// remaining data.
 #[inline(always)]
 fn strcpy<'a>(mut data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
-    loop {
-        let c = data[0];
-        data = &data[1..];
+    while let Some((&c, rest)) = data.split_first() {
+        data = rest;
         if c == 0 {
             break;
         }
@@ -420,16 +1637,17 @@ fn strcpy<'a>(mut data: &'a [u8], result: &mut Vec<u8>) -> &'a [u8] {
 }
 
 #[inline(always)]
-/// Like strcmp, but `string` is a Rust string and data is a `\0`-terminated string.
+/// Like strcmp, but `string` is a Rust string and data is a `\0`-terminated
+/// string (or a string terminated by the end of `data`).
 fn strcmp(string: &[u8], data: &[u8]) -> core::cmp::Ordering {
     for (i, c) in string.iter().enumerate() {
-        let ord = c.cmp(&data[i]);
+        let ord = c.cmp(data.get(i).unwrap_or(&0));
         if ord != core::cmp::Ordering::Equal {
             return ord;
         }
     }
 
-    if data[string.len()] == 0 {
+    if matches!(data.get(string.len()), None | Some(0)) {
         core::cmp::Ordering::Equal
     } else {
         core::cmp::Ordering::Less
@@ -456,20 +1674,294 @@ impl RearCodedListBuilder {
             data: Vec::with_capacity(1024),
             last_str: Vec::with_capacity(1024),
             pointers: Vec::new(),
+            separate_codes: false,
+            trailing_terminator: true,
+            padding: 0,
+            codes: Vec::new(),
+            code_pointers: Vec::new(),
+            len: 0,
+            is_sorted: true,
+            length_prefixed: false,
+            k,
+            stats: Default::default(),
+        }
+    }
+
+    /// Creates a builder for a rear-coded list with a block size of `k`,
+    /// preallocating space for `data_bytes` bytes of encoded data and for
+    /// `num_strings` strings.
+    ///
+    /// The hints are just used to avoid reallocations when building large
+    /// lists: the builder will work correctly even if they are exceeded.
+    pub fn with_capacity(k: usize, data_bytes: usize, num_strings: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(data_bytes),
+            last_str: Vec::with_capacity(1024),
+            pointers: Vec::with_capacity(num_strings.div_ceil(k)),
+            separate_codes: false,
+            trailing_terminator: true,
+            padding: 0,
+            codes: Vec::new(),
+            code_pointers: Vec::new(),
             len: 0,
             is_sorted: true,
+            length_prefixed: false,
             k,
             stats: Default::default(),
         }
     }
 
+    /// Returns the largest block size whose worst-case in-block scan on
+    /// `sample` does not exceed `max_scan` bytes.
+    ///
+    /// Accessing a string requires decoding all strings preceding it in its
+    /// block, so the cost of the slowest access is given by the number of
+    /// bytes of the largest block. This method simulates the (`\0`-terminated)
+    /// encoding of `sample` for every possible block size and returns the
+    /// largest one whose largest block is at most `max_scan` bytes, thus
+    /// targeting access latency rather than space. The sample should be a
+    /// representative contiguous run of the strings that will be pushed.
+    ///
+    /// The result is always at least one, even when a single string of the
+    /// sample does not fit within `max_scan` bytes.
+    pub fn k_for_latency(sample: &[impl AsRef<str>], max_scan: usize) -> usize {
+        // bytes_as_head[i] is the cost of the i-th string at the start of a
+        // block; prefix[i] is the sum of the costs of the first i strings when
+        // rear coded
+        let mut bytes_as_head = Vec::with_capacity(sample.len());
+        let mut prefix = Vec::with_capacity(sample.len() + 1);
+        prefix.push(0);
+        let mut last: &[u8] = &[];
+        for string in sample {
+            let string = string.as_ref().as_bytes();
+            let (lcp, _) = longest_common_prefix(last, string);
+            let rear_length = last.len() - lcp;
+            bytes_as_head.push(string.len() + 1);
+            prefix.push(
+                prefix.last().unwrap() + encode_int_len(rear_length) + string.len() - lcp + 1,
+            );
+            last = string;
+        }
+
+        let n = sample.len();
+        let mut best = 1;
+        for k in 2..=n {
+            let max_block = (0..n)
+                .step_by(k)
+                .map(|start| {
+                    let end = (start + k).min(n);
+                    bytes_as_head[start] + prefix[end] - prefix[start + 1]
+                })
+                .max()
+                .unwrap_or(0);
+            if max_block <= max_scan {
+                best = k;
+            }
+        }
+        best
+    }
+
+    /// Sets whether suffixes should be preceded by their length instead of
+    /// being `\0`-terminated.
+    ///
+    /// Length-prefixed lists can contain strings with `\0` characters, which
+    /// would otherwise be truncated. The length is encoded using the same
+    /// variable-length code used for rear lengths.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if some strings have already been pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4).length_prefixed(true);
+    /// rclb.push("a\0b");
+    /// rclb.push("a\0c");
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.get(1), "a\0c");
+    /// ```
+    pub fn length_prefixed(mut self, length_prefixed: bool) -> Self {
+        assert!(
+            self.len == 0,
+            "The encoding mode must be set before pushing strings"
+        );
+        self.length_prefixed = length_prefixed;
+        self
+    }
+
+    /// Sets whether rear lengths should be stored in a separate array instead
+    /// of being interleaved with the suffixes.
+    ///
+    /// With separate rear lengths, scanning a block reads two sequential
+    /// streams, but the suffixes are packed more densely. The space occupancy
+    /// increases just by one pointer per block; which layout is faster depends
+    /// on the strings and on the architecture (see the
+    /// `bench_rear_coded_list` example). Lists built in this mode do not
+    /// support [raw blocks](RearCodedList::raw_block).
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if some strings have already been pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4).separate_codes(true);
+    /// rclb.push("abc");
+    /// rclb.push("abd");
+    /// let rcl = rclb.build();
+    /// assert!(rcl.has_separate_codes());
+    /// assert_eq!(rcl.get(1), "abd");
+    /// ```
+    pub fn separate_codes(mut self, separate_codes: bool) -> Self {
+        assert!(
+            self.len == 0,
+            "The encoding mode must be set before pushing strings"
+        );
+        self.separate_codes = separate_codes;
+        self
+    }
+
+    /// Sets whether the last string of the list should be followed by a `\0`
+    /// terminator.
+    ///
+    /// Since the end of the last string is implied by the end of the encoded
+    /// data, its terminator is redundant, and it can be omitted to save a
+    /// byte. This setting has no effect on [length-prefixed
+    /// lists](RearCodedListBuilder::length_prefixed). Note that, in this
+    /// case, the [raw block](RearCodedList::raw_block) containing the last
+    /// string is not terminated, so it can be appended only as the last block
+    /// of a list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4).trailing_terminator(false);
+    /// rclb.push("abc");
+    /// rclb.push("abd");
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.get(1), "abd");
+    /// ```
+    pub fn trailing_terminator(mut self, trailing_terminator: bool) -> Self {
+        self.trailing_terminator = trailing_terminator;
+        self
+    }
+
+    /// Sets the number of zero bytes appended to the encoded strings.
+    ///
+    /// The padding is a guard region making it possible to read the encoded
+    /// strings a word (or a SIMD register) at a time without ever touching
+    /// memory past the end of the allocation. It is accounted for in the
+    /// space usage of the list, but it is otherwise ignored: it does not
+    /// belong to any [raw block](RearCodedList::raw_block), and queries and
+    /// iterators stop after the last string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::traits::IndexedSeq;
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4).padding(16);
+    /// rclb.push("abc");
+    /// rclb.push("abd");
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.padding(), 16);
+    /// assert_eq!(rcl.get(1), "abd");
+    /// assert_eq!(rcl.iter().count(), 2);
+    /// ```
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the size in bytes of the data (suffixes and rear lengths) of
+    /// the last block.
+    fn last_block_bytes(&self) -> usize {
+        let last_ptr = self.pointers.last().copied().unwrap_or(0);
+        let last_code_ptr = self.code_pointers.last().copied().unwrap_or(0);
+        self.data.len() - last_ptr + self.codes.len() - last_code_ptr
+    }
+
+    /// Returns the histogram of the lengths in bytes of the encoded rear
+    /// lengths of the strings pushed so far.
+    ///
+    /// The entry of index `i` is the number of rear lengths encoded with
+    /// `i` + 1 bytes. The first string of each block has no rear length, so
+    /// the entries sum to the number of strings that are not the first of
+    /// their block. Most rear lengths should use a single byte: a significant
+    /// number of longer codes is usually a sign that the strings are not
+    /// sorted, or that they share little.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// rclb.push("a".repeat(200));
+    /// rclb.push("b");
+    /// rclb.push("bc");
+    /// assert_eq!(rclb.code_len_histogram()[..2], [1, 1]);
+    /// ```
+    pub fn code_len_histogram(&self) -> [usize; 9] {
+        self.stats.code_len_histogram
+    }
+
+    /// Returns whether every string pushed so far is greater than or equal to
+    /// its predecessor in lexicographical byte order.
+    ///
+    /// The check is performed incrementally while pushing, so this method
+    /// runs in constant time. The list returned by
+    /// [`build`](RearCodedListBuilder::build) will use binary searches only if
+    /// this method returns true.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    ///
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// rclb.push("a");
+    /// rclb.push("b");
+    /// rclb.push("b");
+    /// assert!(rclb.is_sorted());
+    /// rclb.push("a");
+    /// assert!(!rclb.is_sorted());
+    /// ```
+    #[inline(always)]
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted
+    }
+
     /// Builds the rear-coded list.
-    pub fn build(self) -> RearCodedList<Box<[u8]>, Box<[usize]>> {
+    pub fn build(mut self) -> RearCodedList<Box<[u8]>, Box<[usize]>> {
+        if !self.trailing_terminator && !self.length_prefixed && self.len != 0 {
+            let terminator = self.data.pop();
+            debug_assert_eq!(terminator, Some(0));
+        }
+        self.data.resize(self.data.len() + self.padding, 0);
         RearCodedList {
+            separate_codes: self.separate_codes,
+            padding: self.padding,
             data: self.data.into(),
+            codes: self.codes.into(),
             pointers: self.pointers.into(),
+            code_pointers: self.code_pointers.into(),
             len: self.len,
             is_sorted: self.is_sorted,
+            length_prefixed: self.length_prefixed,
+            version: RearCodedList::<Box<[u8]>, Box<[usize]>>::VERSION,
             k: self.k,
         }
     }
@@ -490,13 +1982,15 @@ impl RearCodedListBuilder {
         // at every multiple of k we just encode the string as is
         let to_encode = if self.len % self.k == 0 {
             // compute the size in bytes of the previous block
-            let last_ptr = self.pointers.last().copied().unwrap_or(0);
-            let block_bytes = self.data.len() - last_ptr;
+            let block_bytes = self.last_block_bytes();
             // update stats
             self.stats.max_block_bytes = self.stats.max_block_bytes.max(block_bytes);
             self.stats.sum_block_bytes += block_bytes;
             // save a pointer to the start of the string
             self.pointers.push(self.data.len());
+            if self.separate_codes {
+                self.code_pointers.push(self.codes.len());
+            }
 
             // compute the redundancy
             let rear_length = self.last_str.len() - lcp;
@@ -512,18 +2006,30 @@ impl RearCodedListBuilder {
             self.stats.sum_lcp += lcp;
             // encode the len of the bytes in data
             let rear_length = self.last_str.len() - lcp;
-            let prev_len = self.data.len();
-            encode_int(rear_length, &mut self.data);
+            let codes = if self.separate_codes {
+                &mut self.codes
+            } else {
+                &mut self.data
+            };
+            let prev_len = codes.len();
+            encode_int(rear_length, codes);
             // update stats
-            self.stats.code_bytes += self.data.len() - prev_len;
+            self.stats.code_bytes += codes.len() - prev_len;
+            self.stats.code_len_histogram[codes.len() - prev_len - 1] += 1;
             // return the delta suffix
             &string.as_bytes()[lcp..]
         };
-        // Write the data to the buffer
-        self.data.extend_from_slice(to_encode);
-        // push the \0 terminator
-        self.data.push(0);
-        self.stats.suffixes_bytes += to_encode.len() + 1;
+        let prev_len = self.data.len();
+        if self.length_prefixed {
+            // write the length of the suffix and the suffix
+            encode_int(to_encode.len(), &mut self.data);
+            self.data.extend_from_slice(to_encode);
+        } else {
+            // write the suffix and the \0 terminator
+            self.data.extend_from_slice(to_encode);
+            self.data.push(0);
+        }
+        self.stats.suffixes_bytes += self.data.len() - prev_len;
 
         // put the string as last_str for the next iteration
         self.last_str.clear();
@@ -531,6 +2037,110 @@ impl RearCodedListBuilder {
         self.len += 1;
     }
 
+    /// Appends a block that has already been rear coded to the end of the
+    /// list.
+    ///
+    /// This method makes it possible to build a list in a distributed
+    /// fashion: workers encode their strings in self-contained blocks, which
+    /// are then stitched together without re-encoding. A block can be
+    /// obtained, for example, from a list built by a worker using
+    /// [`RearCodedList::raw_block`]. The block is decoded once to update the
+    /// statistics and the last string.
+    ///
+    /// `first_string` is the first string of the block, which is used to
+    /// keep track of whether the strings are sorted.
+    ///
+    /// # Safety
+    ///
+    /// `block_data` must be a valid block containing `num_strings` strings
+    /// that are valid UTF-8, encoded with the same
+    /// [encoding mode](RearCodedListBuilder::length_prefixed); that is, it
+    /// must start with the uncompressed string `first_string`, followed by
+    /// `num_strings` − 1 strings encoded by rear length and suffix. Since
+    /// strings are returned without checking their UTF-8 validity, an
+    /// invalid block can lead to undefined behavior.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the rear lengths are [stored
+    /// separately](RearCodedListBuilder::separate_codes), if the number of
+    /// strings in the list is not a multiple of the block size, if
+    /// `num_strings` is zero or larger than the block size, or if the block
+    /// does not contain exactly `num_strings` strings.
+    pub unsafe fn append_raw_block(
+        &mut self,
+        block_data: &[u8],
+        num_strings: usize,
+        first_string: &[u8],
+    ) {
+        assert!(
+            !self.separate_codes,
+            "Raw blocks cannot be appended if rear lengths are stored separately"
+        );
+        assert!(
+            self.len % self.k == 0,
+            "Raw blocks can be appended only at block boundaries"
+        );
+        assert!(
+            num_strings != 0 && num_strings <= self.k,
+            "The number of strings in a block must be in [1..{}]",
+            self.k
+        );
+
+        // compute the size in bytes of the previous block
+        let block_bytes = self.last_block_bytes();
+        self.stats.max_block_bytes = self.stats.max_block_bytes.max(block_bytes);
+        self.stats.sum_block_bytes += block_bytes;
+
+        let (lcp, order) = longest_common_prefix(&self.last_str, first_string);
+        if order == core::cmp::Ordering::Greater {
+            self.is_sorted = false;
+        }
+        if self.len != 0 {
+            self.stats.redundancy += lcp as isize;
+            self.stats.redundancy -= encode_int_len(self.last_str.len() - lcp) as isize;
+        }
+
+        self.pointers.push(self.data.len());
+        self.data.extend_from_slice(block_data);
+
+        // decode the block to update the statistics and the last string
+        let mut data = block_data;
+        for index in 0..num_strings {
+            let lcp = if index == 0 {
+                0
+            } else {
+                let (rear_length, tmp) = decode_int(data);
+                self.stats.code_bytes += data.len() - tmp.len();
+                self.stats.code_len_histogram[data.len() - tmp.len() - 1] += 1;
+                data = tmp;
+                let lcp = self.last_str.len() - rear_length;
+                self.stats.max_lcp = self.stats.max_lcp.max(lcp);
+                self.stats.sum_lcp += lcp;
+                lcp
+            };
+            let (suffix, tmp) = split_suffix(data, self.length_prefixed);
+            self.stats.suffixes_bytes += data.len() - tmp.len();
+            data = tmp;
+
+            if index != 0 && self.last_str[lcp..] > *suffix {
+                self.is_sorted = false;
+            }
+            self.last_str.truncate(lcp);
+            self.last_str.extend_from_slice(suffix);
+            debug_assert!(index != 0 || self.last_str == first_string);
+
+            self.stats.max_str_len = self.stats.max_str_len.max(self.last_str.len());
+            self.stats.sum_str_len += self.last_str.len();
+        }
+        assert!(
+            data.is_empty(),
+            "The block contains more than {} strings",
+            num_strings
+        );
+        self.len += num_strings;
+    }
+
     /// Appends all the strings from a [`Lender`] to the end of the list.
     ///
     /// We prefer to implement extension via a [`Lender`] instead of an
@@ -566,6 +2176,37 @@ impl RearCodedListBuilder {
         });
     }
 
+    /// Appends all the strings from a fallible [`Lender`] to the end of the
+    /// list, stopping at the first error.
+    ///
+    /// This method accepts lenders whose lends are `Result<&str, E>`, such as
+    /// [`LineLender`](crate::utils::lenders::LineLender), so that a list can
+    /// be built directly from a file without collecting its lines.
+    ///
+    /// The strings lent before an error are appended to the list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::dict::RearCodedListBuilder;
+    /// use sux::utils::LineLender;
+    /// let mut rclb = RearCodedListBuilder::new(4);
+    /// let lines = std::io::Cursor::new("aa\naab\nabc\nabdd\nabde\nabdf\n");
+    /// rclb.try_extend(LineLender::new(lines))?;
+    /// let rcl = rclb.build();
+    /// assert_eq!(rcl.len(), 6);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn try_extend<E, L: IntoLender>(&mut self, into_lender: L) -> Result<(), E>
+    where
+        L::Lender: for<'lend> Lending<'lend, Lend = Result<&'lend str, E>>,
+    {
+        for_!(string in into_lender {
+            self.push(string?);
+        });
+        Ok(())
+    }
+
     /// Prints in a human-readable format the statistics of the
     /// strings currently in the builder.
     pub fn print_stats(&self) {
@@ -593,7 +2234,9 @@ impl RearCodedListBuilder {
             self.stats.sum_str_len as f64 / self.len as f64
         );
 
-        let ptr_size: usize = self.pointers.len() * core::mem::size_of::<usize>();
+        let ptr_size: usize =
+            (self.pointers.len() + self.code_pointers.len()) * core::mem::size_of::<usize>();
+        let data_size = self.data.len() + self.codes.len();
 
         fn human(key: &str, x: usize) {
             const UOM: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -606,9 +2249,14 @@ impl RearCodedListBuilder {
             println!("{:>20}:{:>10.3}{}{:>20} ", key, y, UOM[uom_idx], x);
         }
 
-        let total_size = ptr_size + self.data.len() + core::mem::size_of::<Self>();
-        human("data_bytes", self.data.len());
+        let total_size = ptr_size + data_size + core::mem::size_of::<Self>();
+        human("data_bytes", data_size);
         human("codes_bytes", self.stats.code_bytes);
+        for (i, &count) in self.stats.code_len_histogram.iter().enumerate() {
+            if count != 0 {
+                println!("{:>20}: {:>10}", format!("{}_byte_codes", i + 1), count);
+            }
+        }
         human("suffixes_bytes", self.stats.suffixes_bytes);
         human("ptrs_bytes", ptr_size);
         human("uncompressed_size", self.stats.sum_str_len);
@@ -616,18 +2264,17 @@ impl RearCodedListBuilder {
 
         human(
             "optimal_size",
-            (self.data.len() as isize - self.stats.redundancy) as usize,
+            (data_size as isize - self.stats.redundancy) as usize,
         );
         human("redundancy", self.stats.redundancy as usize);
         let overhead = self.stats.redundancy + ptr_size as isize;
         println!(
             "overhead_ratio: {:>10}",
-            overhead as f64 / (overhead + self.data.len() as isize) as f64
+            overhead as f64 / (overhead + data_size as isize) as f64
         );
         println!(
             "no_overhead_compression_ratio: {:.3}",
-            (self.data.len() as isize - self.stats.redundancy) as f64
-                / self.stats.sum_str_len as f64
+            (data_size as isize - self.stats.redundancy) as f64 / self.stats.sum_str_len as f64
         );
 
         println!(
@@ -654,196 +2301,38 @@ fn longest_common_prefix(a: &[u8], b: &[u8]) -> (usize, core::cmp::Ordering) {
     }
 }
 
-/// Computes the length in bytes of value encoded as VByte
-#[inline(always)]
-fn encode_int_len(mut value: usize) -> usize {
-    let mut len = 1;
-    let mut max = 1 << 7;
-    while value >= max {
-        len += 1;
-        value -= max;
-        max <<= 7;
-    }
-    len
-}
-
-const UPPER_BOUND_1: usize = 128;
-const UPPER_BOUND_2: usize = 128_usize.pow(2) + UPPER_BOUND_1;
-const UPPER_BOUND_3: usize = 128_usize.pow(3) + UPPER_BOUND_2;
-const UPPER_BOUND_4: usize = 128_usize.pow(4) + UPPER_BOUND_3;
-const UPPER_BOUND_5: usize = 128_usize.pow(5) + UPPER_BOUND_4;
-const UPPER_BOUND_6: usize = 128_usize.pow(6) + UPPER_BOUND_5;
-const UPPER_BOUND_7: usize = 128_usize.pow(7) + UPPER_BOUND_6;
-const UPPER_BOUND_8: usize = 128_usize.pow(8) + UPPER_BOUND_7;
-
-/// VByte encode an integer
-#[inline(always)]
-fn encode_int(mut value: usize, data: &mut Vec<u8>) {
-    if value < UPPER_BOUND_1 {
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_2 {
-        value -= UPPER_BOUND_1;
-        debug_assert!((value >> 8) < (1 << 6));
-        data.push(0x80 | (value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_3 {
-        value -= UPPER_BOUND_2;
-        debug_assert!((value >> 16) < (1 << 5));
-        data.push(0xC0 | (value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_4 {
-        value -= UPPER_BOUND_3;
-        debug_assert!((value >> 24) < (1 << 4));
-        data.push(0xE0 | (value >> 24) as u8);
-        data.push((value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_5 {
-        value -= UPPER_BOUND_4;
-        debug_assert!((value >> 32) < (1 << 3));
-        data.push(0xF0 | (value >> 32) as u8);
-        data.push((value >> 24) as u8);
-        data.push((value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_6 {
-        value -= UPPER_BOUND_5;
-        debug_assert!((value >> 40) < (1 << 2));
-        data.push(0xF8 | (value >> 40) as u8);
-        data.push((value >> 32) as u8);
-        data.push((value >> 24) as u8);
-        data.push((value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_7 {
-        value -= UPPER_BOUND_6;
-        debug_assert!((value >> 48) < (1 << 1));
-        data.push(0xFC | (value >> 48) as u8);
-        data.push((value >> 40) as u8);
-        data.push((value >> 32) as u8);
-        data.push((value >> 24) as u8);
-        data.push((value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-    if value < UPPER_BOUND_8 {
-        value -= UPPER_BOUND_7;
-        data.push(0xFE);
-        data.push((value >> 48) as u8);
-        data.push((value >> 40) as u8);
-        data.push((value >> 32) as u8);
-        data.push((value >> 24) as u8);
-        data.push((value >> 16) as u8);
-        data.push((value >> 8) as u8);
-        data.push(value as u8);
-        return;
-    }
-
-    data.push(0xFF);
-    data.push((value >> 56) as u8);
-    data.push((value >> 48) as u8);
-    data.push((value >> 40) as u8);
-    data.push((value >> 32) as u8);
-    data.push((value >> 24) as u8);
-    data.push((value >> 16) as u8);
-    data.push((value >> 8) as u8);
-    data.push(value as u8);
-}
-
-#[inline(always)]
-fn decode_int(data: &[u8]) -> (usize, &[u8]) {
-    let x = data[0];
-    if x < 0x80 {
-        return (x as usize, &data[1..]);
-    }
-    if x < 0xC0 {
-        let x = (((x & !0xC0) as usize) << 8 | data[1] as usize) + UPPER_BOUND_1;
-        return (x, &data[2..]);
-    }
-    if x < 0xE0 {
-        let x = (((x & !0xE0) as usize) << 16 | (data[1] as usize) << 8 | data[2] as usize)
-            + UPPER_BOUND_2;
-        return (x, &data[3..]);
-    }
-    if x < 0xF0 {
-        let x = (((x & !0xF0) as usize) << 24
-            | (data[1] as usize) << 16
-            | (data[2] as usize) << 8
-            | data[3] as usize)
-            + UPPER_BOUND_3;
-        return (x, &data[4..]);
-    }
-    if x < 0xF8 {
-        let x = (((x & !0xF8) as usize) << 32
-            | (data[1] as usize) << 24
-            | (data[2] as usize) << 16
-            | (data[3] as usize) << 8
-            | data[4] as usize)
-            + UPPER_BOUND_4;
-        return (x, &data[5..]);
-    }
-    if x < 0xFC {
-        let x = (((x & !0xFC) as usize) << 40
-            | (data[1] as usize) << 32
-            | (data[2] as usize) << 24
-            | (data[3] as usize) << 16
-            | (data[4] as usize) << 8
-            | data[5] as usize)
-            + UPPER_BOUND_5;
-        return (x, &data[6..]);
-    }
-    if x < 0xFE {
-        let x = (((x & !0xFE) as usize) << 48
-            | (data[1] as usize) << 40
-            | (data[2] as usize) << 32
-            | (data[3] as usize) << 24
-            | (data[4] as usize) << 16
-            | (data[5] as usize) << 8
-            | data[6] as usize)
-            + UPPER_BOUND_6;
-        return (x, &data[7..]);
-    }
-    if x < 0xFF {
-        let x = ((data[1] as usize) << 48
-            | (data[2] as usize) << 40
-            | (data[3] as usize) << 32
-            | (data[4] as usize) << 24
-            | (data[5] as usize) << 16
-            | (data[6] as usize) << 8
-            | data[7] as usize)
-            + UPPER_BOUND_7;
-        return (x, &data[8..]);
-    }
-
-    let x = (data[1] as usize) << 56
-        | (data[2] as usize) << 48
-        | (data[3] as usize) << 40
-        | (data[4] as usize) << 32
-        | (data[5] as usize) << 24
-        | (data[6] as usize) << 16
-        | (data[7] as usize) << 8
-        | data[8] as usize;
-    (x, &data[9..])
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_with_capacity() {
+        let words = ["aa", "aab", "abc", "abdd", "abde", "abdf", "b", "ba", "bab"];
+        let mut a = RearCodedListBuilder::new(4);
+        let mut b = RearCodedListBuilder::with_capacity(4, 1 << 16, words.len());
+        assert!(b.data.capacity() >= 1 << 16);
+        assert!(b.pointers.capacity() >= words.len().div_ceil(4));
+        for word in words {
+            a.push(word);
+            b.push(word);
+        }
+        assert!(b.data.capacity() >= 1 << 16);
+        let (a, b) = (a.build(), b.build());
+        assert_eq!(a.data, b.data);
+        assert_eq!(a.pointers, b.pointers);
+        assert_eq!(a.len, b.len);
+        assert_eq!(a.is_sorted, b.is_sorted);
+
+        // Hints that are too small must not be a problem
+        let mut c = RearCodedListBuilder::with_capacity(4, 1, 1);
+        for word in words {
+            c.push(word);
+        }
+        let c = c.build();
+        assert_eq!(a.data, c.data);
+        assert_eq!(a.pointers, c.pointers);
+    }
+
     #[test]
     fn test_strcmp() {
         assert_eq!(strcmp(b"abcd", b"abcd\0"), core::cmp::Ordering::Equal);
@@ -863,58 +2352,6 @@ mod tests {
         assert_eq!(strcmp(b"ab", b"ab\0"), core::cmp::Ordering::Equal);
     }
 
-    #[test]
-    fn test_encode_decode_int() {
-        let values = [
-            0,
-            1,
-            2,
-            3,
-            4,
-            5,
-            6,
-            7,
-            8,
-            UPPER_BOUND_1 - 1,
-            UPPER_BOUND_1,
-            UPPER_BOUND_1 + 1,
-            UPPER_BOUND_2 - 1,
-            UPPER_BOUND_2,
-            UPPER_BOUND_2 + 1,
-            UPPER_BOUND_3 - 1,
-            UPPER_BOUND_3,
-            UPPER_BOUND_3 + 1,
-            UPPER_BOUND_4 - 1,
-            UPPER_BOUND_4,
-            UPPER_BOUND_4 + 1,
-            UPPER_BOUND_5 - 1,
-            UPPER_BOUND_5,
-            UPPER_BOUND_5 + 1,
-            UPPER_BOUND_6 - 1,
-            UPPER_BOUND_6,
-            UPPER_BOUND_6 + 1,
-            UPPER_BOUND_7 - 1,
-            UPPER_BOUND_7,
-            UPPER_BOUND_7 + 1,
-            UPPER_BOUND_8 - 1,
-            UPPER_BOUND_8,
-            UPPER_BOUND_8 + 1,
-        ];
-        let mut buffer = Vec::with_capacity(128);
-
-        for i in &values {
-            encode_int(*i, &mut buffer);
-        }
-
-        let mut data = &buffer[..];
-        for i in &values {
-            let (j, tmp) = decode_int(data);
-            assert_eq!(data.len() - tmp.len(), encode_int_len(*i));
-            data = tmp;
-            assert_eq!(*i, j);
-        }
-    }
-
     #[test]
     fn test_longest_common_prefix() {
         let str1 = b"absolutely";
@@ -957,4 +2394,81 @@ mod tests {
         let rcl = builder.build();
         read_into_lender::<&RearCodedList>(&rcl);
     }
+
+    #[test]
+    fn test_debug_blocks() {
+        let words = ["aa", "aab", "abc", "abdd", "abde", "abdf", "b", "ba", "bab"];
+        for k in [1, 2, 4, 9, 100] {
+            let mut builder = RearCodedListBuilder::new(k);
+            for word in words {
+                builder.push(word);
+            }
+            let rcl = builder.build();
+            let blocks = rcl.debug_blocks().collect::<Vec<_>>();
+            assert_eq!(blocks.len(), words.len().div_ceil(k));
+            for (i, block) in blocks.iter().enumerate() {
+                assert_eq!(block.index, i);
+                assert_eq!(block.offset, rcl.pointers[i]);
+            }
+            assert_eq!(
+                blocks.iter().map(|b| b.bytes).sum::<usize>(),
+                rcl.data.len()
+            );
+            assert_eq!(blocks.iter().map(|b| b.strings).sum::<usize>(), rcl.len);
+        }
+    }
+
+    /// Copies of the previous serialized layouts, which must be loaded
+    /// by [`RearCodedList::load_full_checked`].
+    mod layouts {
+        use epserde::*;
+
+        pub mod baseline {
+            use super::*;
+
+            #[derive(Debug, Epserde)]
+            pub struct RearCodedList {
+                pub k: usize,
+                pub len: usize,
+                pub is_sorted: bool,
+                pub data: Box<[u8]>,
+                pub pointers: Box<[usize]>,
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_legacy() -> anyhow::Result<()> {
+        use epserde::ser::Serialize;
+        let words = ["aa", "aab", "abc", "abdd", "abde", "abdf", "b", "ba", "bab"];
+        let mut b = RearCodedListBuilder::new(4);
+        for word in words {
+            b.push(word);
+        }
+        let rcl = b.build();
+
+        let tmp_file = std::env::temp_dir().join("test_rcl_legacy.bin");
+        layouts::baseline::RearCodedList {
+            k: rcl.k,
+            len: rcl.len,
+            is_sorted: rcl.is_sorted,
+            data: rcl.data.clone(),
+            pointers: rcl.pointers.clone(),
+        }
+        .store(&tmp_file)?;
+        let loaded = RearCodedList::load_full_checked(&tmp_file)?;
+        std::fs::remove_file(&tmp_file)?;
+
+        assert_eq!(
+            loaded.version(),
+            RearCodedList::<Box<[u8]>, Box<[usize]>>::VERSION
+        );
+        assert!(!loaded.is_length_prefixed());
+        assert_eq!(loaded.data, rcl.data);
+        assert_eq!(loaded.pointers, rcl.pointers);
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(loaded.get(i), *word);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Immutable maps from strings to integers based on [rear-coded
+//! lists](crate::dict::rear_coded_list).
+
+use crate::bits::BitFieldVec;
+use crate::dict::rear_coded_list::{RearCodedList, RearCodedListBuilder};
+use crate::traits::{BitFieldSlice, BitFieldSliceMut, IndexedDict};
+use epserde::*;
+use mem_dbg::*;
+
+/// An immutable map from strings to integers.
+///
+/// The keys are stored in a sorted [`RearCodedList`], so they are compressed
+/// and [`get`](StringIntMap::get) is performed by binary search; the list is
+/// [length-prefixed](RearCodedListBuilder::length_prefixed), so keys can
+/// contain `\0` characters. The values are stored in the same order in a
+/// [bit-field vector](BitFieldVec) whose width is that of the largest value.
+///
+/// Instances are built by a [`StringIntMapBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::dict::StringIntMapBuilder;
+///
+/// let mut builder = StringIntMapBuilder::new(8);
+/// builder.push("pear", 3);
+/// builder.push("apple", 10);
+/// builder.push("fig", 7);
+/// let map = builder.build();
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map.get("apple"), Some(10));
+/// assert_eq!(map.get("pear"), Some(3));
+/// assert_eq!(map.get("kiwi"), None);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct StringIntMap {
+    /// The keys, sorted.
+    keys: RearCodedList,
+    /// The value associated with the key of given position in `keys`.
+    values: BitFieldVec<u64, Box<[u64]>>,
+}
+
+impl StringIntMap {
+    /// Returns the number of keys.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if there are no keys.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value associated with the given key, or `None` if the key
+    /// is not in the map.
+    pub fn get(&self, key: impl AsRef<str>) -> Option<u64> {
+        self.keys
+            .index_of(key.as_ref())
+            .map(|pos| self.values.get(pos))
+    }
+
+    /// Returns the underlying sorted list of keys.
+    pub fn keys(&self) -> &RearCodedList {
+        &self.keys
+    }
+
+    /// Returns the values, in the order of the [keys](StringIntMap::keys).
+    pub fn values(&self) -> &BitFieldVec<u64, Box<[u64]>> {
+        &self.values
+    }
+}
+
+/// Builder for a [`StringIntMap`].
+///
+/// Pairs can be pushed in any order, as they are sorted by key by
+/// [`build`](StringIntMapBuilder::build).
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub struct StringIntMapBuilder {
+    /// The block size of the underlying [`RearCodedList`].
+    k: usize,
+    /// The key-value pairs pushed so far.
+    pairs: Vec<(String, u64)>,
+}
+
+impl StringIntMapBuilder {
+    /// Creates a builder for a map whose keys are stored in a
+    /// [`RearCodedList`] with a block size of `k`.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            pairs: Vec::new(),
+        }
+    }
+
+    /// Adds a key-value pair to the builder.
+    pub fn push(&mut self, key: impl AsRef<str>, value: u64) {
+        self.pairs.push((key.as_ref().to_owned(), value));
+    }
+
+    /// Builds the map.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the same key has been pushed twice.
+    pub fn build(mut self) -> StringIntMap {
+        self.pairs.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let max = self
+            .pairs
+            .iter()
+            .map(|&(_, value)| value)
+            .max()
+            .unwrap_or(0);
+        let bit_width = (u64::BITS - max.leading_zeros()) as usize;
+        let mut rclb = RearCodedListBuilder::new(self.k).length_prefixed(true);
+        let mut values = BitFieldVec::<u64>::new(bit_width, self.pairs.len());
+        for (pos, (key, value)) in self.pairs.iter().enumerate() {
+            assert!(
+                pos == 0 || self.pairs[pos - 1].0 != *key,
+                "Duplicate key {:?}",
+                key
+            );
+            rclb.push(key);
+            values.set(pos, *value);
+        }
+
+        StringIntMap {
+            keys: rclb.build(),
+            values: values.into(),
+        }
+    }
+}
+
+impl<S: AsRef<str>> Extend<(S, u64)> for StringIntMapBuilder {
+    fn extend<T: IntoIterator<Item = (S, u64)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
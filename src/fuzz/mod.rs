@@ -4,4 +4,6 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+pub mod rear_coded_list;
 pub mod select;
+pub mod vbyte;
@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+use crate::prelude::*;
+use arbitrary::Arbitrary;
+use lender::*;
+use std::collections::BTreeSet;
+
+#[derive(Arbitrary, Debug)]
+pub struct Data {
+    /// the strings to encode (they will be sorted and deduplicated)
+    strings: Vec<String>,
+    /// other strings to look up
+    queries: Vec<String>,
+    /// the block size
+    k: u8,
+}
+
+/// get random data and check that access, iteration and lookup in a
+/// rear-coded list are consistent
+pub fn harness(data: Data) {
+    let k = data.k as usize % 64 + 1;
+    // NUL is used as a terminator, so it cannot appear in strings
+    let strings = data
+        .strings
+        .into_iter()
+        .map(|s| s.replace('\0', ""))
+        .collect::<BTreeSet<_>>();
+
+    let mut rclb = RearCodedListBuilder::new(k);
+    strings.iter().for_each(|s| rclb.push(s));
+    let rcl = rclb.build();
+
+    assert_eq!(rcl.len(), strings.len());
+
+    for (i, (s, t)) in strings.iter().zip(rcl.iter()).enumerate() {
+        assert_eq!(*s, rcl.get(i), "Wrong string at idx {}", i);
+        assert_eq!(*s, t, "Iterator is wrong at idx {}", i);
+        assert_eq!(
+            rcl.index_of(s.as_str()),
+            Some(i),
+            "Lookup is wrong at idx {}",
+            i
+        );
+        assert!(rcl.contains(s.as_str()));
+    }
+    assert_eq!(rcl.iter().count(), strings.len());
+
+    let mut lender = rcl.lend();
+    let mut i = 0;
+    while let Some(s) = lender.next() {
+        assert_eq!(s, rcl.get(i), "Lender is wrong at idx {}", i);
+        i += 1;
+    }
+    assert_eq!(i, strings.len());
+
+    // NUL is used as a terminator, so it cannot appear in queries either
+    for query in data.queries.into_iter().map(|s| s.replace('\0', "")) {
+        assert_eq!(
+            rcl.contains(query.as_str()),
+            strings.contains(&query),
+            "Lookup of {:?} is wrong",
+            query
+        );
+    }
+}
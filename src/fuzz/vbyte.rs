@@ -0,0 +1,36 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+use crate::utils::{decode_int, encode_int, encode_int_len};
+use arbitrary::Arbitrary;
+
+#[derive(Arbitrary, Debug)]
+pub struct Data {
+    /// the values to encode
+    values: Vec<usize>,
+}
+
+/// get random data and check that the VByte codec is the identity
+pub fn harness(data: Data) {
+    let mut encoded = Vec::new();
+    for &value in &data.values {
+        let len = encoded.len();
+        encode_int(value, &mut encoded);
+        assert_eq!(
+            encoded.len() - len,
+            encode_int_len(value),
+            "Wrong encoding length for {}",
+            value
+        );
+    }
+
+    let mut encoded = encoded.as_slice();
+    for (i, &value) in data.values.iter().enumerate() {
+        let (decoded, tail) = decode_int(encoded);
+        assert_eq!(decoded, value, "Wrong decoded value at idx {}", i);
+        encoded = tail;
+    }
+    assert!(encoded.is_empty(), "Spurious bytes after decoding");
+}
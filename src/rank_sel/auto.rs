@@ -0,0 +1,522 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Automatic choice of rank and selection structures.
+//!
+//! Choosing among the many rank/select structures available in this crate
+//! requires some expertise. The functions in this module inspect the length
+//! and the density of a bit vector and build a structure that is a reasonable
+//! choice given a [space/speed tradeoff](SpaceSpeed), returning it as a trait
+//! object.
+//!
+//! For selection, the decision (see [`select_kind`]) is as follows:
+//!
+//! - if the bit vector contains at least a one, and its density is at most
+//!   1/64, the positions of the ones are stored using an [Elias–Fano
+//!   representation](crate::dict::elias_fano) in an [`EliasFanoSelect`], as
+//!   this will use significantly less space than the bit vector itself;
+//! - otherwise, if the target is [`SpaceSpeed::Speed`] and the density is
+//!   between 1/4 and 3/4, we use a [`SelectAdaptConst`] with default
+//!   parameters, which are tuned for this case;
+//! - otherwise, we use a [`SelectAdapt`], which adapts to the density, with
+//!   a maximum of 2³ (for [`SpaceSpeed::Speed`]) or 2¹ (for
+//!   [`SpaceSpeed::Space`]) 64-bit words per subinventory.
+//!
+//! For selection of zeros (see [`select_zero_kind`]), the decision is the
+//! same, with the roles of ones and zeros exchanged; however, in the case of
+//! an Elias–Fano representation of the zeros the bit vector is kept, with a
+//! [`Rank9`] on top of it, in an [`EliasFanoSelectZero`], so that ranking and
+//! selection of ones are still available. Since the representation must then
+//! be smaller than the inventory of a selection structure, rather than than
+//! the bit vector, it is used only if the density of zeros is at most 1/256.
+//!
+//! For ranking (see [`build_rank`]), we use a [`Rank9`] for
+//! [`SpaceSpeed::Speed`] and a `RankSmall<1, 11>` (see
+//! [`rank_small![3; -]`](crate::rank_small)), which has a single 32-bit word
+//! of relative counters for each 2048 bits (an overhead of 3.125%), for
+//! [`SpaceSpeed::Space`].
+//!
+//! If you want to use a [`RankSmall`] but you do not know in advance which
+//! parameters to use, [`rank_small_params`] suggests a combination given the
+//! length and the density of the bit vector, and [`AnyRankSmall`] makes it
+//! possible to choose the combination at runtime.
+//!
+//! If you know in advance the structure you need, using it directly avoids
+//! dynamic dispatch.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sux::prelude::*;
+//! use sux::rank_sel::auto::*;
+//!
+//! let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0];
+//! let select = build_select(bits.clone(), SpaceSpeed::Speed);
+//! assert_eq!(select.select(0), Some(1));
+//! assert_eq!(select.select(4), None);
+//!
+//! let select_zero = build_select_zero(bits.clone(), SpaceSpeed::Speed);
+//! assert_eq!(select_zero.select_zero(0), Some(0));
+//!
+//! let rank = build_rank(bits, SpaceSpeed::Space);
+//! assert_eq!(rank.rank(4), 2);
+//! ```
+
+use crate::dict::elias_fano::{EfSeq, EliasFanoBuilder};
+use crate::prelude::*;
+use ambassador::Delegate;
+use epserde::*;
+use mem_dbg::*;
+
+use crate::ambassador_impl_AsRef;
+use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_BitCount;
+use crate::traits::rank_sel::ambassador_impl_BitLength;
+use crate::traits::rank_sel::ambassador_impl_NumBits;
+use crate::traits::rank_sel::ambassador_impl_Rank;
+use crate::traits::rank_sel::ambassador_impl_RankHinted;
+use crate::traits::rank_sel::ambassador_impl_RankUnchecked;
+use crate::traits::rank_sel::ambassador_impl_RankZero;
+use crate::traits::rank_sel::ambassador_impl_Select;
+use crate::traits::rank_sel::ambassador_impl_SelectHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectUnchecked;
+use crate::traits::rank_sel::ambassador_impl_SelectZeroHinted;
+use std::ops::Index;
+
+/// The space/speed tradeoff to target when choosing a structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpaceSpeed {
+    /// Prefer smaller structures.
+    Space,
+    /// Prefer faster structures.
+    Speed,
+}
+
+/// The kinds of selection structures that can be chosen by [`select_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectKind {
+    /// An [`EliasFanoSelect`].
+    EliasFano,
+    /// A [`SelectAdaptConst`] with default parameters.
+    SelectAdaptConst,
+    /// A [`SelectAdapt`] with the given maximum base-2 logarithm of the
+    /// number of 64-bit words per subinventory.
+    SelectAdapt(usize),
+}
+
+/// Returns the kind of selection structure that [`build_select`] would
+/// build on a bit vector of length `len` containing `num_ones` ones.
+///
+/// See the [module documentation](self) for the decision thresholds.
+pub fn select_kind(len: usize, num_ones: usize, target: SpaceSpeed) -> SelectKind {
+    if num_ones != 0 && num_ones * 64 <= len {
+        return SelectKind::EliasFano;
+    }
+    adapt_kind(len, num_ones, target)
+}
+
+/// Returns the kind of inventory-based selection structure to build on a bit
+/// vector of length `len` containing `num_ones` ones.
+fn adapt_kind(len: usize, num_ones: usize, target: SpaceSpeed) -> SelectKind {
+    match target {
+        SpaceSpeed::Speed if num_ones * 4 >= len && num_ones * 4 <= len * 3 => {
+            SelectKind::SelectAdaptConst
+        }
+        SpaceSpeed::Speed => SelectKind::SelectAdapt(3),
+        SpaceSpeed::Space => SelectKind::SelectAdapt(1),
+    }
+}
+
+/// Builds a selection structure on the given bit vector, choosing its type
+/// by [`select_kind`].
+pub fn build_select(bits: BitVec, target: SpaceSpeed) -> Box<dyn Select> {
+    let bits: AddNumBits<_> = bits.into();
+    match select_kind(bits.len(), bits.num_ones(), target) {
+        SelectKind::EliasFano => Box::new(EliasFanoSelect::new(&bits.into_inner())),
+        SelectKind::SelectAdaptConst => Box::new(SelectAdaptConst::<_, _>::new(bits)),
+        SelectKind::SelectAdapt(log2_u64_per_subinv) => {
+            Box::new(SelectAdapt::new(bits, log2_u64_per_subinv))
+        }
+    }
+}
+
+/// The kinds of selection structures for zeros that can be chosen by
+/// [`select_zero_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectZeroKind {
+    /// An [`EliasFanoSelectZero`].
+    EliasFano,
+    /// A [`SelectZeroAdaptConst`] with default parameters.
+    SelectZeroAdaptConst,
+    /// A [`SelectZeroAdapt`] with the given maximum base-2 logarithm of the
+    /// number of 64-bit words per subinventory.
+    SelectZeroAdapt(usize),
+}
+
+/// Returns the kind of selection structure for zeros that
+/// [`build_select_zero`] would build on a bit vector of length `len`
+/// containing `num_ones` ones.
+///
+/// See the [module documentation](self) for the decision thresholds.
+pub fn select_zero_kind(len: usize, num_ones: usize, target: SpaceSpeed) -> SelectZeroKind {
+    let num_zeros = len - num_ones;
+    if num_zeros != 0 && num_zeros * 256 <= len {
+        return SelectZeroKind::EliasFano;
+    }
+    match adapt_kind(len, num_zeros, target) {
+        SelectKind::EliasFano => unreachable!(),
+        SelectKind::SelectAdaptConst => SelectZeroKind::SelectZeroAdaptConst,
+        SelectKind::SelectAdapt(log2_u64_per_subinv) => {
+            SelectZeroKind::SelectZeroAdapt(log2_u64_per_subinv)
+        }
+    }
+}
+
+/// Builds a selection structure for zeros on the given bit vector, choosing
+/// its type by [`select_zero_kind`].
+pub fn build_select_zero(bits: BitVec, target: SpaceSpeed) -> Box<dyn SelectZero> {
+    let bits: AddNumBits<_> = bits.into();
+    match select_zero_kind(bits.len(), bits.num_ones(), target) {
+        SelectZeroKind::EliasFano => {
+            Box::new(EliasFanoSelectZero::new(Rank9::new(bits.into_inner())))
+        }
+        SelectZeroKind::SelectZeroAdaptConst => Box::new(SelectZeroAdaptConst::<_, _>::new(bits)),
+        SelectZeroKind::SelectZeroAdapt(log2_u64_per_subinv) => {
+            Box::new(SelectZeroAdapt::new(bits, log2_u64_per_subinv))
+        }
+    }
+}
+
+/// Builds a ranking structure on the given bit vector.
+///
+/// See the [module documentation](self) for the choice of structure.
+pub fn build_rank(bits: BitVec, target: SpaceSpeed) -> Box<dyn Rank> {
+    match target {
+        SpaceSpeed::Speed => Box::new(Rank9::new(bits)),
+        SpaceSpeed::Space => Box::new(crate::rank_small![3; bits]),
+    }
+}
+
+/// Returns the parameters `(NUM_U32S, COUNTER_WIDTH)` of the [`RankSmall`]
+/// structure suggested for a bit vector of length `len` with the given
+/// density (fraction of ones).
+///
+/// The decision is as follows:
+///
+/// - for bit vectors of at most 2²⁰ bits the additional space is negligible
+///   in absolute terms, so we suggest the fastest structure, `RankSmall<2,
+///   9>`;
+/// - if the density is at most 1/64 or at least 63/64, the bit vector is
+///   highly compressible, and selection on the rare bits is better served by
+///   an [`EliasFanoSelect`] (see [`select_kind`]); in this case ranking is
+///   rarely the bottleneck, and we suggest the smallest structure,
+///   `RankSmall<3, 13>`;
+/// - otherwise, we suggest `RankSmall<1, 10>` for bit vectors of at most 2²⁸
+///   bits and `RankSmall<1, 11>` for larger bit vectors.
+///
+/// The result can be passed to [`AnyRankSmall::new`]; if the length of the
+/// bit vector is known at compile time, the corresponding
+/// [`rank_small`](crate::rank_small) macro invocation will avoid the runtime
+/// dispatch.
+pub fn rank_small_params(len: usize, density: f64) -> (usize, usize) {
+    if len <= 1 << 20 {
+        (2, 9)
+    } else if density.min(1.0 - density) <= 1.0 / 64.0 {
+        (3, 13)
+    } else if len <= 1 << 28 {
+        (1, 10)
+    } else {
+        (1, 11)
+    }
+}
+
+/// A [`RankSmall`] structure whose parameters are chosen at runtime.
+///
+/// Each variant wraps one of the supported combinations of parameters,
+/// and ranking operations are forwarded to the wrapped structure.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::rank_sel::auto::*;
+///
+/// let bits = bit_vec![1, 0, 1, 1, 0, 1, 0, 1];
+/// let params = rank_small_params(bits.len(), 5.0 / 8.0);
+/// let rank = AnyRankSmall::new(bits, params);
+/// assert_eq!(rank.params(), (2, 9));
+/// assert_eq!(rank.rank(4), 3);
+/// assert_eq!(rank.rank_zero(4), 1);
+/// ```
+#[derive(Debug, Clone, MemDbg, MemSize)]
+pub enum AnyRankSmall<B = BitVec> {
+    /// A `RankSmall<2, 9>` (see [`rank_small![0; -]`](crate::rank_small)).
+    R2_9(RankSmall<2, 9, B>),
+    /// A `RankSmall<1, 9>` (see [`rank_small![1; -]`](crate::rank_small)).
+    R1_9(RankSmall<1, 9, B>),
+    /// A `RankSmall<1, 10>` (see [`rank_small![2; -]`](crate::rank_small)).
+    R1_10(RankSmall<1, 10, B>),
+    /// A `RankSmall<1, 11>` (see [`rank_small![3; -]`](crate::rank_small)).
+    R1_11(RankSmall<1, 11, B>),
+    /// A `RankSmall<3, 13>` (see [`rank_small![4; -]`](crate::rank_small)).
+    R3_13(RankSmall<3, 13, B>),
+}
+
+/// Forwards an expression to the structure wrapped by an [`AnyRankSmall`].
+macro_rules! any_rank_small {
+    ($self: expr, $r: ident => $e: expr) => {
+        match $self {
+            AnyRankSmall::R2_9($r) => $e,
+            AnyRankSmall::R1_9($r) => $e,
+            AnyRankSmall::R1_10($r) => $e,
+            AnyRankSmall::R1_11($r) => $e,
+            AnyRankSmall::R3_13($r) => $e,
+        }
+    };
+}
+
+impl<B: AsRef<[usize]> + BitLength + RankHinted<64>> AnyRankSmall<B> {
+    /// Creates a new structure on the given bit vector using the parameters
+    /// `(NUM_U32S, COUNTER_WIDTH)`, usually returned by
+    /// [`rank_small_params`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the combination of parameters is not
+    /// supported.
+    pub fn new(bits: B, params: (usize, usize)) -> Self {
+        match params {
+            (2, 9) => Self::R2_9(RankSmall::<2, 9, _>::new(bits)),
+            (1, 9) => Self::R1_9(RankSmall::<1, 9, _>::new(bits)),
+            (1, 10) => Self::R1_10(RankSmall::<1, 10, _>::new(bits)),
+            (1, 11) => Self::R1_11(RankSmall::<1, 11, _>::new(bits)),
+            (3, 13) => Self::R3_13(RankSmall::<3, 13, _>::new(bits)),
+            (num_u32s, counter_width) => panic!(
+                "Unsupported RankSmall parameters: NUM_U32S = {}, COUNTER_WIDTH = {}",
+                num_u32s, counter_width
+            ),
+        }
+    }
+}
+
+impl<B> AnyRankSmall<B> {
+    /// Returns the parameters `(NUM_U32S, COUNTER_WIDTH)` of the wrapped
+    /// structure.
+    pub fn params(&self) -> (usize, usize) {
+        match self {
+            Self::R2_9(_) => (2, 9),
+            Self::R1_9(_) => (1, 9),
+            Self::R1_10(_) => (1, 10),
+            Self::R1_11(_) => (1, 11),
+            Self::R3_13(_) => (3, 13),
+        }
+    }
+
+    pub fn into_inner(self) -> B {
+        any_rank_small!(self, r => r.into_inner())
+    }
+}
+
+impl<B: BitLength> BitLength for AnyRankSmall<B> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        any_rank_small!(self, r => BitLength::len(r))
+    }
+}
+
+impl<B: BitLength> NumBits for AnyRankSmall<B> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        any_rank_small!(self, r => r.num_ones())
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength + RankHinted<64>> RankUnchecked for AnyRankSmall<B> {
+    #[inline(always)]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        any_rank_small!(self, r => r.rank_unchecked(pos))
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength + RankHinted<64>> Rank for AnyRankSmall<B> {}
+
+impl<B: AsRef<[usize]> + BitLength + RankHinted<64>> RankZero for AnyRankSmall<B> {}
+
+/// A selection structure for sparse bit vectors storing the positions of
+/// the ones using an [Elias–Fano representation](crate::dict::elias_fano).
+///
+/// Differently from the other selection structures, this structure does not
+/// contain the underlying bit vector, and thus it does not provide access to
+/// the bits.
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize)]
+pub struct EliasFanoSelect<E = EfSeq> {
+    ones: E,
+    len: usize,
+}
+
+impl EliasFanoSelect {
+    /// Creates a new structure from a bit vector.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the bit vector contains no ones.
+    pub fn new<B: AsRef<[usize]>>(bits: &BitVec<B>) -> Self {
+        let num_ones = bits.count_ones();
+        assert!(num_ones != 0, "The bit vector contains no ones");
+        let len = bits.len();
+        let mut efb = EliasFanoBuilder::new(num_ones, len);
+        // SAFETY: positions are increasing and smaller than len
+        bits.iter_ones()
+            .for_each(|pos| unsafe { efb.push_unchecked(pos) });
+        Self {
+            ones: efb.build_with_seq(),
+            len,
+        }
+    }
+}
+
+impl<E> EliasFanoSelect<E> {
+    /// Returns the underlying Elias–Fano representation and the length in
+    /// bits.
+    pub fn into_raw_parts(self) -> (E, usize) {
+        (self.ones, self.len)
+    }
+}
+
+impl<E> BitLength for EliasFanoSelect<E> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<E: IndexedSeq<Input = usize, Output = usize>> NumBits for EliasFanoSelect<E> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.ones.len()
+    }
+}
+
+impl<E: IndexedSeq<Input = usize, Output = usize>> SelectUnchecked for EliasFanoSelect<E> {
+    #[inline(always)]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.ones.get_unchecked(rank)
+    }
+}
+
+impl<E: IndexedSeq<Input = usize, Output = usize>> Select for EliasFanoSelect<E> {}
+
+/// A selection structure for zeros for dense bit vectors storing the
+/// positions of the zeros using an [Elias–Fano
+/// representation](crate::dict::elias_fano).
+///
+/// When a bit vector contains very few zeros, storing their positions
+/// explicitly using an Elias–Fano representation uses less space than an
+/// inventory, and [selecting zeros](SelectZero::select_zero) reduces to an access to the
+/// representation. All other operations are forwarded to the underlying
+/// structure, usually a [`Rank9`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+/// use sux::rank_sel::auto::EliasFanoSelectZero;
+///
+/// let bits = bit_vec![1, 1, 0, 1, 1, 1, 0, 1];
+/// let select_zero = EliasFanoSelectZero::new(Rank9::new(bits));
+/// assert_eq!(select_zero.select_zero(0), Some(2));
+/// assert_eq!(select_zero.select_zero(1), Some(6));
+/// assert_eq!(select_zero.select_zero(2), None);
+/// // Other operations are forwarded
+/// assert_eq!(select_zero.rank(4), 3);
+/// assert!(select_zero[0]);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
+#[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(Index<usize>, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
+#[delegate(crate::traits::rank_sel::NumBits, target = "bits")]
+#[delegate(crate::traits::rank_sel::Rank, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankUnchecked, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankZero, target = "bits")]
+#[delegate(crate::traits::rank_sel::Select, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectUnchecked, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectZeroHinted, target = "bits")]
+pub struct EliasFanoSelectZero<B = Rank9, E = EfSeq> {
+    bits: B,
+    zeros: E,
+}
+
+impl<B: AsRef<[usize]> + BitLength> EliasFanoSelectZero<B> {
+    /// Creates a new structure from a bit vector, or a structure wrapping a
+    /// bit vector.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the bit vector contains no zeros.
+    pub fn new(bits: B) -> Self {
+        let len = bits.len();
+        // SAFETY: the length is the length of the underlying bit vector
+        let bit_vec = unsafe { BitVec::from_raw_parts(bits.as_ref(), len) };
+        let num_zeros = len - bit_vec.count_ones();
+        assert!(num_zeros != 0, "The bit vector contains no zeros");
+        let mut efb = EliasFanoBuilder::new(num_zeros, len);
+        // SAFETY: positions are increasing and smaller than len
+        bit_vec
+            .iter_zeros()
+            .for_each(|pos| unsafe { efb.push_unchecked(pos) });
+        Self {
+            bits,
+            zeros: efb.build_with_seq(),
+        }
+    }
+}
+
+impl<B: BitLength, E> EliasFanoSelectZero<B, E> {
+    /// Returns the number of bits in the bit vector.
+    ///
+    /// This method is equivalent to
+    /// [`BitLength::len`](crate::traits::BitLength::len), but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        BitLength::len(self)
+    }
+}
+
+impl<B, E> EliasFanoSelectZero<B, E> {
+    /// Returns the Elias–Fano representation of the positions of the zeros.
+    pub fn zeros(&self) -> &E {
+        &self.zeros
+    }
+
+    /// Returns the underlying structure and the Elias–Fano representation of
+    /// the positions of the zeros.
+    pub fn into_raw_parts(self) -> (B, E) {
+        (self.bits, self.zeros)
+    }
+
+    pub fn into_inner(self) -> B {
+        self.bits
+    }
+}
+
+impl<B, E: IndexedSeq<Input = usize, Output = usize>> SelectZeroUnchecked
+    for EliasFanoSelectZero<B, E>
+{
+    #[inline(always)]
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        self.zeros.get_unchecked(rank)
+    }
+}
+
+impl<B: NumBits, E: IndexedSeq<Input = usize, Output = usize>> SelectZero
+    for EliasFanoSelectZero<B, E>
+{
+}
@@ -107,6 +107,12 @@ pub use select_adapt_const::*;
 mod select_zero_adapt_const;
 pub use select_zero_adapt_const::*;
 
+mod select_dual_adapt_const;
+pub use select_dual_adapt_const::*;
+
+mod select_as_zero;
+pub use select_as_zero::*;
+
 mod rank_small;
 pub use rank_small::*;
 
@@ -121,3 +127,8 @@ pub use rank9::*;
 
 mod select9;
 pub use select9::*;
+
+mod mut_rank9;
+pub use mut_rank9::*;
+
+pub mod auto;
@@ -0,0 +1,200 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::rank9::BlockCounters;
+use crate::prelude::*;
+use ambassador::Delegate;
+use epserde::*;
+use mem_dbg::*;
+
+use crate::ambassador_impl_AsRef;
+use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_BitLength;
+use crate::traits::rank_sel::ambassador_impl_RankHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectZeroHinted;
+use std::ops::Index;
+
+/// A mutable version of [`Rank9`] whose counters are kept up to date when
+/// bits are [set](MutRank9::set).
+///
+/// The layout of the counters is the same as that of [`Rank9`], so ranking
+/// has exactly the same cost. Setting a bit to a value different from the
+/// current one, however, requires updating the relative counters of its block
+/// and the absolute counters of all following blocks, so its cost is linear
+/// in the number of blocks after the one containing the bit. This structure
+/// is thus convenient only when updates are rare, or concentrated towards the
+/// end of the bit vector: for bulk updates, it is much faster to modify the
+/// underlying bit vector and build a new [`Rank9`], which takes time linear
+/// in the number of blocks.
+///
+/// A [`MutRank9`] can be turned at no cost into a [`Rank9`] using
+/// [`into_rank9`](MutRank9::into_rank9), and vice versa using [`From`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+///
+/// let mut rank9 = MutRank9::new(bit_vec![1, 0, 1, 1, 0, 1, 0, 1]);
+/// assert_eq!(rank9.rank(4), 3);
+/// rank9.set(1, true);
+/// assert_eq!(rank9.rank(4), 4);
+/// rank9.set(0, false);
+/// assert_eq!(rank9.rank(4), 3);
+/// assert_eq!(rank9.num_ones(), 5);
+///
+/// // Back to a static structure
+/// let rank9 = rank9.into_rank9();
+/// assert_eq!(rank9.rank(8), 5);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
+#[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(Index<usize>, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectZeroHinted, target = "bits")]
+pub struct MutRank9<B = BitVec, C = Vec<BlockCounters>> {
+    bits: B,
+    counts: C,
+}
+
+impl<B: AsRef<[usize]> + BitLength> MutRank9<B, Vec<BlockCounters>> {
+    /// Creates a new MutRank9 structure from a given bit vector.
+    pub fn new(bits: B) -> Self {
+        Rank9::new(bits).into()
+    }
+}
+
+impl<B, C> MutRank9<B, C> {
+    pub fn into_inner(self) -> B {
+        self.bits
+    }
+}
+
+impl<B> MutRank9<B, Vec<BlockCounters>> {
+    /// Returns a static [`Rank9`] structure with the same content.
+    pub fn into_rank9(self) -> Rank9<B, Box<[BlockCounters]>> {
+        Rank9 {
+            bits: self.bits,
+            counts: self.counts.into_boxed_slice(),
+        }
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength> From<Rank9<B, Box<[BlockCounters]>>>
+    for MutRank9<B, Vec<BlockCounters>>
+{
+    fn from(rank9: Rank9<B, Box<[BlockCounters]>>) -> Self {
+        Self {
+            bits: rank9.bits,
+            counts: rank9.counts.into_vec(),
+        }
+    }
+}
+
+impl<B, T: IntoBitVec<B>, C> IntoBitVec<B> for MutRank9<T, C> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
+impl<B: BitLength, C> MutRank9<B, C> {
+    /// Returns the number of bits in the underlying bit vector.
+    ///
+    /// This method is equivalent to
+    /// [`BitLength::len`](crate::traits::BitLength::len), but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        BitLength::len(self)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + AsMut<[usize]> + BitLength,
+        C: AsRef<[BlockCounters]> + AsMut<[BlockCounters]>,
+    > MutRank9<B, C>
+{
+    /// Sets the bit of given position to the given value, updating the
+    /// counters.
+    ///
+    /// If the bit changes, this method takes time linear in the number of
+    /// blocks of 512 bits following the one containing the bit.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is not smaller than the length of the
+    /// underlying bit vector.
+    pub fn set(&mut self, pos: usize, value: bool) {
+        let len = BitLength::len(self);
+        if pos >= len {
+            panic!("Bit index out of bounds: {} >= {}", pos, len);
+        }
+        let word_pos = pos / usize::BITS as usize;
+        let bit_pos = pos % usize::BITS as usize;
+        let word = &mut self.bits.as_mut()[word_pos];
+        if (*word >> bit_pos & 1 != 0) == value {
+            return;
+        }
+        *word ^= 1 << bit_pos;
+
+        let block = word_pos / Rank9::<B, C>::WORDS_PER_BLOCK;
+        let offset = word_pos % Rank9::<B, C>::WORDS_PER_BLOCK;
+        // A one in the 9-bit field of each relative counter following the
+        // modified word; as all counters are in [0..512) before and after
+        // the update, adding or subtracting this mask does not cause carries
+        // or borrows between fields
+        let mask = (offset + 1..Rank9::<B, C>::WORDS_PER_BLOCK)
+            .fold(0, |mask, j| mask | 1 << (9 * (j ^ 7)));
+
+        let counts = self.counts.as_mut();
+        if value {
+            counts[block].relative += mask;
+            counts[block + 1..].iter_mut().for_each(|c| c.absolute += 1);
+        } else {
+            counts[block].relative -= mask;
+            counts[block + 1..].iter_mut().for_each(|c| c.absolute -= 1);
+        }
+    }
+}
+
+impl<B: BitLength, C: AsRef<[BlockCounters]>> NumBits for MutRank9<B, C> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        // SAFETY: The last counter is always present
+        unsafe { self.counts.as_ref().last().unwrap_unchecked().absolute }
+    }
+}
+
+impl<B: BitLength, C: AsRef<[BlockCounters]>> BitCount for MutRank9<B, C> {
+    #[inline(always)]
+    fn count_ones(&self) -> usize {
+        self.num_ones()
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> RankUnchecked for MutRank9<B, C> {
+    /// # Safety
+    ///
+    /// The same weakened safety requirement of [`Rank9`] applies.
+    #[inline(always)]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        let word_pos = pos / usize::BITS as usize;
+        let bit_pos = pos % usize::BITS as usize;
+        let block = word_pos / Rank9::<B, C>::WORDS_PER_BLOCK;
+        let offset = word_pos % Rank9::<B, C>::WORDS_PER_BLOCK;
+        let word = self.bits.as_ref().get_unchecked(word_pos);
+        let counts = self.counts.as_ref().get_unchecked(block);
+
+        counts.absolute + counts.rel(offset) + (word & ((1 << bit_pos) - 1)).count_ones() as usize
+    }
+}
+
+impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> Rank for MutRank9<B, C> {}
+impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> RankZero for MutRank9<B, C> {}
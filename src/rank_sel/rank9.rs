@@ -92,7 +92,11 @@ pub struct Rank9<B = BitVec, C = Box<[BlockCounters]>> {
     pub(super) counts: C,
 }
 
-#[doc(hidden)]
+/// The counters of a 512-bit block of a [`Rank9`].
+///
+/// The absolute counter is the number of ones before the block, and the
+/// relative counters, one for each word of the block, are the number of
+/// ones between the start of the block and the start of the word.
 #[derive(Epserde, Copy, Debug, Clone, MemDbg, MemSize, Default)]
 #[repr(C)]
 #[zero_copy]
@@ -102,11 +106,23 @@ pub struct BlockCounters {
 }
 
 impl BlockCounters {
+    /// Returns the number of ones before the block.
+    #[inline(always)]
+    pub fn absolute(&self) -> usize {
+        self.absolute
+    }
+
+    /// Returns the number of ones between the start of the block and the
+    /// start of the word of index `word` in the block.
     #[inline(always)]
     pub fn rel(&self, word: usize) -> usize {
         self.relative >> (9 * (word ^ 7)) & 0x1FF
     }
 
+    /// Sets the relative counter of the word of index `word` in the block.
+    ///
+    /// The counter is combined with the current one using a logical or, so
+    /// it should be set just once.
     #[inline(always)]
     pub fn set_rel(&mut self, word: usize, counter: usize) {
         self.relative |= counter << (9 * (word ^ 7));
@@ -137,6 +153,13 @@ impl<B, C> Rank9<B, C> {
     }
 }
 
+impl<B, T: IntoBitVec<B>, C> IntoBitVec<B> for Rank9<T, C> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<B: BitLength, C> Rank9<B, C> {
     /// Returns the number of bits in the underlying bit vector.
     ///
@@ -152,17 +175,40 @@ impl<B: BitLength, C> Rank9<B, C> {
 impl<B: AsRef<[usize]> + BitLength> Rank9<B, Box<[BlockCounters]>> {
     /// Creates a new Rank9 structure from a given bit vector.
     pub fn new(bits: B) -> Self {
-        let num_bits = bits.len();
-        let num_words = num_bits.div_ceil(usize::BITS as usize);
-        let num_counts = num_bits.div_ceil(usize::BITS as usize * Self::WORDS_PER_BLOCK);
+        let mut counts = vec![BlockCounters::default(); Self::expected_counts(&bits)];
+        Self::fill_counts(&bits, &mut counts);
 
+        Self {
+            bits,
+            counts: counts.into(),
+        }
+    }
+
+    /// Returns the number of [`BlockCounters`] of a structure built on the
+    /// given bit vector.
+    ///
+    /// This is the length of the slice that must be passed to
+    /// [`new_in`](Rank9::new_in).
+    pub fn expected_counts(bits: &B) -> usize {
         // We use the last counter to store the total number of ones
-        let mut counts = Vec::with_capacity(num_counts + 1);
+        bits.len()
+            .div_ceil(usize::BITS as usize * Self::WORDS_PER_BLOCK)
+            + 1
+    }
+
+    /// Computes the counters of the given bit vector into `counts`, which must
+    /// have length [`expected_counts(bits)`](Rank9::expected_counts).
+    fn fill_counts(bits: &B, counts: &mut [BlockCounters]) {
+        let num_words = bits.num_words();
+        assert_eq!(counts.len(), Self::expected_counts(bits));
 
         let mut num_ones = 0;
 
-        for i in (0..num_words).step_by(Self::WORDS_PER_BLOCK) {
-            let mut count = BlockCounters {
+        for (count, i) in counts
+            .iter_mut()
+            .zip((0..num_words).step_by(Self::WORDS_PER_BLOCK))
+        {
+            *count = BlockCounters {
                 absolute: num_ones,
                 relative: 0,
             };
@@ -175,19 +221,71 @@ impl<B: AsRef<[usize]> + BitLength> Rank9<B, Box<[BlockCounters]>> {
                     num_ones += bits.as_ref()[i + j].count_ones() as usize;
                 }
             }
-
-            counts.push(count);
         }
 
-        counts.push(BlockCounters {
+        // SAFETY: there is always at least one counter
+        *unsafe { counts.last_mut().unwrap_unchecked() } = BlockCounters {
             absolute: num_ones,
             relative: 0,
-        });
+        };
+    }
+}
 
-        Self {
-            bits,
-            counts: counts.into(),
-        }
+impl<'a, B: AsRef<[usize]> + BitLength> Rank9<B, &'a mut [BlockCounters]> {
+    /// Creates a new Rank9 structure from a given bit vector, storing the
+    /// counters in a slice provided by the caller.
+    ///
+    /// The slice must have length
+    /// [`expected_counts(&bits)`](Rank9::expected_counts); its previous
+    /// content is overwritten. In this way, the counters can be written,
+    /// for example, in a preallocated region of a memory-mapped file, rather
+    /// than in a newly allocated vector.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `counts` has not the expected length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::prelude::*;
+    /// # use sux::rank_sel::BlockCounters;
+    /// let bits = bit_vec![1, 0, 1, 1, 0, 1, 0, 1];
+    /// let mut counts = vec![BlockCounters::default(); Rank9::expected_counts(&bits)];
+    /// let rank9 = Rank9::new_in(&bits, counts.as_mut_slice());
+    /// assert_eq!(rank9.rank(4), 3);
+    /// ```
+    pub fn new_in(bits: B, counts: &'a mut [BlockCounters]) -> Self {
+        Rank9::<B, Box<[BlockCounters]>>::fill_counts(&bits, counts);
+        Self { bits, counts }
+    }
+}
+
+impl<B, C: AsRef<[BlockCounters]>> Rank9<B, C> {
+    /// Returns the counters of the blocks of 512 bits.
+    ///
+    /// There is one counter for each block, plus a final counter whose
+    /// [absolute count](BlockCounters::absolute) is the number of ones in the
+    /// bit vector.
+    #[inline(always)]
+    pub fn blocks(&self) -> &[BlockCounters] {
+        self.counts.as_ref()
+    }
+
+    /// Returns the number of ones before the block of 512 bits of given
+    /// index.
+    ///
+    /// The rank of position `pos` is the rank of block `pos / 512` plus the
+    /// number of ones in the block up to `pos`. Block of index equal to the
+    /// number of blocks has rank equal to the number of ones.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `block` is greater than the number of
+    /// blocks.
+    #[inline(always)]
+    pub fn block_rank(&self, block: usize) -> usize {
+        self.counts.as_ref()[block].absolute
     }
 }
 
@@ -13,7 +13,7 @@ use std::ptr::{addr_of, addr_of_mut, read_unaligned, write_unaligned};
 
 use crate::{
     prelude::{BitLength, BitVec, Rank, RankHinted, RankUnchecked, RankZero},
-    traits::{BitCount, NumBits},
+    traits::{BitCount, IntoBitVec, NumBits},
 };
 
 use crate::ambassador_impl_AsRef;
@@ -452,6 +452,58 @@ impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B: BitLength, C1, C2>
     pub fn len(&self) -> usize {
         BitLength::len(self)
     }
+
+    /// Returns the number of ones in the bit vector.
+    ///
+    /// This method is equivalent to
+    /// [`NumBits::num_ones`](crate::traits::NumBits::num_ones), but it is
+    /// provided to reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn num_ones(&self) -> usize {
+        self.num_ones
+    }
+}
+
+impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B: AsRef<[usize]> + BitLength, C1, C2>
+    RankSmall<NUM_U32S, COUNTER_WIDTH, B, C1, C2>
+where
+    Self: Rank,
+{
+    /// Returns the number of ones in the range `start..end`.
+    ///
+    /// The bit vector is virtually zero-extended, so `end` can be larger than
+    /// the length. If `start` and `end` are within a subblock the ones are
+    /// counted directly on the words of the bit vector, without accessing the
+    /// counters; otherwise, the result is the difference of two ranks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::{bit_vec, rank_small};
+    /// let rank_small = rank_small![1; bit_vec![1, 0, 1, 1, 0, 1, 0, 1]];
+    /// assert_eq!(rank_small.rank_range(1, 6), 3);
+    /// assert_eq!(rank_small.rank_range(3, 3), 0);
+    /// assert_eq!(rank_small.rank_range(5, 100), 2);
+    /// ```
+    pub fn rank_range(&self, start: usize, end: usize) -> usize {
+        let end = end.min(self.len());
+        if start >= end {
+            return 0;
+        }
+        let start_word = start / 64;
+        let end_word = (end - 1) / 64;
+        if end_word - start_word < Self::WORDS_PER_SUBBLOCK {
+            let words = &self.bits.as_ref()[start_word..=end_word];
+            let mut count = words.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+            count -= (words[0] & ((1 << (start % 64)) - 1)).count_ones() as usize;
+            if end % 64 != 0 {
+                count -= (words[words.len() - 1] >> (end % 64)).count_ones() as usize;
+            }
+            count
+        } else {
+            self.rank(end) - self.rank(start)
+        }
+    }
 }
 
 impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B, C1, C2>
@@ -483,6 +535,15 @@ impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B, C1, C2>
     }
 }
 
+impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B, T: IntoBitVec<B>, C1, C2> IntoBitVec<B>
+    for RankSmall<NUM_U32S, COUNTER_WIDTH, T, C1, C2>
+{
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B: BitLength, C1, C2> NumBits
     for RankSmall<NUM_U32S, COUNTER_WIDTH, B, C1, C2>
 {
@@ -9,8 +9,8 @@
 use super::rank9::BlockCounters;
 use super::Rank9;
 use crate::{
-    prelude::SelectUnchecked,
-    traits::{BitLength, NumBits, Select},
+    prelude::{BitVec, SelectUnchecked},
+    traits::{BitLength, IntoBitVec, NumBits, Select},
 };
 use ambassador::Delegate;
 use common_traits::SelectInWord;
@@ -145,6 +145,13 @@ impl<R, I> Select9<R, I> {
     const ONES_PER_INVENTORY: usize = 1 << Self::LOG2_ZEROS_PER_INVENTORY;
 }
 
+impl<B, R: IntoBitVec<B>, I> IntoBitVec<B> for Select9<R, I> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.rank9.into_bit_vec()
+    }
+}
+
 impl<R: BitLength, I> Select9<R, I> {
     /// Returns the number of bits in the underlying bit vector.
     ///
@@ -159,13 +166,11 @@ impl<R: BitLength, I> Select9<R, I> {
 
 impl<B: AsRef<[usize]> + BitLength, C: AsRef<[BlockCounters]>> Select9<Rank9<B, C>, Box<[usize]>> {
     pub fn new(rank9: Rank9<B, C>) -> Self {
-        let num_bits = rank9.len();
-        let num_words = (num_bits + 63) / 64;
-        let inventory_size =
-            (rank9.num_ones() + Self::ONES_PER_INVENTORY - 1) / Self::ONES_PER_INVENTORY;
+        let num_words = rank9.num_words();
+        let inventory_size = rank9.num_ones().div_ceil(Self::ONES_PER_INVENTORY);
 
         let u64_per_subinventory = 4;
-        let subinventory_size = (num_words + u64_per_subinventory - 1) / u64_per_subinventory;
+        let subinventory_size = num_words.div_ceil(u64_per_subinventory);
 
         let mut inventory = Vec::with_capacity(inventory_size + 1);
         let mut subinventory = vec![0; subinventory_size].into_boxed_slice();
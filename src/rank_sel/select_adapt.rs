@@ -13,8 +13,8 @@ use mem_dbg::{MemDbg, MemSize};
 use std::cmp::{max, min};
 
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
-    traits::{NumBits, SelectUnchecked},
+    prelude::{BitCount, BitFieldSlice, BitLength, BitVec, Select, SelectHinted},
+    traits::{IntoBitVec, NumBits, SelectUnchecked},
 };
 
 use crate::ambassador_impl_AsRef;
@@ -340,6 +340,13 @@ impl<B, I> SelectAdapt<B, I> {
     pub const DEFAULT_TARGET_INVENTORY_SPAN: usize = 8192;
 }
 
+impl<B, T: IntoBitVec<B>, I> IntoBitVec<B> for SelectAdapt<T, I> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<B: BitLength, C> SelectAdapt<B, C> {
     /// Returns the number of bits in the bit vector.
     ///
@@ -352,6 +359,42 @@ impl<B: BitLength, C> SelectAdapt<B, C> {
     }
 }
 
+/// Returns recommended values for the base-2 logarithm of the number of ones
+/// per inventory and for the base-2 logarithm of the number of 64-bit words per
+/// subinventory for a bit vector of length `len` containing `num_ones` ones.
+///
+/// The first value is computed as [`SelectAdapt::with_span`] would using a
+/// [default target inventory
+/// span](SelectAdapt::DEFAULT_TARGET_INVENTORY_SPAN), but it is never smaller
+/// than two; the second value is 3, or less if there are too few ones per
+/// inventory to fill a subinventory.
+///
+/// The values can be used as the `LOG2_ONES_PER_INVENTORY` and
+/// `LOG2_U64_PER_SUBINVENTORY` parameters of [`SelectAdaptConst`], or passed to
+/// [`SelectAdapt::with_inv`] (which is what
+/// [`SelectAdapt::with_recommended`] does). Note that on very sparse bit
+/// vectors (less than a one every 16384 bits) the span of an inventory entry
+/// will be too large for a [`SelectAdaptConst`] anyway (see
+/// [`SelectAdaptConst::new_checked`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::rank_sel::recommend_params;
+///
+/// // Dense vectors need more ones per inventory
+/// assert_eq!(recommend_params(1 << 20, 1 << 21), (12, 3));
+/// assert_eq!(recommend_params(1 << 10, 1 << 21), (2, 0));
+/// ```
+pub fn recommend_params(num_ones: usize, len: usize) -> (usize, usize) {
+    let log2_ones_per_inventory = (num_ones * SelectAdapt::<(), ()>::DEFAULT_TARGET_INVENTORY_SPAN)
+        .div_ceil(max(1, len))
+        .max(4)
+        .ilog2() as usize;
+    let log2_u64_per_subinventory = min(3, log2_ones_per_inventory - 2);
+    (log2_ones_per_inventory, log2_u64_per_subinventory)
+}
+
 impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
     /// Creates a new selection structure over a bit vecotr using a
     /// [default target inventory
@@ -375,6 +418,30 @@ impl<B: AsRef<[usize]> + BitCount> SelectAdapt<B, Box<[usize]>> {
         )
     }
 
+    /// Creates a new selection structure over a bit vector using the
+    /// parameters returned by [`recommend_params`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let bits: AddNumBits<_> = bit_vec![0, 1, 1, 0, 1, 0, 0, 1].into();
+    /// let select = SelectAdapt::with_recommended(bits);
+    /// assert_eq!(select.select(2), Some(4));
+    /// ```
+    pub fn with_recommended(bits: B) -> Self {
+        let num_ones = bits.count_ones();
+        let (log2_ones_per_inventory, log2_u64_per_subinventory) =
+            recommend_params(num_ones, bits.len());
+        Self::_new(
+            bits,
+            num_ones,
+            log2_ones_per_inventory,
+            log2_u64_per_subinventory,
+        )
+    }
+
     /// Creates a new selection structure over a bit vector with a specified
     /// target inventory span.
     ///
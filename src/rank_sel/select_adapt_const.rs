@@ -14,8 +14,9 @@ use mem_dbg::{MemDbg, MemSize};
 use std::cmp::{max, min};
 
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength, Select, SelectHinted},
-    traits::{NumBits, SelectUnchecked},
+    bits::OnesIterator,
+    prelude::{BitCount, BitFieldSlice, BitLength, BitVec, Select, SelectHinted},
+    traits::{IntoBitVec, NumBits, RankUnchecked, SelectUnchecked, SelectZeroHinted},
 };
 
 use crate::ambassador_impl_AsRef;
@@ -33,6 +34,54 @@ use crate::traits::rank_sel::ambassador_impl_SelectZeroHinted;
 use crate::traits::rank_sel::ambassador_impl_SelectZeroUnchecked;
 use std::ops::Index;
 
+/// The error returned by [`SelectAdaptConst::new_checked`] when the
+/// parameters of the structure are not suited to the bit vector.
+///
+/// Since this type implements [`std::error::Error`], it can be converted
+/// into an [`anyhow::Error`] using the `?` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamsMismatch {
+    /// The average span of an inventory entry (i.e., the average distance
+    /// between indexed ones) does not fit the 16-bit subinventory, so
+    /// subinventories will use 32-bit or 64-bit entries, possibly spilling.
+    /// `LOG2_ONES_PER_INVENTORY` should be decreased.
+    SpanTooLarge {
+        /// The average span, in bits, of an inventory entry.
+        avg_span: usize,
+    },
+    /// The 16-bit subinventory has more entries than the number of ones in
+    /// an inventory entry, so part of it is wasted.
+    /// `LOG2_U64_PER_SUBINVENTORY` should be decreased.
+    SubinventoryTooLarge {
+        /// The number of 16-bit entries in a subinventory.
+        entries: usize,
+        /// The number of ones per inventory entry.
+        ones_per_inventory: usize,
+    },
+}
+
+impl core::fmt::Display for ParamsMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParamsMismatch::SpanTooLarge { avg_span } => write!(
+                f,
+                "The average inventory span ({} bits) does not fit a 16-bit subinventory",
+                avg_span
+            ),
+            ParamsMismatch::SubinventoryTooLarge {
+                entries,
+                ones_per_inventory,
+            } => write!(
+                f,
+                "The subinventory has {} entries, but there are just {} ones per inventory",
+                entries, ones_per_inventory
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamsMismatch {}
+
 /// A const-based version of [`SelectAdapt`](super::SelectAdapt).
 ///
 /// The code of this structure is essentially the same of
@@ -53,6 +102,11 @@ use std::ops::Index;
 ///
 /// [`SelectZeroAdaptConst`](super::SelectZeroAdaptConst) is a variant of this
 /// structure that provides the same functionality for zero bits.
+///
+/// 16-bit and 32-bit subinventory entries are packed into `usize` words by
+/// reinterpreting the words as slices of `u16` or `u32`, so their layout
+/// depends on the endianness of the platform: an inventory serialized on a
+/// little-endian platform cannot be used on a big-endian one, and vice versa.
 
 ///
 /// # Examples
@@ -162,9 +216,9 @@ pub struct SelectAdaptConst<
     const LOG2_ONES_PER_INVENTORY: usize = 12,
     const LOG2_U64_PER_SUBINVENTORY: usize = 3,
 > {
-    bits: B,
-    inventory: I,
-    spill: I,
+    pub(super) bits: B,
+    pub(super) inventory: I,
+    pub(super) spill: I,
 }
 
 impl<B, I, const LOG2_ONES_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTORY: usize>
@@ -173,7 +227,7 @@ impl<B, I, const LOG2_ONES_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTORY
     const LOG2_ONES_PER_SUB16: usize =
         LOG2_ONES_PER_INVENTORY.saturating_sub(LOG2_U64_PER_SUBINVENTORY + 2);
     const ONES_PER_SUB16_MASK: usize = (1 << Self::LOG2_ONES_PER_SUB16) - 1;
-    const ONES_PER_INVENTORY: usize = (1 << LOG2_ONES_PER_INVENTORY);
+    pub(crate) const ONES_PER_INVENTORY: usize = (1 << LOG2_ONES_PER_INVENTORY);
     const ONES_PER_INVENTORY_MASK: usize = (1 << LOG2_ONES_PER_INVENTORY) - 1;
 
     // Compute adaptively the number of 32-bit subinventory entries
@@ -209,6 +263,118 @@ impl<B, I, const LOG2_ONES_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTORY
     }
 
     pub const DEFAULT_TARGET_INVENTORY_SPAN: usize = 8192;
+
+    /// Returns the number of words of the inventory (including the
+    /// subinventories, but excluding the spill buffer) of a structure built
+    /// on a bit vector containing `num_ones` ones.
+    pub fn expected_inventory_words(num_ones: usize) -> usize {
+        num_ones.div_ceil(Self::ONES_PER_INVENTORY) * ((1 << LOG2_U64_PER_SUBINVENTORY) + 1) + 1
+    }
+
+    /// Checks whether the parameters of the structure are suited to a bit
+    /// vector of length `len` containing `num_ones` ones.
+    fn check_params(len: usize, num_ones: usize) -> Result<(), ParamsMismatch> {
+        let entries = 4 << LOG2_U64_PER_SUBINVENTORY;
+        if entries > Self::ONES_PER_INVENTORY {
+            return Err(ParamsMismatch::SubinventoryTooLarge {
+                entries,
+                ones_per_inventory: Self::ONES_PER_INVENTORY,
+            });
+        }
+        if num_ones != 0 {
+            let avg_span = (len as u128 * Self::ONES_PER_INVENTORY as u128 / num_ones as u128)
+                .min(usize::MAX as u128) as usize;
+            if SpanType::from_span(avg_span) != SpanType::U16 {
+                return Err(ParamsMismatch::SpanTooLarge { avg_span });
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a new selection structure from its components.
+    ///
+    /// This method makes it possible to rebuild a structure whose components
+    /// have been stored separately (e.g., by custom serialization) without
+    /// recomputing the inventory and the spill buffer.
+    ///
+    /// # Safety
+    ///
+    /// `inventory` and `spill` must be identical to those that would be built
+    /// by [`new`](SelectAdaptConst::new) on `bits` with the same parameters.
+    /// If you cannot guarantee this condition, use
+    /// [`try_from_parts`](SelectAdaptConst::try_from_parts).
+    pub unsafe fn from_raw_parts(bits: B, inventory: I, spill: I) -> Self {
+        Self {
+            bits,
+            inventory,
+            spill,
+        }
+    }
+
+    /// Returns the bit vector, the inventory, and the spill buffer.
+    pub fn into_raw_parts(self) -> (B, I, I) {
+        (self.bits, self.inventory, self.spill)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + BitCount,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns whether the inventory and the spill buffer are consistent with
+    /// the bit vector.
+    ///
+    /// The check is performed by building a new inventory and spill buffer,
+    /// so it takes the same time as [`new`](SelectAdaptConst::new).
+    pub fn validate(&self) -> bool {
+        let expected = SelectAdaptConst::<
+            &B,
+            Box<[usize]>,
+            LOG2_ONES_PER_INVENTORY,
+            LOG2_U64_PER_SUBINVENTORY,
+        >::new(&self.bits);
+        self.inventory.as_ref() == &*expected.inventory && self.spill.as_ref() == &*expected.spill
+    }
+
+    /// Creates a new selection structure from its components, checking
+    /// their consistency using [`validate`](SelectAdaptConst::validate).
+    ///
+    /// Returns `None` if the components are not consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// # use sux::traits::{Select, AddNumBits};
+    /// # use sux::rank_sel::SelectAdaptConst;
+    /// let bits: AddNumBits<_> = bit_vec![1, 0, 1, 1, 0, 1, 0, 1].into();
+    /// let (bits, inventory, spill) = SelectAdaptConst::<_, _>::new(bits).into_raw_parts();
+    ///
+    /// let select = SelectAdaptConst::<_, _>::try_from_parts(bits, inventory, spill).unwrap();
+    /// assert_eq!(select.select(3), Some(5));
+    /// ```
+    pub fn try_from_parts(bits: B, inventory: I, spill: I) -> Option<Self> {
+        // SAFETY: the structure is returned only if it is valid
+        let select = unsafe { Self::from_raw_parts(bits, inventory, spill) };
+        select.validate().then_some(select)
+    }
+}
+
+impl<
+        B,
+        T: IntoBitVec<B>,
+        I,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > IntoBitVec<B> for SelectAdaptConst<T, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
 }
 
 impl<
@@ -229,53 +395,191 @@ impl<
     }
 }
 
+impl<
+        B: BitLength,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns the number of bytes used by the inventory, including the
+    /// subinventories and the spill buffer.
+    #[inline(always)]
+    pub fn inventory_bytes(&self) -> usize {
+        (self.inventory.as_ref().len() + self.spill.as_ref().len()) * core::mem::size_of::<usize>()
+    }
+
+    /// Returns the ratio between the [number of bytes used by the
+    /// inventory](SelectAdaptConst::inventory_bytes) and the number of bytes
+    /// of the words of the bit vector, or zero if the bit vector is empty.
+    ///
+    /// Every 2<sup>`LOG2_ONES_PER_INVENTORY`</sup> ones the structure uses
+    /// 1 + 2<sup>`LOG2_U64_PER_SUBINVENTORY`</sup> words, so, barring the
+    /// spill buffer, which is empty when the ones are not too sparse, on a bit
+    /// vector with density of ones *d* the ratio is approximately
+    /// 64*d*(1 + 2<sup>`LOG2_U64_PER_SUBINVENTORY`</sup>) /
+    /// 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>: with the default parameters,
+    /// less than 0.15*d*.
+    pub fn overhead_fraction(&self) -> f64 {
+        let num_words = self.bits.num_words();
+        if num_words == 0 {
+            0.0
+        } else {
+            self.inventory_bytes() as f64 / (num_words * core::mem::size_of::<usize>()) as f64
+        }
+    }
+}
+
 impl<
         B: AsRef<[usize]> + BitCount,
         const LOG2_ONES_PER_INVENTORY: usize,
         const LOG2_U64_PER_SUBINVENTORY: usize,
     > SelectAdaptConst<B, Box<[usize]>, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
 {
+    /// Creates a new selection structure, checking first that the parameters
+    /// are suited to the density of the bit vector.
+    ///
+    /// A [`ParamsMismatch`] is returned if the average distance between
+    /// indexed ones does not fit a 16-bit subinventory, which makes the
+    /// structure slower and larger, or if the subinventory is larger than
+    /// the number of ones per inventory entry, which wastes space. The check
+    /// requires just counting the ones of the bit vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// # use sux::traits::AddNumBits;
+    /// # use sux::rank_sel::{ParamsMismatch, SelectAdaptConst};
+    /// let bits: AddNumBits<_> = bit_vec![1, 0, 1, 1, 0, 1, 0, 1].into();
+    /// assert!(SelectAdaptConst::<_, _>::new_checked(bits.clone()).is_ok());
+    /// // 2^(3 + 2) 16-bit entries for 2^4 ones per inventory
+    /// assert!(matches!(
+    ///     SelectAdaptConst::<_, _, 4, 3>::new_checked(bits),
+    ///     Err(ParamsMismatch::SubinventoryTooLarge { .. })
+    /// ));
+    /// ```
+    pub fn new_checked(bits: B) -> Result<Self, ParamsMismatch> {
+        Self::check_params(bits.len(), bits.count_ones())?;
+        Ok(Self::new(bits))
+    }
+
     /// Creates a new selection structure over a [`SelectHinted`] with a specified
     /// distance between indexed ones.
 
     pub fn new(bits: B) -> Self {
-        let num_ones = bits.count_ones();
-        let num_bits = max(1, bits.len());
-        let inventory_size = num_ones.div_ceil(Self::ONES_PER_INVENTORY);
+        let (num_ones, positions) = Self::scan_positions(&bits);
+        // SAFETY: we computed the positions by scanning the bit vector
+        unsafe { Self::from_positions(bits, num_ones, &positions) }
+    }
 
-        let u64_per_subinventory = 1 << LOG2_U64_PER_SUBINVENTORY;
-        // A u64 for the inventory, and u64_per_inventory for the subinventory
-        let u64_per_inventory = u64_per_subinventory + 1;
+    /// Returns the number of words of the spill buffer of a structure built on
+    /// the given bit vector.
+    ///
+    /// Together with
+    /// [`expected_inventory_words`](SelectAdaptConst::expected_inventory_words),
+    /// this method provides the lengths of the slices that must be passed to
+    /// [`new_in`](SelectAdaptConst::new_in). Note that this method needs to
+    /// scan the bit vector.
+    pub fn expected_spill_words(bits: &B) -> usize {
+        let (num_ones, positions) = Self::scan_positions(bits);
+        let mut inventory = vec![0; Self::expected_inventory_words(num_ones)];
+        Self::init_inventory(bits.len(), num_ones, &positions, &mut inventory)
+    }
 
-        let inventory_words = inventory_size * u64_per_inventory + 1;
-        let mut inventory = Vec::with_capacity(inventory_words);
+    /// Creates a new selection structure given the positions of the ones of
+    /// rank multiple of 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>.
+    ///
+    /// This method makes it possible to skip the scan of the bit vector
+    /// necessary to find such positions when they are known in advance, as
+    /// it happens, for example, when building the high bits of an
+    /// [`EliasFano`](crate::dict::EliasFano) structure.
+    ///
+    /// # Safety
+    ///
+    /// `num_ones` must be the number of ones of `bits`, and `positions` must
+    /// contain exactly the positions of the ones of `bits` of rank 0,
+    /// 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>,
+    /// 2 · 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>, and so on.
+    pub unsafe fn from_positions(bits: B, num_ones: usize, positions: &[usize]) -> Self {
+        let mut inventory: Box<[usize]> = vec![0; Self::expected_inventory_words(num_ones)].into();
+        let spill_size = Self::init_inventory(bits.len(), num_ones, positions, &mut inventory);
+        let mut spill: Box<[usize]> = vec![0; spill_size].into();
+        Self::fill_inventory(&bits, num_ones, &mut inventory, &mut spill);
+
+        Self {
+            bits,
+            inventory,
+            spill,
+        }
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + BitCount,
+        I,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns the number of ones of the bit vector and the positions of the
+    /// ones of rank multiple of 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>.
+    fn scan_positions(bits: &B) -> (usize, Vec<usize>) {
+        let num_ones = bits.count_ones();
+        let mut positions = Vec::with_capacity(num_ones.div_ceil(Self::ONES_PER_INVENTORY));
 
         let mut past_ones = 0;
         let mut next_quantum = 0;
-        let mut spilled = 0;
 
-        // First phase: we build an inventory for each one out of ones_per_inventory.
-        for (i, word) in bits.as_ref().iter().copied().enumerate() {
+        // First phase: we find the position of each one out of ones_per_inventory.
+        for (i, word) in bits.as_ref()[..bits.num_words()]
+            .iter()
+            .copied()
+            .enumerate()
+        {
             let ones_in_word = word.count_ones() as usize;
 
             while past_ones + ones_in_word > next_quantum {
                 let in_word_index = word.select_in_word(next_quantum - past_ones);
-                let index = (i * usize::BITS as usize) + in_word_index;
-
-                // write the position of the one in the inventory
-                inventory.push(index);
-                // make space for the subinventory
-                inventory.resize(inventory.len() + u64_per_subinventory, 0);
-
+                positions.push((i * usize::BITS as usize) + in_word_index);
                 next_quantum += Self::ONES_PER_INVENTORY;
             }
             past_ones += ones_in_word;
         }
 
         assert_eq!(past_ones, num_ones);
+        (num_ones, positions)
+    }
+
+    /// Writes the positions of the indexed ones in `inventory`, clearing the
+    /// subinventories, and returns the size of the spill buffer.
+    fn init_inventory(
+        len: usize,
+        num_ones: usize,
+        positions: &[usize],
+        inventory: &mut [usize],
+    ) -> usize {
+        let num_bits = max(1, len);
+        let inventory_size = num_ones.div_ceil(Self::ONES_PER_INVENTORY);
+        debug_assert_eq!(positions.len(), inventory_size);
+
+        let u64_per_subinventory = 1 << LOG2_U64_PER_SUBINVENTORY;
+        // A u64 for the inventory, and u64_per_inventory for the subinventory
+        let u64_per_inventory = u64_per_subinventory + 1;
+
+        assert_eq!(inventory.len(), Self::expected_inventory_words(num_ones));
+
+        for (chunk, &index) in inventory.chunks_mut(u64_per_inventory).zip(positions) {
+            // write the position of the one in the inventory
+            chunk[0] = index;
+            // make space for the subinventory
+            chunk[1..].fill(0);
+        }
+
         // in the last inventory write the number of bits
-        inventory.push(num_bits);
-        assert_eq!(inventory.len(), inventory_words);
+        inventory[inventory_size * u64_per_inventory] = num_bits;
+
+        let mut spilled = 0;
 
         // We estimate the subinventory and exact spill size
         for (i, inv) in inventory[..inventory_size * u64_per_inventory]
@@ -286,7 +590,7 @@ impl<
         {
             let start = inv;
             let span = inventory[i * u64_per_inventory + u64_per_inventory] - start;
-            past_ones = i * Self::ONES_PER_INVENTORY;
+            let past_ones = i * Self::ONES_PER_INVENTORY;
             let ones = min(num_ones - past_ones, Self::ONES_PER_INVENTORY);
 
             debug_assert!(start + span == num_bits || ones == Self::ONES_PER_INVENTORY);
@@ -313,12 +617,19 @@ impl<
             }
         }
 
-        let spill_size = spilled;
+        spilled
+    }
 
-        let mut inventory: Box<[usize]> = inventory.into();
-        let mut spill: Box<[usize]> = vec![0; spill_size].into();
+    /// Fills the subinventories and the spill buffer, given an inventory
+    /// initialized by [`init_inventory`](SelectAdaptConst::init_inventory).
+    fn fill_inventory(bits: &B, num_ones: usize, inventory: &mut [usize], spill: &mut [usize]) {
+        let inventory_size = num_ones.div_ceil(Self::ONES_PER_INVENTORY);
+        let u64_per_subinventory = 1 << LOG2_U64_PER_SUBINVENTORY;
+        let u64_per_inventory = u64_per_subinventory + 1;
+        let spill_size = spill.len();
+        spill.fill(0);
 
-        spilled = 0;
+        let mut spilled = 0;
         let locally_stored_u32s = 2 * (u64_per_subinventory - 1);
 
         // Second phase: we fill the subinventories and the spill.
@@ -399,9 +710,10 @@ impl<
 
                     match span_type {
                         SpanType::U16 => {
-                            let subinventory: &mut [u16] = unsafe {
-                                inventory[start_inv_idx + 1..end_inv_idx].align_to_mut().1
+                            let (pre, subinventory, post) = unsafe {
+                                inventory[start_inv_idx + 1..end_inv_idx].align_to_mut::<u16>()
                             };
+                            debug_assert!(pre.is_empty() && post.is_empty());
 
                             subinventory[subinventory_idx] = sub_offset as u16;
                             subinventory_idx += 1;
@@ -415,15 +727,17 @@ impl<
                         }
                         SpanType::U32 => {
                             if subinventory_idx < locally_stored_u32s {
-                                let subinventory: &mut [u32] = unsafe {
-                                    inventory[start_inv_idx + 2..end_inv_idx].align_to_mut().1
+                                let (pre, subinventory, post) = unsafe {
+                                    inventory[start_inv_idx + 2..end_inv_idx].align_to_mut::<u32>()
                                 };
+                                debug_assert!(pre.is_empty() && post.is_empty());
 
                                 debug_assert_eq!(subinventory[subinventory_idx], 0);
                                 subinventory[subinventory_idx] = sub_offset as u32;
                             } else {
-                                let u32_spill: &mut [u32] =
-                                    unsafe { spill[spilled..].align_to_mut().1 };
+                                let (pre, u32_spill, post) =
+                                    unsafe { spill[spilled..].align_to_mut::<u32>() };
+                                debug_assert!(pre.is_empty() && post.is_empty());
                                 debug_assert_eq!(
                                     u32_spill[subinventory_idx - locally_stored_u32s],
                                     0
@@ -488,6 +802,47 @@ impl<
         }
 
         assert_eq!(spilled, spill_size);
+    }
+}
+
+impl<
+        'a,
+        B: AsRef<[usize]> + BitCount,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, &'a mut [usize], LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Creates a new selection structure, storing the inventory and the spill
+    /// buffer in slices provided by the caller.
+    ///
+    /// `inventory` must have length
+    /// [`expected_inventory_words(bits.count_ones())`](SelectAdaptConst::expected_inventory_words),
+    /// and `spill` must have length
+    /// [`expected_spill_words(&bits)`](SelectAdaptConst::expected_spill_words);
+    /// their previous content is overwritten. In this way, the structure can
+    /// be written, for example, in a preallocated region of a memory-mapped
+    /// file, rather than in newly allocated memory.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the slices have not the expected length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::prelude::*;
+    /// let bits: AddNumBits<_> = bit_vec![1, 0, 1, 1, 0, 1, 0, 1].into();
+    /// let mut inventory =
+    ///     vec![0; SelectAdaptConst::<AddNumBits<BitVec>>::expected_inventory_words(bits.count_ones())];
+    /// let mut spill = vec![0; SelectAdaptConst::<_, _>::expected_spill_words(&bits)];
+    /// let select = SelectAdaptConst::<_, _>::new_in(&bits, &mut inventory, &mut spill);
+    /// assert_eq!(select.select(3), Some(5));
+    /// ```
+    pub fn new_in(bits: B, inventory: &'a mut [usize], spill: &'a mut [usize]) -> Self {
+        let (num_ones, positions) = Self::scan_positions(&bits);
+        let spill_size = Self::init_inventory(bits.len(), num_ones, &positions, inventory);
+        assert_eq!(spill.len(), spill_size);
+        Self::fill_inventory(&bits, num_ones, inventory, spill);
 
         Self {
             bits,
@@ -588,6 +943,149 @@ impl<
 {
 }
 
+impl<
+        B: AsRef<[usize]> + NumBits + SelectHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns an iterator over the positions of the ones of rank
+    /// `start_rank`, `start_rank` + 1, and so on.
+    ///
+    /// The position of the first one is computed using
+    /// [`select_unchecked`](SelectUnchecked::select_unchecked), but the
+    /// following positions are found by scanning sequentially the underlying
+    /// bit vector, reading each word at most once. Thus, retrieving the
+    /// positions of consecutive ones is much faster than calling
+    /// [`select`](Select::select) repeatedly.
+    ///
+    /// If `start_rank` is not smaller than the number of ones the iterator is
+    /// empty.
+    pub fn select_iter(&self, start_rank: usize) -> OnesIterator<'_, B> {
+        let len = self.bits.len();
+        let from = if start_rank < self.bits.num_ones() {
+            // SAFETY: start_rank is smaller than the number of ones
+            unsafe { self.select_unchecked(start_rank) }
+        } else {
+            len
+        };
+        OnesIterator::new_from(&self.bits, len, from)
+    }
+
+    /// Returns the position of the one of rank `rank` and the distance from
+    /// it to the following one (or to the length of the bit vector, if it is
+    /// the last one), or `None` if `rank` is not smaller than the number of
+    /// ones.
+    ///
+    /// The following one is found by scanning the underlying bit vector, so
+    /// this method is faster than two calls to [`select`](Select::select)
+    /// when ones are not too sparse.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sux::bit_vec;
+    /// # use sux::traits::AddNumBits;
+    /// # use sux::rank_sel::SelectAdaptConst;
+    /// let bits: AddNumBits<_> = bit_vec![0, 1, 0, 0, 1, 1, 0, 0].into();
+    /// let select = SelectAdaptConst::<_, _>::new(bits);
+    /// assert_eq!(select.select_with_gap(0), Some((1, 3)));
+    /// assert_eq!(select.select_with_gap(1), Some((4, 1)));
+    /// assert_eq!(select.select_with_gap(2), Some((5, 3)));
+    /// assert_eq!(select.select_with_gap(3), None);
+    /// ```
+    pub fn select_with_gap(&self, rank: usize) -> Option<(usize, usize)> {
+        let mut ones = self.select_iter(rank);
+        let pos = ones.next()?;
+        let next = ones.next().unwrap_or(self.bits.len());
+        Some((pos, next - pos))
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + NumBits + RankUnchecked + SelectZeroHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns the position of the zero of given rank, using the inventory
+    /// of ones and the ranking structure of the backend to bound the search.
+    ///
+    /// Selection of zeros is usually [forwarded](SelectZeroUnchecked) to the
+    /// backend. This method, instead, is available only when the backend
+    /// provides [ranking](RankUnchecked) (e.g., it is a [`Rank9`](super::Rank9)),
+    /// and it works as follows: since the number of ones before each position
+    /// in the inventory is known, a binary search on the inventory finds the
+    /// interval between two consecutive indexed ones containing the zero; then,
+    /// a binary search on the word boundaries of the interval using ranking
+    /// finds the word containing the zero. The cost is thus logarithmic, and it
+    /// does not depend on the distance between zeros.
+    ///
+    /// If you need to select zeros often, a
+    /// [`SelectZeroAdaptConst`](super::SelectZeroAdaptConst) will be faster.
+    ///
+    /// # Safety
+    ///
+    /// `rank` must be smaller than the number of zeros.
+    pub unsafe fn select_zero_by_inventory_unchecked(&self, rank: usize) -> usize {
+        let inventory = self.inventory.as_ref();
+        let u64_per_inventory = (1 << LOG2_U64_PER_SUBINVENTORY) + 1;
+        let inventory_size = inventory.len() / u64_per_inventory;
+        // The position of the one of rank i * ONES_PER_INVENTORY, or the length
+        // of the bit vector for i equal to the inventory size
+        let pos = |i: usize| inventory.get_unchecked(i * u64_per_inventory).get();
+
+        // Find the last indexed one preceded by at most rank zeros
+        let (mut lo, mut hi) = (0, inventory_size);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if pos(mid) - mid * Self::ONES_PER_INVENTORY <= rank {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        // The zero is between start (excluded if it is a one) and end
+        let start = if lo == 0 { 0 } else { pos(lo - 1) };
+        let end = pos(lo);
+
+        // Find the last word starting in [start, end) preceded by at most
+        // rank zeros; the word containing start qualifies.
+        let (mut lo, mut hi) = (
+            start / usize::BITS as usize,
+            (end - 1) / usize::BITS as usize + 1,
+        );
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let word_pos = mid * usize::BITS as usize;
+            if word_pos - self.bits.rank_unchecked(word_pos) <= rank {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let hint_pos = lo * usize::BITS as usize;
+        let hint_rank = hint_pos - self.bits.rank_unchecked(hint_pos);
+        self.bits.select_zero_hinted(rank, hint_pos, hint_rank)
+    }
+
+    /// Returns the position of the zero of given rank, or `None` if there is
+    /// no such zero, using the inventory of ones and the ranking structure of
+    /// the backend.
+    ///
+    /// See [`select_zero_by_inventory_unchecked`](SelectAdaptConst::select_zero_by_inventory_unchecked).
+    pub fn select_zero_by_inventory(&self, rank: usize) -> Option<usize> {
+        if rank >= self.bits.len() - self.bits.num_ones() {
+            None
+        } else {
+            // SAFETY: rank is smaller than the number of zeros
+            Some(unsafe { self.select_zero_by_inventory_unchecked(rank) })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
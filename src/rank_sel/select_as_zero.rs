@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::prelude::*;
+use ambassador::Delegate;
+use epserde::Epserde;
+use mem_dbg::{MemDbg, MemSize};
+use std::ops::Index;
+
+use crate::traits::rank_sel::ambassador_impl_BitLength;
+
+/// A thin wrapper presenting a structure built over the complement of a bit
+/// vector as a structure over the bit vector itself.
+///
+/// The wrapped structure is assumed to be built over the bitwise complement
+/// of the bit vector of interest. Then, its [selection on
+/// ones](crate::traits::Select) answers [selection on
+/// zeros](crate::traits::SelectZero) for the original bit vector, and vice
+/// versa; analogously, ranking, counting and indexing are complemented. In
+/// this way, a single inventory can answer the opposite query, and there is
+/// no need to build both an inventory for ones and an inventory for zeros
+/// when only one kind of selection is needed.
+///
+/// Note that the complemented bit vector must have no ones beyond its
+/// length, as every [`BitVec`] operation guarantees.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::bit_vec;
+/// use sux::rank_sel::{SelectAdaptConst, SelectAsZero};
+/// use sux::traits::{AddNumBits, Select, SelectZero};
+///
+/// let mut bits = bit_vec![0, 1, 0, 0, 1, 0, 1, 0];
+/// bits.flip();
+/// let bits: AddNumBits<_> = bits.into();
+/// let sel = SelectAsZero::new(SelectAdaptConst::<_, _>::new(bits));
+///
+/// assert_eq!(sel.select_zero(0), Some(0));
+/// assert_eq!(sel.select_zero(1), Some(2));
+/// assert_eq!(sel.select_zero(4), Some(7));
+/// assert_eq!(sel.select_zero(5), None);
+/// assert!(sel[1]);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
+#[delegate(crate::traits::rank_sel::BitLength, target = "inner")]
+pub struct SelectAsZero<S> {
+    inner: S,
+}
+
+impl<S> SelectAsZero<S> {
+    /// Wraps a structure built over the complement of a bit vector.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped structure.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: NumBits> NumBits for SelectAsZero<S> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        self.inner.num_zeros()
+    }
+}
+
+impl<S: BitCount> BitCount for SelectAsZero<S> {
+    #[inline(always)]
+    fn count_ones(&self) -> usize {
+        self.inner.count_zeros()
+    }
+}
+
+impl<S: Index<usize, Output = bool>> Index<usize> for SelectAsZero<S> {
+    type Output = bool;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        if self.inner[index] {
+            &false
+        } else {
+            &true
+        }
+    }
+}
+
+impl<S: RankUnchecked> RankUnchecked for SelectAsZero<S> {
+    #[inline(always)]
+    unsafe fn rank_unchecked(&self, pos: usize) -> usize {
+        pos - self.inner.rank_unchecked(pos)
+    }
+}
+
+impl<S: Rank> Rank for SelectAsZero<S> {}
+
+impl<S: Rank> RankZero for SelectAsZero<S> {}
+
+impl<S: SelectZeroUnchecked> SelectUnchecked for SelectAsZero<S> {
+    #[inline(always)]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.inner.select_zero_unchecked(rank)
+    }
+}
+
+impl<S: SelectZeroUnchecked + NumBits> Select for SelectAsZero<S> {}
+
+impl<S: SelectUnchecked> SelectZeroUnchecked for SelectAsZero<S> {
+    #[inline(always)]
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        self.inner.select_unchecked(rank)
+    }
+}
+
+impl<S: SelectUnchecked + NumBits> SelectZero for SelectAsZero<S> {}
@@ -0,0 +1,297 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::{SelectAdaptConst, SelectZeroAdaptConst};
+use ambassador::Delegate;
+use common_traits::SelectInWord;
+use epserde::Epserde;
+use mem_dbg::{MemDbg, MemSize};
+
+use crate::{
+    prelude::{BitCount, BitLength, BitVec, SelectHinted, SelectZeroHinted},
+    traits::{IntoBitVec, NumBits, Select, SelectUnchecked, SelectZero, SelectZeroUnchecked},
+};
+
+use crate::ambassador_impl_AsRef;
+use crate::ambassador_impl_Index;
+use crate::traits::rank_sel::ambassador_impl_BitCount;
+use crate::traits::rank_sel::ambassador_impl_BitLength;
+use crate::traits::rank_sel::ambassador_impl_NumBits;
+use crate::traits::rank_sel::ambassador_impl_Rank;
+use crate::traits::rank_sel::ambassador_impl_RankHinted;
+use crate::traits::rank_sel::ambassador_impl_RankUnchecked;
+use crate::traits::rank_sel::ambassador_impl_RankZero;
+use crate::traits::rank_sel::ambassador_impl_SelectHinted;
+use crate::traits::rank_sel::ambassador_impl_SelectZeroHinted;
+use std::ops::Index;
+
+/// A selection structure providing both [`Select`] and [`SelectZero`] using
+/// the inventories of [`SelectAdaptConst`] and [`SelectZeroAdaptConst`].
+///
+/// The same functionality can be obtained by wrapping a
+/// [`SelectAdaptConst`] into a [`SelectZeroAdaptConst`] (or vice versa), as
+/// in the case of the [high bits of the Elias–Fano
+/// representation](crate::dict::elias_fano::EfSeqDict). This structure,
+/// however, stores the bit vector and the two inventories at the same level,
+/// avoiding nested types and a double indirection on the underlying bit
+/// vector. Moreover, the positions indexed by the two inventories are found
+/// by a single scan of the bit vector. Queries are exactly as in
+/// [`SelectAdaptConst`] and [`SelectZeroAdaptConst`], with which this
+/// structure shares the code and the parameters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use sux::bit_vec;
+/// # use sux::traits::{Select, SelectZero, AddNumBits};
+/// # use sux::rank_sel::SelectDualAdaptConst;
+/// let bits: AddNumBits<_> = bit_vec![1, 0, 1, 1, 0, 1, 0, 1].into();
+/// let select = SelectDualAdaptConst::<_, _>::new(bits);
+///
+/// assert_eq!(select.select(0), Some(0));
+/// assert_eq!(select.select(1), Some(2));
+/// assert_eq!(select.select(4), Some(7));
+/// assert_eq!(select.select(5), None);
+///
+/// assert_eq!(select.select_zero(0), Some(1));
+/// assert_eq!(select.select_zero(1), Some(4));
+/// assert_eq!(select.select_zero(2), Some(6));
+/// assert_eq!(select.select_zero(3), None);
+///
+/// // Access to the underlying bit vector is forwarded
+/// assert_eq!(select[0], true);
+/// assert_eq!(select[1], false);
+/// ```
+#[derive(Epserde, Debug, Clone, MemDbg, MemSize, Delegate)]
+#[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(Index<usize>, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitCount, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
+#[delegate(crate::traits::rank_sel::NumBits, target = "bits")]
+#[delegate(crate::traits::rank_sel::Rank, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankUnchecked, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankZero, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectZeroHinted, target = "bits")]
+pub struct SelectDualAdaptConst<
+    B,
+    I = Box<[usize]>,
+    const LOG2_ONES_PER_INVENTORY: usize = 12,
+    const LOG2_U64_PER_SUBINVENTORY: usize = 3,
+> {
+    bits: B,
+    ones_inventory: I,
+    ones_spill: I,
+    zeros_inventory: I,
+    zeros_spill: I,
+}
+
+impl<B, I, const LOG2_ONES_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTORY: usize>
+    SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    pub fn into_inner(self) -> B {
+        self.bits
+    }
+}
+
+impl<
+        B,
+        T: IntoBitVec<B>,
+        I,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > IntoBitVec<B>
+    for SelectDualAdaptConst<T, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
+impl<
+        B: BitLength,
+        I,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns the number of bits in the bit vector.
+    ///
+    /// This method is equivalent to
+    /// [`BitLength::len`](crate::traits::BitLength::len), but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        BitLength::len(self)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + BitCount,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectDualAdaptConst<B, Box<[usize]>, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Creates a new selection structure for ones and zeros.
+    ///
+    /// The positions of the indexed ones and zeros are found by a single scan
+    /// of the bit vector; the two inventories are then built from such
+    /// positions, exactly as in [`SelectAdaptConst::from_positions`] and
+    /// [`SelectZeroAdaptConst::from_positions`].
+    pub fn new(bits: B) -> Self {
+        let num_ones = bits.count_ones();
+        let num_zeros = BitLength::len(&bits) - num_ones;
+        let (ones_positions, zeros_positions) = Self::scan_positions(&bits, num_ones, num_zeros);
+        // SAFETY: we computed the positions by scanning the bit vector
+        let ones = unsafe {
+            SelectAdaptConst::<
+                &B,
+                Box<[usize]>,
+                LOG2_ONES_PER_INVENTORY,
+                LOG2_U64_PER_SUBINVENTORY,
+            >::from_positions(&bits, num_ones, &ones_positions)
+        };
+        // SAFETY: we computed the positions by scanning the bit vector
+        let zeros = unsafe {
+            SelectZeroAdaptConst::<
+                &B,
+                Box<[usize]>,
+                LOG2_ONES_PER_INVENTORY,
+                LOG2_U64_PER_SUBINVENTORY,
+            >::from_positions(&bits, num_zeros, &zeros_positions)
+        };
+        let (ones_inventory, ones_spill) = (ones.inventory, ones.spill);
+        let (zeros_inventory, zeros_spill) = (zeros.inventory, zeros.spill);
+        Self {
+            bits,
+            ones_inventory,
+            ones_spill,
+            zeros_inventory,
+            zeros_spill,
+        }
+    }
+
+    /// Returns the positions of the ones and of the zeros of rank multiple of
+    /// 2<sup>`LOG2_ONES_PER_INVENTORY`</sup>, given the number of ones and
+    /// of zeros of the bit vector.
+    fn scan_positions(bits: &B, num_ones: usize, num_zeros: usize) -> (Vec<usize>, Vec<usize>) {
+        let quantum = 1 << LOG2_ONES_PER_INVENTORY;
+        let mut ones_positions = Vec::with_capacity(num_ones.div_ceil(quantum));
+        let mut zeros_positions = Vec::with_capacity(num_zeros.div_ceil(quantum));
+
+        let (mut past_ones, mut next_one) = (0, 0);
+        let (mut past_zeros, mut next_zero) = (0, 0);
+
+        for (i, word) in bits.as_ref()[..bits.num_words()]
+            .iter()
+            .copied()
+            .enumerate()
+        {
+            let ones_in_word = word.count_ones() as usize;
+            while past_ones + ones_in_word > next_one {
+                let in_word_index = word.select_in_word(next_one - past_ones);
+                ones_positions.push((i * usize::BITS as usize) + in_word_index);
+                next_one += quantum;
+            }
+            past_ones += ones_in_word;
+
+            // The padding of the last word must not be counted
+            let zeros_in_word = (usize::BITS as usize - ones_in_word).min(num_zeros - past_zeros);
+            while past_zeros + zeros_in_word > next_zero {
+                let in_word_index = (!word).select_in_word(next_zero - past_zeros);
+                zeros_positions.push((i * usize::BITS as usize) + in_word_index);
+                next_zero += quantum;
+            }
+            past_zeros += zeros_in_word;
+        }
+
+        assert_eq!(past_ones, num_ones);
+        assert_eq!(past_zeros, num_zeros);
+        (ones_positions, zeros_positions)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + BitLength,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns a view of this structure as a [`SelectAdaptConst`].
+    #[inline(always)]
+    fn ones(
+        &self,
+    ) -> SelectAdaptConst<&B, &[usize], LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY> {
+        SelectAdaptConst {
+            bits: &self.bits,
+            inventory: self.ones_inventory.as_ref(),
+            spill: self.ones_spill.as_ref(),
+        }
+    }
+
+    /// Returns a view of this structure as a [`SelectZeroAdaptConst`].
+    #[inline(always)]
+    fn zeros(
+        &self,
+    ) -> SelectZeroAdaptConst<&B, &[usize], LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+    {
+        SelectZeroAdaptConst {
+            bits: &self.bits,
+            inventory: self.zeros_inventory.as_ref(),
+            spill: self.zeros_spill.as_ref(),
+        }
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + BitLength + SelectHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectUnchecked
+    for SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    #[inline(always)]
+    unsafe fn select_unchecked(&self, rank: usize) -> usize {
+        self.ones().select_unchecked(rank)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + NumBits + SelectHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > Select for SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+}
+
+impl<
+        B: AsRef<[usize]> + BitLength + SelectZeroHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectZeroUnchecked
+    for SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    #[inline(always)]
+    unsafe fn select_zero_unchecked(&self, rank: usize) -> usize {
+        self.zeros().select_zero_unchecked(rank)
+    }
+}
+
+impl<
+        B: AsRef<[usize]> + NumBits + SelectZeroHinted,
+        I: AsRef<[usize]>,
+        const LOG2_ONES_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectZero
+    for SelectDualAdaptConst<B, I, LOG2_ONES_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+}
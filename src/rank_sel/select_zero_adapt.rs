@@ -14,8 +14,8 @@ use mem_dbg::{MemDbg, MemSize};
 use std::cmp::{max, min};
 
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength, SelectZeroHinted},
-    traits::{NumBits, SelectZero, SelectZeroUnchecked},
+    prelude::{BitCount, BitFieldSlice, BitLength, BitVec, SelectZeroHinted},
+    traits::{IntoBitVec, NumBits, SelectZero, SelectZeroUnchecked},
 };
 
 use crate::ambassador_impl_AsRef;
@@ -191,6 +191,13 @@ impl<B, I> SelectZeroAdapt<B, I> {
     pub const DEFAULT_TARGET_INVENTORY_SPAN: usize = 8192;
 }
 
+impl<B, T: IntoBitVec<B>, I> IntoBitVec<B> for SelectZeroAdapt<T, I> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<B: BitLength, C> SelectZeroAdapt<B, C> {
     /// Returns the number of bits in the bit vector.
     ///
@@ -458,7 +465,7 @@ impl<B: AsRef<[usize]> + BitCount> SelectZeroAdapt<B, Box<[usize]>> {
             let mut word = (!bits.as_ref()[word_idx] >> bit_idx) << bit_idx;
 
             'outer: loop {
-                let ones_in_word = word.count_ones() as usize;
+                let ones_in_word = (word.count_ones() as usize).min(num_ones - past_ones);
 
                 // If the quantum is in this word, write it in the subinventory.
                 // Note that this can happen multiple times in the same word if
@@ -8,8 +8,8 @@
 
 use super::{Inventory, SpanType};
 use crate::{
-    prelude::{BitCount, BitFieldSlice, BitLength},
-    traits::{NumBits, SelectZero, SelectZeroHinted, SelectZeroUnchecked},
+    prelude::{BitCount, BitFieldSlice, BitLength, BitVec},
+    traits::{IntoBitVec, NumBits, SelectZero, SelectZeroHinted, SelectZeroUnchecked},
 };
 use ambassador::Delegate;
 use common_traits::SelectInWord;
@@ -145,9 +145,9 @@ pub struct SelectZeroAdaptConst<
     const LOG2_ZEROS_PER_INVENTORY: usize = 12,
     const LOG2_U64_PER_SUBINVENTORY: usize = 3,
 > {
-    bits: B,
-    inventory: I,
-    spill: I,
+    pub(super) bits: B,
+    pub(super) inventory: I,
+    pub(super) spill: I,
 }
 
 impl<B, I, const LOG2_ZEROS_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTORY: usize>
@@ -194,6 +194,21 @@ impl<B, I, const LOG2_ZEROS_PER_INVENTORY: usize, const LOG2_U64_PER_SUBINVENTOR
     pub const DEFAULT_TARGET_INVENTORY_SPAN: usize = 8192;
 }
 
+impl<
+        B,
+        T: IntoBitVec<B>,
+        I,
+        const LOG2_ZEROS_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > IntoBitVec<B>
+    for SelectZeroAdaptConst<T, I, LOG2_ZEROS_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<
         B: BitLength,
         C,
@@ -222,7 +237,28 @@ impl<
     /// distance between indexed zeros.
 
     pub fn new(bits: B) -> Self {
-        let num_ones = bits.count_zeros();
+        let num_zeros = bits.count_zeros();
+        let positions = Self::scan_positions(&bits, num_zeros);
+        // SAFETY: we computed the positions by scanning the bit vector
+        unsafe { Self::from_positions(bits, num_zeros, &positions) }
+    }
+
+    /// Creates a new selection structure given the positions of the zeros of
+    /// rank multiple of 2<sup>`LOG2_ZEROS_PER_INVENTORY`</sup>.
+    ///
+    /// This method makes it possible to skip the scan of the bit vector
+    /// necessary to find such positions when they are known in advance, as
+    /// it happens, for example, when building a
+    /// [`SelectDualAdaptConst`](super::SelectDualAdaptConst).
+    ///
+    /// # Safety
+    ///
+    /// `num_zeros` must be the number of zeros of `bits`, and `positions`
+    /// must contain exactly the positions of the zeros of `bits` of rank 0,
+    /// 2<sup>`LOG2_ZEROS_PER_INVENTORY`</sup>,
+    /// 2 · 2<sup>`LOG2_ZEROS_PER_INVENTORY`</sup>, and so on.
+    pub unsafe fn from_positions(bits: B, num_zeros: usize, positions: &[usize]) -> Self {
+        let num_ones = num_zeros;
         let num_bits = max(1, bits.len());
         let inventory_size = num_ones.div_ceil(Self::ONES_PER_INVENTORY);
 
@@ -238,29 +274,17 @@ impl<
         let inventory_words = inventory_size * u64_per_inventory + 1;
         let mut inventory = Vec::with_capacity(inventory_words);
 
-        let mut past_ones = 0;
-        let mut next_quantum = 0;
         let mut spilled = 0;
 
         // First phase: we build an inventory for each one out of ones_per_inventory.
-        for (i, word) in bits.as_ref().iter().copied().map(|b| !b).enumerate() {
-            let ones_in_word = (word.count_ones() as usize).min(num_ones - past_ones);
-
-            while past_ones + ones_in_word > next_quantum {
-                let in_word_index = word.select_in_word(next_quantum - past_ones);
-                let index = (i * usize::BITS as usize) + in_word_index;
-
-                // write the position of the one in the inventory
-                inventory.push(index);
-                // make space for the subinventory
-                inventory.resize(inventory.len() + u64_per_subinventory, 0);
-
-                next_quantum += Self::ONES_PER_INVENTORY;
-            }
-            past_ones += ones_in_word;
+        assert_eq!(positions.len(), inventory_size);
+        for &index in positions {
+            // write the position of the one in the inventory
+            inventory.push(index);
+            // make space for the subinventory
+            inventory.resize(inventory.len() + u64_per_subinventory, 0);
         }
 
-        assert_eq!(past_ones, num_ones);
         // in the last inventory write the number of bits
         inventory.push(num_bits);
         assert_eq!(inventory.len(), inventory_words);
@@ -274,7 +298,7 @@ impl<
         {
             let start = inv;
             let span = inventory[i * u64_per_inventory + u64_per_inventory] - start;
-            past_ones = i * Self::ONES_PER_INVENTORY;
+            let past_ones = i * Self::ONES_PER_INVENTORY;
             let ones = min(num_ones - past_ones, Self::ONES_PER_INVENTORY);
 
             debug_assert!(start + span == num_bits || ones == Self::ONES_PER_INVENTORY);
@@ -485,6 +509,39 @@ impl<
     }
 }
 
+impl<
+        B: AsRef<[usize]> + BitCount,
+        I,
+        const LOG2_ZEROS_PER_INVENTORY: usize,
+        const LOG2_U64_PER_SUBINVENTORY: usize,
+    > SelectZeroAdaptConst<B, I, LOG2_ZEROS_PER_INVENTORY, LOG2_U64_PER_SUBINVENTORY>
+{
+    /// Returns the positions of the zeros of rank multiple of
+    /// 2<sup>`LOG2_ZEROS_PER_INVENTORY`</sup>, given the number of zeros of
+    /// the bit vector.
+    fn scan_positions(bits: &B, num_zeros: usize) -> Vec<usize> {
+        let mut positions = Vec::with_capacity(num_zeros.div_ceil(Self::ONES_PER_INVENTORY));
+
+        let mut past_zeros = 0;
+        let mut next_quantum = 0;
+
+        for (i, word) in bits.as_ref().iter().copied().map(|b| !b).enumerate() {
+            // The padding of the last word must not be counted
+            let zeros_in_word = (word.count_ones() as usize).min(num_zeros - past_zeros);
+
+            while past_zeros + zeros_in_word > next_quantum {
+                let in_word_index = word.select_in_word(next_quantum - past_zeros);
+                positions.push((i * usize::BITS as usize) + in_word_index);
+                next_quantum += Self::ONES_PER_INVENTORY;
+            }
+            past_zeros += zeros_in_word;
+        }
+
+        assert_eq!(past_zeros, num_zeros);
+        positions
+    }
+}
+
 impl<
         B: AsRef<[usize]> + BitLength + SelectZeroHinted,
         I: AsRef<[usize]>,
@@ -112,6 +112,15 @@ impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, C, I, O>
     }
 }
 
+impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, B, C: IntoBitVec<B>, I, O> IntoBitVec<B>
+    for SelectZeroSmall<NUM_U32S, COUNTER_WIDTH, C, I, O>
+{
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.small_counters.into_bit_vec()
+    }
+}
+
 impl<const NUM_U32S: usize, const COUNTER_WIDTH: usize, C: BitLength, I, O>
     SelectZeroSmall<NUM_U32S, COUNTER_WIDTH, C, I, O>
 {
@@ -104,6 +104,39 @@ pub trait IndexedSeq: Types {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns a vector containing the values at positions 0, `step`,
+    /// 2 · `step`, … that are smaller than [`len`](`IndexedSeq::len`).
+    ///
+    /// This method is useful to build coarse indices. The default
+    /// implementation calls [`get_unchecked`](`IndexedSeq::get_unchecked`)
+    /// on each sampled position, but implementations with a faster sequential
+    /// access should override it.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `step` is zero.
+    fn sample(&self, step: usize) -> Vec<Self::Output> {
+        assert!(step != 0, "The sampling step must be positive");
+        (0..self.len())
+            .step_by(step)
+            .map(|index| unsafe { self.get_unchecked(index) })
+            .collect()
+    }
+
+    /// Returns an iterator on the pairs given by the indices and the values
+    /// of the sequence, in increasing index order.
+    ///
+    /// The result is the same as that of `(0..self.len()).map(|i| (i,
+    /// self.get(i)))`, and this is exactly what the default implementation
+    /// does, but implementations with a faster sequential access should
+    /// override it.
+    fn enumerate_values(&self) -> impl Iterator<Item = (usize, Self::Output)> + '_
+    where
+        Self: Sized,
+    {
+        (0..self.len()).map(move |index| (index, unsafe { self.get_unchecked(index) }))
+    }
 }
 
 /// Access by value to the dictionary.
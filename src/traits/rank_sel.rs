@@ -14,11 +14,13 @@
 
 use crate::ambassador_impl_AsRef;
 use crate::ambassador_impl_Index;
+use crate::bits::BitVec;
 use ambassador::{delegatable_trait, Delegate};
 use epserde::Epserde;
 use impl_tools::autoimpl;
 use mem_dbg::{MemDbg, MemSize};
 use std::ops::Index;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// A trait expressing a length in bits.
 ///
@@ -29,8 +31,44 @@ use std::ops::Index;
 pub trait BitLength {
     /// Returns a length in bits.
     fn len(&self) -> usize;
+
+    /// Returns the number of words necessary to store
+    /// [`len`](BitLength::len) bits.
+    #[inline(always)]
+    fn num_words(&self) -> usize {
+        self.len().div_ceil(usize::BITS as usize)
+    }
+
+    /// Returns the number of unused bits in the last of the
+    /// [`num_words`](BitLength::num_words) words.
+    ///
+    /// The result is always smaller than the number of bits in a word.
+    #[inline(always)]
+    fn padding_bits(&self) -> usize {
+        self.num_words() * usize::BITS as usize - self.len()
+    }
 }
 
+/// Non-panicking access to the bits of a bit vector.
+///
+/// This trait is automatically implemented for every type implementing
+/// [`BitLength`] and `Index<usize, Output = bool>`, which includes bit
+/// vectors and all structures forwarding indexing to an underlying bit vector.
+pub trait BitAccess: BitLength + Index<usize, Output = bool> {
+    /// Returns the bit of given index, or `None` if `index` is not
+    /// smaller than [the length](BitLength::len).
+    #[inline(always)]
+    fn try_get_bit(&self, index: usize) -> Option<bool> {
+        if index < self.len() {
+            Some(self[index])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: BitLength + Index<usize, Output = bool> + ?Sized> BitAccess for T {}
+
 /// Potentially expensive bit-counting methods.
 ///
 /// The methods in this trait compute the number of ones or zeros
@@ -186,6 +224,58 @@ pub trait Select: SelectUnchecked + NumBits {
             Some(unsafe { self.select_unchecked(rank) })
         }
     }
+
+    /// Returns the position of the one of given rank, or the length of the
+    /// underlying bit vector if no such bit exists.
+    ///
+    /// This method is equivalent to `select(rank).unwrap_or(len())`, and it
+    /// is convenient when iterating over ranks, as the length acts as a
+    /// sentinel.
+    fn select_or_len(&self, rank: usize) -> usize {
+        if rank >= self.num_ones() {
+            self.len()
+        } else {
+            unsafe { self.select_unchecked(rank) }
+        }
+    }
+}
+
+/// Returns the number of ones preceding the specified position using only
+/// selection.
+///
+/// The result is the same as that of [`Rank::rank`], but it is computed by a
+/// binary search on the positions returned by
+/// [`select_unchecked`](SelectUnchecked::select_unchecked), so it requires a
+/// logarithmic number of selections. This function makes it possible to rank
+/// on structures with fast selection but no ranking support (e.g., a
+/// [`SelectAdaptConst`](crate::rank_sel::SelectAdaptConst) on a plain
+/// [`BitVec`]) without building a separate ranking structure.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+///
+/// let bits: AddNumBits<_> = bit_vec![0, 1, 0, 1, 1, 0, 1, 0].into();
+/// let select = SelectAdaptConst::<_, _>::new(bits);
+/// assert_eq!(rank_via_select(&select, 0), 0);
+/// assert_eq!(rank_via_select(&select, 4), 2);
+/// assert_eq!(rank_via_select(&select, 100), 4);
+/// ```
+pub fn rank_via_select<S: Select + ?Sized>(s: &S, pos: usize) -> usize {
+    // Invariant: the ones of rank smaller than lo precede pos, and the ones of
+    // rank at least hi do not
+    let mut lo = 0;
+    let mut hi = s.num_ones();
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if unsafe { s.select_unchecked(mid) } < pos {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
 }
 
 /// Selection zeros over a bit vector without bound checks.
@@ -256,6 +346,36 @@ pub trait SelectZeroHinted {
     unsafe fn select_zero_hinted(&self, rank: usize, hint_pos: usize, hint_rank: usize) -> usize;
 }
 
+/// Recursive unwrapping of rank/select structures to the underlying bit vector.
+///
+/// Structures in [`rank_sel`](crate::rank_sel) can be stacked, and their
+/// `into_inner` method returns just the next level. This trait is
+/// implemented by [`BitVec`] (as the identity) and forwarded by
+/// [`AddNumBits`] and by all structures in [`rank_sel`](crate::rank_sel)
+/// that contain their backend, so that [`into_bit_vec`](IntoBitVec::into_bit_vec)
+/// peels off all levels at once.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+///
+/// let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0];
+/// let sel = SelectAdaptConst::<_, _>::new(Rank9::new(bits.clone()));
+/// assert_eq!(sel.into_bit_vec(), bits);
+/// ```
+pub trait IntoBitVec<B = Vec<usize>> {
+    /// Consumes this structure and returns the underlying bit vector.
+    fn into_bit_vec(self) -> BitVec<B>;
+}
+
+impl<B> IntoBitVec<B> for BitVec<B> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self
+    }
+}
+
 /// A thin wrapper implementing [`NumBits`] by caching the result of
 /// [`BitCount::count_ones`].
 ///
@@ -304,6 +424,13 @@ impl<B> AddNumBits<B> {
     }
 }
 
+impl<B, T: IntoBitVec<B>> IntoBitVec<B> for AddNumBits<T> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
 impl<B: BitLength> AddNumBits<B> {
     /// Returns the number of bits in the underlying bit vector.
     ///
@@ -313,6 +440,20 @@ impl<B: BitLength> AddNumBits<B> {
     pub fn len(&self) -> usize {
         BitLength::len(self)
     }
+
+    /// Returns the fraction of ones in the underlying bit vector, or zero if
+    /// the bit vector is empty.
+    ///
+    /// Differently from [`BitVec::density`](crate::bits::BitVec::density),
+    /// this method runs in constant time.
+    #[inline(always)]
+    pub fn density(&self) -> f64 {
+        if self.len() == 0 {
+            0.0
+        } else {
+            self.number_of_ones as f64 / self.len() as f64
+        }
+    }
 }
 
 impl<B: BitLength> NumBits for AddNumBits<B> {
@@ -329,6 +470,89 @@ impl<B: BitLength> BitCount for AddNumBits<B> {
     }
 }
 
+impl<B: BitLength> AddNumBits<B> {
+    /// Returns the cached number of ones.
+    ///
+    /// This method is equivalent to [`NumBits::num_ones`], but it makes
+    /// explicit that no computation is performed.
+    #[inline(always)]
+    pub fn cached_count(&self) -> usize {
+        self.number_of_ones
+    }
+}
+
+impl<B: BitCount> AddNumBits<B> {
+    /// Creates a new wrapper using a number of ones that is already known,
+    /// thus avoiding the scan performed by [`From::from`].
+    ///
+    /// This constructor is useful in pipelines that keep track of the number
+    /// of ones while generating the bits. Differently from
+    /// [`from_raw_parts`](AddNumBits::from_raw_parts), the number of ones is
+    /// checked in debug mode.
+    ///
+    /// # Safety
+    ///
+    /// `count` must be the number of ones in `bits`.
+    ///
+    /// # Panics
+    ///
+    /// In debug mode, this method will panic if `count` is not the number of
+    /// ones in `bits`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let bits = unsafe { AddNumBits::with_count(bit_vec![0, 1, 0, 1], 2) };
+    /// assert_eq!(bits.num_ones(), 2);
+    /// ```
+    #[inline(always)]
+    pub unsafe fn with_count(bits: B, count: usize) -> Self {
+        debug_assert_eq!(
+            count,
+            bits.count_ones(),
+            "The number of ones is not the one provided"
+        );
+        Self {
+            bits,
+            number_of_ones: count,
+        }
+    }
+
+    /// Returns the number of ones computed by scanning the underlying
+    /// structure, ignoring the cached count.
+    pub fn recount(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    /// Returns whether the [cached count](AddNumBits::cached_count) is equal
+    /// to the [actual number of ones](AddNumBits::recount).
+    ///
+    /// This method is useful to catch cases in which the underlying structure
+    /// has been modified without updating the cached count, for example
+    /// after [`from_raw_parts`](AddNumBits::from_raw_parts).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sux::prelude::*;
+    ///
+    /// let bits: AddNumBits<_> = bit_vec![0, 1, 0, 1].into();
+    /// assert!(bits.verify_count());
+    ///
+    /// let (mut bits, count) = bits.into_raw_parts();
+    /// bits.set(0, true);
+    /// let bits = unsafe { AddNumBits::from_raw_parts(bits, count) };
+    /// assert_eq!(bits.cached_count(), 2);
+    /// assert_eq!(bits.recount(), 3);
+    /// assert!(!bits.verify_count());
+    /// ```
+    pub fn verify_count(&self) -> bool {
+        self.number_of_ones == self.recount()
+    }
+}
+
 impl<B: BitCount> From<B> for AddNumBits<B> {
     fn from(bits: B) -> Self {
         let number_of_ones = bits.count_ones();
@@ -338,3 +562,146 @@ impl<B: BitCount> From<B> for AddNumBits<B> {
         }
     }
 }
+
+/// A thin wrapper implementing [`NumBits`] by computing lazily, and then
+/// caching, the result of [`BitCount::count_ones`].
+///
+/// Differently from [`AddNumBits`], which counts the ones of the wrapped
+/// structure at construction time, this structure performs the count the first
+/// time [`NumBits::num_ones`] or [`BitCount::count_ones`] is called: thus,
+/// the first call has a cost linear in the length of the bit vector, whereas
+/// subsequent calls run in constant time. This is useful, for example, when
+/// memory-mapping a bit vector that has not been stored with its number of
+/// ones, as the bit vector is not scanned at load time (and possibly never,
+/// if the number of ones is not needed).
+///
+/// If the first calls happen concurrently the count might be computed more
+/// than once, but the result is always the same.
+///
+/// This structure forwards to the wrapped structure all traits defined in [this
+/// module](crate::rank_sel) except for [`NumBits`] and [`BitCount`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::prelude::*;
+///
+/// let bits = AddLazyNumBits::new(bit_vec![0, 1, 0, 1, 1]);
+/// assert_eq!(bits.cached_count(), None);
+/// assert_eq!(bits.num_ones(), 3);
+/// assert_eq!(bits.cached_count(), Some(3));
+/// ```
+#[derive(Debug, MemDbg, MemSize, Delegate)]
+#[delegate(AsRef<[usize]>, target = "bits")]
+#[delegate(Index<usize>, target = "bits")]
+#[delegate(crate::traits::rank_sel::BitLength, target = "bits")]
+#[delegate(crate::traits::rank_sel::Rank, target = "bits", where = "B: BitCount")]
+#[delegate(crate::traits::rank_sel::RankHinted<64>, target = "bits")]
+#[delegate(crate::traits::rank_sel::RankUnchecked, target = "bits")]
+#[delegate(
+    crate::traits::rank_sel::RankZero,
+    target = "bits",
+    where = "B: BitCount"
+)]
+#[delegate(
+    crate::traits::rank_sel::Select,
+    target = "bits",
+    where = "B: BitCount"
+)]
+#[delegate(crate::traits::rank_sel::SelectHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectUnchecked, target = "bits")]
+#[delegate(
+    crate::traits::rank_sel::SelectZero,
+    target = "bits",
+    where = "B: BitCount"
+)]
+#[delegate(crate::traits::rank_sel::SelectZeroHinted, target = "bits")]
+#[delegate(crate::traits::rank_sel::SelectZeroUnchecked, target = "bits")]
+pub struct AddLazyNumBits<B> {
+    bits: B,
+    /// The number of ones, or `usize::MAX` if it has not been computed yet.
+    number_of_ones: AtomicUsize,
+}
+
+impl<B> AddLazyNumBits<B> {
+    /// Wraps a structure without counting its ones.
+    pub fn new(bits: B) -> Self {
+        Self {
+            bits,
+            number_of_ones: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    pub fn into_inner(self) -> B {
+        self.bits
+    }
+
+    /// Returns the number of ones, if it has already been computed.
+    #[inline(always)]
+    pub fn cached_count(&self) -> Option<usize> {
+        match self.number_of_ones.load(Ordering::Relaxed) {
+            usize::MAX => None,
+            number_of_ones => Some(number_of_ones),
+        }
+    }
+}
+
+impl<B: Clone> Clone for AddLazyNumBits<B> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            number_of_ones: AtomicUsize::new(self.number_of_ones.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<B, T: IntoBitVec<B>> IntoBitVec<B> for AddLazyNumBits<T> {
+    #[inline(always)]
+    fn into_bit_vec(self) -> BitVec<B> {
+        self.bits.into_bit_vec()
+    }
+}
+
+impl<B: BitLength> AddLazyNumBits<B> {
+    /// Returns the number of bits in the underlying bit vector.
+    ///
+    /// This method is equivalent to [`BitLength::len`], but it is provided to
+    /// reduce ambiguity in method resolution.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        BitLength::len(self)
+    }
+}
+
+impl<B: BitCount> NumBits for AddLazyNumBits<B> {
+    #[inline(always)]
+    fn num_ones(&self) -> usize {
+        match self.cached_count() {
+            Some(number_of_ones) => number_of_ones,
+            None => {
+                let number_of_ones = self.bits.count_ones();
+                self.number_of_ones.store(number_of_ones, Ordering::Relaxed);
+                number_of_ones
+            }
+        }
+    }
+}
+
+impl<B: BitCount> BitCount for AddLazyNumBits<B> {
+    #[inline(always)]
+    fn count_ones(&self) -> usize {
+        self.num_ones()
+    }
+}
+
+impl<B: BitCount> AddLazyNumBits<B> {
+    /// Converts the structure into an [`AddNumBits`], counting the ones if
+    /// necessary.
+    pub fn into_add_num_bits(self) -> AddNumBits<B> {
+        let number_of_ones = self.num_ones();
+        AddNumBits {
+            bits: self.bits,
+            number_of_ones,
+        }
+    }
+}
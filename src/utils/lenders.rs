@@ -23,6 +23,13 @@
 //! by the `zstd` feature) that lends lines from a zstd-compressed [`Read`], and
 //! [`GzipLineLender`], which lends lines from a gzip-compressed [`Read`].
 //!
+//! If lines must be processed in parallel, [`read_lines_chunked`] returns
+//! chunks of lines as owned strings.
+//!
+//! [`DelimLender`] and [`DelimStrLender`] generalize [`LineLender`] to records
+//! separated by an arbitrary delimiter byte (e.g., NUL), lending them as
+//! `&[u8]` or `&str`, respectively.
+//!
 //! If you have a clonable [`IntoIterator`], you can use [`FromIntoIterator`] to
 //! lend its items; rewinding is implemented by cloning the iterator. Note that
 //! [`FromIntoIterator`] implements the [`From`] trait, but at this time due to
@@ -117,6 +124,183 @@ impl<B: BufRead + Seek> RewindableIoLender<str> for LineLender<B> {
     }
 }
 
+/// Returns an iterator on chunks of `chunk` lines coming from a [`BufRead`],
+/// returned as owned strings.
+///
+/// Differently from [`LineLender`], whose lent lines cannot cross thread
+/// boundaries, the chunks returned by this iterator can be distributed to
+/// parallel workers (e.g., using rayon). Lines are terminated as in the
+/// case of [`LineLender`], and the last chunk might contain less than `chunk`
+/// lines.
+///
+/// After returning an error the iterator ends, and the lines of the chunk
+/// being filled are discarded.
+///
+/// # Panics
+///
+/// This function will panic if `chunk` is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use sux::utils::read_lines_chunked;
+///
+/// let chunks = read_lines_chunked(Cursor::new("a\nb\r\nc\n"), 2)
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(chunks, vec![vec!["a", "b"], vec!["c"]]);
+/// ```
+pub fn read_lines_chunked<B: BufRead>(
+    buf: B,
+    chunk: usize,
+) -> impl Iterator<Item = io::Result<Vec<String>>> {
+    assert!(chunk != 0, "The chunk size must be positive");
+    let mut lender = LineLender::new(buf);
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let mut lines = Vec::with_capacity(chunk);
+        while lines.len() < chunk {
+            match lender.next() {
+                Some(Ok(line)) => lines.push(line.to_owned()),
+                Some(Err(e)) => {
+                    done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+        (!lines.is_empty()).then_some(Ok(lines))
+    })
+}
+
+/// A structure lending the records separated by a given delimiter byte
+/// coming from a [`BufRead`] as `&[u8]`.
+///
+/// The delimiter is not part of the lent records. The records are read into a
+/// reusable internal buffer that grows as needed. Use [`DelimStrLender`] if
+/// you need the records as `&str`.
+///
+/// For example, using the NUL byte as delimiter makes it possible to read the
+/// output of `find -print0`. [`LineLender`] is essentially a specialization
+/// of this structure for `\n` that also removes an optional `\r` before the
+/// delimiter.
+pub struct DelimLender<B> {
+    buf: B,
+    delim: u8,
+    record: Vec<u8>,
+}
+
+impl<B> DelimLender<B> {
+    pub fn new(buf: B, delim: u8) -> Self {
+        DelimLender {
+            buf,
+            delim,
+            record: Vec::with_capacity(128),
+        }
+    }
+}
+
+impl DelimLender<BufReader<File>> {
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        delim: u8,
+    ) -> io::Result<DelimLender<BufReader<File>>> {
+        Ok(DelimLender::new(BufReader::new(File::open(path)?), delim))
+    }
+
+    pub fn from_file(file: File, delim: u8) -> DelimLender<BufReader<File>> {
+        DelimLender::new(BufReader::new(file), delim)
+    }
+}
+
+impl<'lend, B: BufRead> Lending<'lend> for DelimLender<B> {
+    type Lend = io::Result<&'lend [u8]>;
+}
+
+impl<B: BufRead> Lender for DelimLender<B> {
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.record.clear();
+        match self.buf.read_until(self.delim, &mut self.record) {
+            Err(e) => Some(Err(e)),
+            Ok(0) => None,
+            Ok(_) => {
+                if self.record.last() == Some(&self.delim) {
+                    self.record.pop();
+                }
+                Some(Ok(&self.record))
+            }
+        }
+    }
+}
+
+impl<B: BufRead + Seek> RewindableIoLender<[u8]> for DelimLender<B> {
+    type Error = io::Error;
+    fn rewind(mut self) -> io::Result<Self> {
+        self.buf.seek(io::SeekFrom::Start(0)).map(|_| ())?;
+        Ok(self)
+    }
+}
+
+/// A wrapper around a [`DelimLender`] lending its records as `&str`.
+///
+/// Records that are not valid UTF-8 are returned as errors of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData).
+pub struct DelimStrLender<B>(DelimLender<B>);
+
+impl<B> DelimStrLender<B> {
+    pub fn new(buf: B, delim: u8) -> Self {
+        DelimStrLender(DelimLender::new(buf, delim))
+    }
+}
+
+impl DelimStrLender<BufReader<File>> {
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        delim: u8,
+    ) -> io::Result<DelimStrLender<BufReader<File>>> {
+        Ok(DelimStrLender(DelimLender::from_path(path, delim)?))
+    }
+
+    pub fn from_file(file: File, delim: u8) -> DelimStrLender<BufReader<File>> {
+        DelimStrLender(DelimLender::from_file(file, delim))
+    }
+}
+
+impl<B> From<DelimLender<B>> for DelimStrLender<B> {
+    fn from(lender: DelimLender<B>) -> Self {
+        DelimStrLender(lender)
+    }
+}
+
+impl<'lend, B: BufRead> Lending<'lend> for DelimStrLender<B> {
+    type Lend = io::Result<&'lend str>;
+}
+
+impl<B: BufRead> Lender for DelimStrLender<B> {
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.0.next().map(|record| {
+            record.and_then(|record| {
+                std::str::from_utf8(record)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+        })
+    }
+}
+
+impl<B: BufRead + Seek> RewindableIoLender<str> for DelimStrLender<B> {
+    type Error = io::Error;
+    fn rewind(self) -> io::Result<Self> {
+        Ok(DelimStrLender(self.0.rewind()?))
+    }
+}
+
 /// A structure lending the lines coming from a zstd-compressed [`Read`] as
 /// `&str`.
 ///
@@ -10,12 +10,18 @@
 pub mod lenders;
 pub use crate::utils::lenders::*;
 
+pub mod popcount;
+pub use crate::utils::popcount::*;
+
 pub mod sig_store;
 pub use crate::utils::sig_store::*;
 
 pub mod spooky;
 pub use crate::utils::spooky::*;
 
+pub mod vbyte;
+pub use crate::utils::vbyte::*;
+
 /// Transmutes a vector of one type into a vector of another type.
 ///
 /// [It is not safe to transmute a
@@ -43,3 +49,95 @@ pub unsafe fn transmute_boxed_slice<S, D>(b: Box<[S]>) -> Box<[D]> {
     let mut b = std::mem::ManuallyDrop::new(b);
     Box::from_raw(b.as_mut() as *mut [S] as *mut [D])
 }
+
+/// Returns the index of the first element of `values` that is smaller than
+/// its predecessor, or `None` if `values` is monotone (i.e., nondecreasing).
+///
+/// This function can be used to validate (and locate errors in) data before
+/// building an [Elias–Fano representation](crate::dict::elias_fano), whose
+/// builders panic on non-monotone input.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::utils::first_non_monotone;
+///
+/// assert_eq!(first_non_monotone(&[0, 1, 1, 5]), None);
+/// assert_eq!(first_non_monotone(&[0, 2, 1, 5]), Some(2));
+/// ```
+pub fn first_non_monotone(values: &[usize]) -> Option<usize> {
+    values.windows(2).position(|w| w[1] < w[0]).map(|i| i + 1)
+}
+
+/// Returns true if `values` is monotone (i.e., nondecreasing).
+///
+/// See [`first_non_monotone`] to find where monotonicity is violated.
+pub fn is_monotone(values: &[usize]) -> bool {
+    first_non_monotone(values).is_none()
+}
+
+/// Returns the binary entropy H(`p`) = −`p` log₂ `p` − (1 − `p`) log₂(1 − `p`),
+/// with the convention that H(0) = H(1) = 0.
+///
+/// This is the number of bits per element necessary to represent a bit
+/// vector of density `p` using an optimal compressed representation, and it
+/// can be used to estimate whether, say, an [Elias–Fano
+/// representation](crate::dict::elias_fano) would be more compact than a bit
+/// vector.
+///
+/// # Panics
+///
+/// This function will panic if `p` is not in the interval [0 . . 1].
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::utils::entropy_bits_per_element;
+///
+/// assert_eq!(entropy_bits_per_element(0.5), 1.0);
+/// assert_eq!(entropy_bits_per_element(0.0), 0.0);
+/// ```
+pub fn entropy_bits_per_element(p: f64) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "The density {} is not in [0..1]",
+        p
+    );
+    if p == 0.0 || p == 1.0 {
+        0.0
+    } else {
+        -p * p.log2() - (1.0 - p) * (1.0 - p).log2()
+    }
+}
+
+/// Returns the position of the one of given rank in a bit vector represented
+/// as a slice of words, or `None` if there are not enough ones.
+///
+/// Bits are numbered starting from the lowest bit of the first word. The
+/// function simply scans the words accumulating their number of ones, and
+/// then selects in the word containing the one of given rank, so it takes
+/// time linear in the number of words. This is the baseline for selection
+/// without an index: it is useful for small inputs and as a reference.
+///
+/// # Examples
+///
+/// ```rust
+/// use sux::utils::select_in_slice;
+///
+/// let words = [0b1010, 0, 1 << 63];
+/// assert_eq!(select_in_slice(&words, 0), Some(1));
+/// assert_eq!(select_in_slice(&words, 1), Some(3));
+/// assert_eq!(select_in_slice(&words, 2), Some(191));
+/// assert_eq!(select_in_slice(&words, 3), None);
+/// ```
+pub fn select_in_slice(words: &[u64], mut rank: usize) -> Option<usize> {
+    use common_traits::SelectInWord;
+    for (word_index, &word) in words.iter().enumerate() {
+        let ones = word.count_ones() as usize;
+        if rank < ones {
+            return Some(word_index * u64::BITS as usize + word.select_in_word(rank));
+        }
+        rank -= ones;
+    }
+    None
+}
@@ -0,0 +1,110 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Population counting on slices of words.
+//!
+//! On `x86_64` targets supporting AVX2 (as detected at runtime),
+//! [`popcount_slice`] uses a vectorized implementation based on `vpshufb`
+//! described by Wojciech Muła, Nathan Kurz, and Daniel Lemire in “[Faster
+//! Population Counts Using AVX2
+//! Instructions](https://doi.org/10.1093/comjnl/bxx046)”, _The Computer
+//! Journal_, 61(1):111–120, 2018. On all other targets, or for short
+//! slices, it falls back to [`popcount_slice_scalar`].
+
+/// Slices shorter than this number of words are counted using the scalar
+/// implementation, as the vectorized one would not amortize its setup cost.
+#[cfg(target_arch = "x86_64")]
+const AVX2_THRESHOLD: usize = 32;
+
+/// Returns the number of ones in a slice of words.
+///
+/// The result is always identical to that of [`popcount_slice_scalar`], but
+/// the computation is vectorized on `x86_64` targets supporting AVX2.
+#[inline]
+pub fn popcount_slice(words: &[usize]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if words.len() >= AVX2_THRESHOLD && std::is_x86_feature_detected!("avx2") {
+        // SAFETY: we just checked that AVX2 is available
+        return unsafe { popcount_slice_avx2(words) };
+    }
+    popcount_slice_scalar(words)
+}
+
+/// Returns the number of ones in a slice of words using
+/// [`usize::count_ones`] on each word.
+#[inline]
+pub fn popcount_slice_scalar(words: &[usize]) -> usize {
+    words.iter().map(|x| x.count_ones() as usize).sum()
+}
+
+/// Returns the number of ones in a slice of words using AVX2 instructions.
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn popcount_slice_avx2(words: &[usize]) -> usize {
+    use core::arch::x86_64::*;
+
+    // Number of ones in each nibble
+    let lookup = _mm256_setr_epi8(
+        0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, //
+        0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+    );
+    let low_mask = _mm256_set1_epi8(0x0f);
+    let zero = _mm256_setzero_si256();
+    let mut acc = zero;
+
+    // A __m256i contains 256 bits, that is, 256 / usize::BITS words
+    let chunks = words.chunks_exact(256 / usize::BITS as usize);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        let v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+        let lo = _mm256_and_si256(v, low_mask);
+        let hi = _mm256_and_si256(_mm256_srli_epi16(v, 4), low_mask);
+        // Byte-wise counts, at most 8 per byte
+        let counts = _mm256_add_epi8(
+            _mm256_shuffle_epi8(lookup, lo),
+            _mm256_shuffle_epi8(lookup, hi),
+        );
+        // Horizontal sums of groups of 8 bytes into 64-bit lanes
+        acc = _mm256_add_epi64(acc, _mm256_sad_epu8(counts, zero));
+    }
+
+    let mut lanes = [0_u64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, acc);
+    lanes.iter().sum::<u64>() as usize + popcount_slice_scalar(remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_popcount_slice() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for len in [0, 1, 3, 4, 5, 31, 32, 33, 100, 1000, 10000] {
+            for density in [0.0, 0.1, 0.5, 0.9, 1.0] {
+                let words = (0..len)
+                    .map(|_| {
+                        (0..usize::BITS).fold(0, |w, b| w | (rng.gen_bool(density) as usize) << b)
+                    })
+                    .collect::<Vec<usize>>();
+                let expected = words.iter().map(|x| x.count_ones() as usize).sum();
+                assert_eq!(popcount_slice_scalar(&words), expected);
+                assert_eq!(popcount_slice(&words), expected);
+                #[cfg(target_arch = "x86_64")]
+                if std::is_x86_feature_detected!("avx2") {
+                    assert_eq!(unsafe { popcount_slice_avx2(&words) }, expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! A byte-aligned variable-length code for integers.
+//!
+//! This is the code used, for example, for rear lengths in [rear-coded
+//! lists](crate::dict::rear_coded_list) and for gaps in the [delta-VByte
+//! representation](crate::dict::EliasFano::to_delta_vbyte) of Elias–Fano
+//! sequences.
+//!
+//! Integers smaller than 2⁷ are written in a single byte. Otherwise, the
+//! number of leading ones in the first byte is the number of additional
+//! bytes, and the remaining bits of the first byte, followed by the
+//! additional bytes in big-endian order, represent the integer minus the
+//! number of integers representable with fewer bytes. At most nine bytes are
+//! used for a 64-bit integer. Since the number of bytes of an encoding can be
+//! deduced from its first byte, the code is prefix-free.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sux::utils::{decode_int, encode_int, encode_int_len};
+//!
+//! let mut data = Vec::new();
+//! encode_int(100, &mut data);
+//! encode_int(1000, &mut data);
+//! assert_eq!(data.len(), encode_int_len(100) + encode_int_len(1000));
+//!
+//! let (x, data) = decode_int(&data);
+//! assert_eq!(x, 100);
+//! let (y, data) = decode_int(data);
+//! assert_eq!(y, 1000);
+//! assert!(data.is_empty());
+//! ```
+
+/// Returns the length in bytes of the VByte encoding of `value`.
+#[inline(always)]
+pub fn encode_int_len(mut value: usize) -> usize {
+    let mut len = 1;
+    let mut max = 1 << 7;
+    // Values larger than or equal to UPPER_BOUND_8 are written in 9 bytes
+    while len < 9 && value >= max {
+        len += 1;
+        value -= max;
+        max <<= 7;
+    }
+    len
+}
+
+const UPPER_BOUND_1: usize = 128;
+const UPPER_BOUND_2: usize = 128_usize.pow(2) + UPPER_BOUND_1;
+const UPPER_BOUND_3: usize = 128_usize.pow(3) + UPPER_BOUND_2;
+const UPPER_BOUND_4: usize = 128_usize.pow(4) + UPPER_BOUND_3;
+const UPPER_BOUND_5: usize = 128_usize.pow(5) + UPPER_BOUND_4;
+const UPPER_BOUND_6: usize = 128_usize.pow(6) + UPPER_BOUND_5;
+const UPPER_BOUND_7: usize = 128_usize.pow(7) + UPPER_BOUND_6;
+const UPPER_BOUND_8: usize = 128_usize.pow(8) + UPPER_BOUND_7;
+
+/// Appends the VByte encoding of `value` to `data`.
+#[inline(always)]
+pub fn encode_int(mut value: usize, data: &mut Vec<u8>) {
+    if value < UPPER_BOUND_1 {
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_2 {
+        value -= UPPER_BOUND_1;
+        debug_assert!((value >> 8) < (1 << 6));
+        data.push(0x80 | (value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_3 {
+        value -= UPPER_BOUND_2;
+        debug_assert!((value >> 16) < (1 << 5));
+        data.push(0xC0 | (value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_4 {
+        value -= UPPER_BOUND_3;
+        debug_assert!((value >> 24) < (1 << 4));
+        data.push(0xE0 | (value >> 24) as u8);
+        data.push((value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_5 {
+        value -= UPPER_BOUND_4;
+        debug_assert!((value >> 32) < (1 << 3));
+        data.push(0xF0 | (value >> 32) as u8);
+        data.push((value >> 24) as u8);
+        data.push((value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_6 {
+        value -= UPPER_BOUND_5;
+        debug_assert!((value >> 40) < (1 << 2));
+        data.push(0xF8 | (value >> 40) as u8);
+        data.push((value >> 32) as u8);
+        data.push((value >> 24) as u8);
+        data.push((value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_7 {
+        value -= UPPER_BOUND_6;
+        debug_assert!((value >> 48) < (1 << 1));
+        data.push(0xFC | (value >> 48) as u8);
+        data.push((value >> 40) as u8);
+        data.push((value >> 32) as u8);
+        data.push((value >> 24) as u8);
+        data.push((value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+    if value < UPPER_BOUND_8 {
+        value -= UPPER_BOUND_7;
+        data.push(0xFE);
+        data.push((value >> 48) as u8);
+        data.push((value >> 40) as u8);
+        data.push((value >> 32) as u8);
+        data.push((value >> 24) as u8);
+        data.push((value >> 16) as u8);
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+        return;
+    }
+
+    data.push(0xFF);
+    data.push((value >> 56) as u8);
+    data.push((value >> 48) as u8);
+    data.push((value >> 40) as u8);
+    data.push((value >> 32) as u8);
+    data.push((value >> 24) as u8);
+    data.push((value >> 16) as u8);
+    data.push((value >> 8) as u8);
+    data.push(value as u8);
+}
+
+/// Decodes a VByte-encoded integer at the start of `data`, returning the
+/// integer and the remaining data.
+///
+/// # Panics
+///
+/// This function will panic if `data` does not start with a complete
+/// encoding.
+#[inline(always)]
+pub fn decode_int(data: &[u8]) -> (usize, &[u8]) {
+    let x = data[0];
+    if x < 0x80 {
+        return (x as usize, &data[1..]);
+    }
+    if x < 0xC0 {
+        let x = (((x & !0xC0) as usize) << 8 | data[1] as usize) + UPPER_BOUND_1;
+        return (x, &data[2..]);
+    }
+    if x < 0xE0 {
+        let x = (((x & !0xE0) as usize) << 16 | (data[1] as usize) << 8 | data[2] as usize)
+            + UPPER_BOUND_2;
+        return (x, &data[3..]);
+    }
+    if x < 0xF0 {
+        let x = (((x & !0xF0) as usize) << 24
+            | (data[1] as usize) << 16
+            | (data[2] as usize) << 8
+            | data[3] as usize)
+            + UPPER_BOUND_3;
+        return (x, &data[4..]);
+    }
+    if x < 0xF8 {
+        let x = (((x & !0xF8) as usize) << 32
+            | (data[1] as usize) << 24
+            | (data[2] as usize) << 16
+            | (data[3] as usize) << 8
+            | data[4] as usize)
+            + UPPER_BOUND_4;
+        return (x, &data[5..]);
+    }
+    if x < 0xFC {
+        let x = (((x & !0xFC) as usize) << 40
+            | (data[1] as usize) << 32
+            | (data[2] as usize) << 24
+            | (data[3] as usize) << 16
+            | (data[4] as usize) << 8
+            | data[5] as usize)
+            + UPPER_BOUND_5;
+        return (x, &data[6..]);
+    }
+    if x < 0xFE {
+        let x = (((x & !0xFE) as usize) << 48
+            | (data[1] as usize) << 40
+            | (data[2] as usize) << 32
+            | (data[3] as usize) << 24
+            | (data[4] as usize) << 16
+            | (data[5] as usize) << 8
+            | data[6] as usize)
+            + UPPER_BOUND_6;
+        return (x, &data[7..]);
+    }
+    if x < 0xFF {
+        let x = ((data[1] as usize) << 48
+            | (data[2] as usize) << 40
+            | (data[3] as usize) << 32
+            | (data[4] as usize) << 24
+            | (data[5] as usize) << 16
+            | (data[6] as usize) << 8
+            | data[7] as usize)
+            + UPPER_BOUND_7;
+        return (x, &data[8..]);
+    }
+
+    let x = (data[1] as usize) << 56
+        | (data[2] as usize) << 48
+        | (data[3] as usize) << 40
+        | (data[4] as usize) << 32
+        | (data[5] as usize) << 24
+        | (data[6] as usize) << 16
+        | (data[7] as usize) << 8
+        | data[8] as usize;
+    (x, &data[9..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_int() {
+        let values = [
+            0,
+            1,
+            2,
+            3,
+            4,
+            5,
+            6,
+            7,
+            8,
+            UPPER_BOUND_1 - 1,
+            UPPER_BOUND_1,
+            UPPER_BOUND_1 + 1,
+            UPPER_BOUND_2 - 1,
+            UPPER_BOUND_2,
+            UPPER_BOUND_2 + 1,
+            UPPER_BOUND_3 - 1,
+            UPPER_BOUND_3,
+            UPPER_BOUND_3 + 1,
+            UPPER_BOUND_4 - 1,
+            UPPER_BOUND_4,
+            UPPER_BOUND_4 + 1,
+            UPPER_BOUND_5 - 1,
+            UPPER_BOUND_5,
+            UPPER_BOUND_5 + 1,
+            UPPER_BOUND_6 - 1,
+            UPPER_BOUND_6,
+            UPPER_BOUND_6 + 1,
+            UPPER_BOUND_7 - 1,
+            UPPER_BOUND_7,
+            UPPER_BOUND_7 + 1,
+            UPPER_BOUND_8 - 1,
+            UPPER_BOUND_8,
+            UPPER_BOUND_8 + 1,
+            1 << 63,
+            usize::MAX,
+        ];
+        let mut buffer = Vec::with_capacity(128);
+
+        for i in &values {
+            encode_int(*i, &mut buffer);
+        }
+
+        let mut data = &buffer[..];
+        for i in &values {
+            let (j, tmp) = decode_int(data);
+            assert_eq!(data.len() - tmp.len(), encode_int_len(*i));
+            data = tmp;
+            assert_eq!(*i, j);
+        }
+    }
+}
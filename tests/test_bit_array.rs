@@ -0,0 +1,75 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::bits::BitArray;
+use sux::prelude::*;
+
+#[test]
+fn test_bit_array() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for density in [0.0, 0.01, 0.5, 0.99, 1.0] {
+        let mut array = BitArray::<4>::new();
+        let mut bits = BitVec::new(256);
+        assert_eq!(array.len(), bits.len());
+
+        for i in 0..256 {
+            let value = rng.gen_bool(density);
+            array.set(i, value);
+            bits.set(i, value);
+        }
+
+        for i in 0..256 {
+            assert_eq!(array.get(i), bits.get(i));
+            assert_eq!(array[i], bits[i]);
+        }
+        let words: &[usize] = bits.as_ref();
+        assert_eq!(array.as_ref(), words);
+        assert_eq!(array.count_ones(), bits.count_ones());
+        assert_eq!(array.num_ones(), bits.count_ones());
+
+        let ones = bits.iter_ones().collect::<Vec<_>>();
+        for (i, &pos) in ones.iter().enumerate() {
+            assert_eq!(array.select(i), Some(pos));
+        }
+        assert_eq!(array.select(ones.len()), None);
+
+        let zeros = bits.iter_zeros().collect::<Vec<_>>();
+        for (i, &pos) in zeros.iter().enumerate() {
+            assert_eq!(array.select_zero(i), Some(pos));
+        }
+        assert_eq!(array.select_zero(zeros.len()), None);
+
+        let rank9 = Rank9::new(bits.clone());
+        for pos in 0..=256 {
+            assert_eq!(array.rank(pos), rank9.rank(pos));
+            assert_eq!(array.rank_zero(pos), rank9.rank_zero(pos));
+        }
+    }
+}
+
+#[test]
+fn test_bit_array_backend() {
+    let mut array = BitArray::<2>::new();
+    (0..128).step_by(3).for_each(|i| array.set(i, true));
+    let select = SelectAdaptConst::<_, _>::new(array.clone());
+    for i in 0..array.num_ones() {
+        assert_eq!(select.select(i), Some(i * 3));
+    }
+
+    array.fill(true);
+    assert_eq!(array.count_ones(), 128);
+    assert_eq!(array.into_inner(), [usize::MAX; 2]);
+}
+
+#[test]
+#[should_panic]
+fn test_bit_array_out_of_bounds() {
+    let mut array = BitArray::<1>::new();
+    array.set(64, true);
+}
@@ -270,7 +270,16 @@ fn test_from_slice() {
     for i in 0..c.len() {
         assert_eq!(s.get(i) as usize, c.get(i));
     }
-    assert!(BitFieldVec::<u8>::from_slice(&c).is_err())
+    assert_eq!(
+        BitFieldVec::<u8>::from_slice(&c),
+        Err(ConvertError::BitWidthTooLarge {
+            bit_width: 10,
+            word: "u8"
+        })
+    );
+    // The error converts transparently into an anyhow::Error
+    let f = || -> anyhow::Result<BitFieldVec<u8>> { Ok(BitFieldVec::<u8>::from_slice(&c)?) };
+    assert!(f().is_err());
 }
 
 #[test]
@@ -577,3 +586,127 @@ fn test_slice_atomic() {
     atomic_slice_eq(b.as_slice(), v.as_slice());
     assert_eq!(b.get_atomic(21, Ordering::Relaxed), 4);
 }
+
+#[test]
+fn test_iter_vs_vec() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let values = (0..1000)
+        .map(|_| rng.gen_range(0..1 << 9))
+        .collect::<Vec<usize>>();
+    let mut c = BitFieldVec::<usize>::new(9, 0);
+    c.extend(values.iter().copied());
+
+    assert_eq!(c.iter().collect::<Vec<_>>(), values);
+    assert_eq!((&c).into_iter().collect::<Vec<_>>(), values);
+    for from in [0, 1, 10, 500, 999, 1000] {
+        assert_eq!(
+            (&c).into_iter_from(from).collect::<Vec<_>>(),
+            values[from..]
+        );
+    }
+}
+
+#[test]
+fn test_raw_words() {
+    use sux::traits::bit_field_slice::BitFieldSlice;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for bit_width in [0, 1, 3, 7, 8, 13, 32, 63, 64] {
+        for len in [0, 1, 10, 63, 64, 65, 1000] {
+            let mask = if bit_width == 0 {
+                0
+            } else {
+                usize::MAX >> (usize::BITS as usize - bit_width)
+            };
+            let mut b = BitFieldVec::<usize>::with_capacity(bit_width, len);
+            for _ in 0..len {
+                b.push(rng.gen::<usize>() & mask);
+            }
+            // Dirty the backend beyond the values
+            b.push(mask);
+            b.pop();
+
+            let words = b.raw_words();
+            assert_eq!(words.len(), (len * bit_width).div_ceil(64));
+            for i in 0..len {
+                let start = i * bit_width;
+                let (word, bit) = (start / 64, start % 64);
+                let mut value = words.get(word).copied().unwrap_or(0) >> bit;
+                if bit + bit_width > 64 {
+                    value |= words[word + 1] << (64 - bit);
+                }
+                assert_eq!(
+                    value & mask,
+                    b.get(i),
+                    "bit_width = {}, index = {}",
+                    bit_width,
+                    i
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_into_non_atomic() {
+    test_into_non_atomic_param::<u8>();
+    test_into_non_atomic_param::<u16>();
+    test_into_non_atomic_param::<u32>();
+    test_into_non_atomic_param::<u64>();
+    test_into_non_atomic_param::<usize>();
+}
+
+fn test_into_non_atomic_param<W: Word + IntoAtomic + CastableInto<u64> + CastableFrom<u64>>()
+where
+    W::AtomicType: AtomicUnsignedInt + AsBytes,
+{
+    use sux::traits::bit_field_slice::AtomicBitFieldSlice;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for bit_width in 0..W::BITS {
+        let n: usize = 100;
+        let u: u64 = 1 << bit_width;
+        let values: Vec<W> = (0..n).map(|_| rng.gen_range(0..u).cast()).collect();
+
+        let v = AtomicBitFieldVec::<W>::new(bit_width, n);
+        for (i, &value) in values.iter().enumerate() {
+            v.set_atomic(i, value, Ordering::Relaxed);
+        }
+
+        let v = v.into_non_atomic();
+        assert_eq!(v.bit_width(), bit_width);
+        assert_eq!(v.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(v.get(i), value);
+        }
+
+        let v = v.into_atomic();
+        assert_eq!(v.bit_width(), bit_width);
+        assert_eq!(v.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(v.get_atomic(i, Ordering::Relaxed), value);
+        }
+
+        // The From implementations, also on boxed slices
+        let v: BitFieldVec<W, Vec<W>> = v.into();
+        let v: BitFieldVec<W, Box<[W]>> = v.into();
+        let v: AtomicBitFieldVec<W, Box<[W::AtomicType]>> = v.into();
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(v.get_atomic(i, Ordering::Relaxed), value);
+        }
+        let v: BitFieldVec<W, Box<[W]>> = v.into();
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(v.get(i), value);
+        }
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Index out of bounds")]
+fn test_atomic_set_unchecked_out_of_bounds() {
+    use sux::traits::bit_field_slice::AtomicBitFieldSlice;
+    let v = AtomicBitFieldVec::<usize>::new(5, 10);
+    // The backend has room for more values, but the index is out of bounds
+    unsafe { v.set_atomic_unchecked(10, 1, Ordering::Relaxed) };
+}
@@ -9,7 +9,7 @@ use core::sync::atomic::Ordering;
 use epserde::prelude::*;
 use rand::rngs::SmallRng;
 use rand::seq::SliceRandom;
-use rand::{RngCore, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
 use std::sync::atomic::AtomicUsize;
 use sux::prelude::*;
 
@@ -227,6 +227,57 @@ fn test_atomic_fill() {
     }
 }
 
+#[test]
+fn test_atomic_set_range() {
+    for len in [1, 64, 65, 100, 127, 128, 1000] {
+        for start in [0, 1, 63, 64, 65, len / 2, len] {
+            for end in [start, start + 1, start + 63, start + 64, start + 130, len] {
+                if start > len || end > len || start > end {
+                    continue;
+                }
+                let c = AtomicBitVec::new(len);
+                c.set_range_atomic(start..end, true, Ordering::Relaxed);
+                for i in 0..len {
+                    assert_eq!(c.get(i, Ordering::Relaxed), (start..end).contains(&i));
+                }
+                let c = AtomicBitVec::with_value(len, true);
+                c.set_range_atomic(start..end, false, Ordering::Relaxed);
+                for i in 0..len {
+                    assert_eq!(c.get(i, Ordering::Relaxed), !(start..end).contains(&i));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_atomic_set_range_concurrent() {
+    let len = 10_000;
+    let c = AtomicBitVec::new(len);
+    let ranges = [
+        3..1000,
+        500..1500,
+        2001..2003,
+        2002..5000,
+        4999..6000,
+        7000..7000,
+    ];
+    std::thread::scope(|s| {
+        for range in ranges.iter().cloned() {
+            let c = &c;
+            s.spawn(move || c.set_range_atomic(range, true, Ordering::Relaxed));
+        }
+    });
+    // Union of the ranges: 3..1500, 2001..6000
+    assert_eq!(c.count_ones(), 1497 + 3999);
+    for i in 0..len {
+        assert_eq!(
+            c.get(i, Ordering::Relaxed),
+            ranges.iter().any(|r| r.contains(&i))
+        );
+    }
+}
+
 #[test]
 fn test_flip() {
     for len in [0, 1, 64, 65, 100, 127, 128, 1000] {
@@ -513,3 +564,511 @@ fn test_macro() {
     assert!(!b[4]);
     assert!(!b[5]);
 }
+
+#[test]
+fn test_checksum() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000] {
+        let mut bits = BitVec::new(len);
+        for i in 0..len {
+            bits.set(i, rng.next_u32() % 2 == 0);
+        }
+        let checksum = bits.checksum();
+        assert!(bits.verify(checksum));
+
+        // Flipping a single bit changes the checksum
+        for i in 0..len {
+            bits.set(i, !bits[i]);
+            assert_ne!(bits.checksum(), checksum);
+            assert!(!bits.verify(checksum));
+            bits.set(i, !bits[i]);
+            assert_eq!(bits.checksum(), checksum);
+        }
+
+        // Padding bits do not affect the checksum
+        let (mut raw, _) = bits.clone().into_raw_parts();
+        if len % usize::BITS as usize != 0 {
+            *raw.last_mut().unwrap() ^= usize::MAX << (len % usize::BITS as usize);
+        }
+        raw.push(usize::MAX);
+        let dirty = unsafe { BitVec::from_raw_parts(raw, len) };
+        assert_eq!(dirty.checksum(), checksum);
+
+        // The length is part of the checksum
+        let mut longer = bits.clone();
+        longer.push(false);
+        assert_ne!(longer.checksum(), checksum);
+
+        let atomic: AtomicBitVec = bits.into();
+        assert_eq!(atomic.checksum(Ordering::Relaxed), checksum);
+        assert!(atomic.verify(checksum, Ordering::Relaxed));
+    }
+}
+
+#[test]
+fn test_density() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    assert_eq!(BitVec::new(0).density(), 0.0);
+    for len in [1, 10, 64, 1000] {
+        let mut bits = BitVec::new(len);
+        for i in 0..len {
+            bits.set(i, rng.next_u32() % 4 == 0);
+        }
+        let expected = bits.count_ones() as f64 / len as f64;
+        assert_eq!(bits.density(), expected);
+        let bits: AddNumBits<_> = bits.into();
+        assert_eq!(bits.density(), expected);
+    }
+    assert_eq!(BitVec::with_value(100, true).density(), 1.0);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_from_u64() {
+    let words: Vec<u64> = vec![0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, 0xff];
+    let expected = unsafe {
+        BitVec::from_raw_parts(words.iter().map(|&w| w as usize).collect::<Vec<_>>(), 140)
+    };
+
+    let b: BitVec<&[usize]> = unsafe { BitVec::from_raw_parts(words.as_slice(), 140) }.into();
+    assert_eq!(b, expected);
+    let rank9 = Rank9::new(b);
+    let mut ones = 0;
+    for i in 0..140 {
+        assert_eq!(rank9.rank(i), ones);
+        ones += expected[i] as usize;
+    }
+    assert_eq!(rank9.rank(140), ones);
+
+    let mut mut_words = words.clone();
+    let mut b: BitVec<&mut [usize]> =
+        unsafe { BitVec::from_raw_parts(mut_words.as_mut_slice(), 140) }.into();
+    b.set(0, false);
+    assert_eq!(mut_words[0], 0x0123_4567_89ab_cdee);
+
+    let b: BitVec<Box<[usize]>> =
+        unsafe { BitVec::from_raw_parts(words.clone().into_boxed_slice(), 140) }.into();
+    assert_eq!(b, expected);
+
+    let b: BitVec<Vec<usize>> = unsafe { BitVec::from_raw_parts(words, 140) }.into();
+    assert_eq!(b, expected);
+    let rank9 = Rank9::new(b);
+    assert_eq!(rank9.rank(140), ones);
+}
+
+#[test]
+fn test_set_ones_sorted() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 64, 65, 1000, 10000] {
+        for num in [0, 1, 10, 100, 5000] {
+            let mut positions = (0..num)
+                .map(|_| rng.next_u64() as usize % len)
+                .collect::<Vec<_>>();
+            positions.sort();
+
+            let mut expected = BitVec::new(len);
+            for &pos in &positions {
+                expected.set(pos, true);
+            }
+
+            let mut b = BitVec::new(len);
+            b.set_ones_sorted(&positions);
+            assert_eq!(b, expected);
+
+            // Existing ones must be preserved
+            let mut b = BitVec::with_value(len, true);
+            b.set_ones_sorted(&positions);
+            assert_eq!(b, BitVec::with_value(len, true));
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn test_set_ones_sorted_unsorted() {
+    let mut b = BitVec::new(100);
+    b.set_ones_sorted(&[3, 1]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn test_set_ones_sorted_out_of_bounds() {
+    let mut b = BitVec::new(100);
+    b.set_ones_sorted(&[3, 100]);
+}
+
+#[test]
+fn test_slice() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1000;
+    let mut b = BitVec::new(len);
+    for i in 0..len {
+        b.set(i, rng.next_u64() % 3 == 0);
+    }
+
+    for (start, end) in [
+        (0, 0),
+        (0, len),
+        (1, 2),
+        (3, 60),
+        (3, 64),
+        (5, 70),
+        (63, 65),
+        (64, 128),
+        (100, 999),
+        (17, 1000),
+        (1000, 1000),
+    ] {
+        let s = b.slice(start..end);
+        assert_eq!(s.len(), end - start);
+        assert_eq!(BitLength::len(&s), end - start);
+        for i in 0..s.len() {
+            assert_eq!(s[i], b[start + i]);
+            assert_eq!(s.get(i), b[start + i]);
+        }
+        assert_eq!(
+            s.iter().collect::<Vec<_>>(),
+            (start..end).map(|i| b[i]).collect::<Vec<_>>()
+        );
+        assert_eq!(s.count_ones(), (start..end).filter(|&i| b[i]).count());
+
+        // Slices of slices
+        if s.len() >= 2 {
+            let t = s.slice(1..s.len() - 1);
+            assert_eq!(t.len(), s.len() - 2);
+            for i in 0..t.len() {
+                assert_eq!(t[i], b[start + 1 + i]);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_slice_out_of_bounds() {
+    let b = BitVec::new(100);
+    let _ = b.slice(50..101);
+}
+
+#[test]
+#[should_panic]
+fn test_slice_index_out_of_bounds() {
+    let b = BitVec::new(100);
+    let s = b.slice(10..20);
+    let _ = s[10];
+}
+
+#[test]
+fn test_atomic_rank() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 64, 65, 100, 127, 128, 1000, 10_000] {
+        // Fully populated
+        let c = AtomicBitVec::with_value(len, true);
+        let rank9 = Rank9::new(BitVec::with_value(len, true));
+        for pos in 0..=len {
+            assert_eq!(c.rank_atomic(pos, Ordering::Relaxed), pos);
+            assert_eq!(c.rank_atomic(pos, Ordering::Relaxed), rank9.rank(pos));
+        }
+
+        // Random
+        let c = AtomicBitVec::new(len);
+        for i in 0..len {
+            c.set(i, rng.next_u64() % 2 == 0, Ordering::Relaxed);
+        }
+        let b: BitVec = (0..len).map(|i| c.get(i, Ordering::Relaxed)).collect();
+        let rank9 = Rank9::new(b);
+        for pos in 0..=len {
+            assert_eq!(c.rank_atomic(pos, Ordering::Relaxed), rank9.rank(pos));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_atomic_rank_out_of_bounds() {
+    let c = AtomicBitVec::new(100);
+    c.rank_atomic(101, Ordering::Relaxed);
+}
+
+fn brute_force_runs(b: &BitVec) -> (usize, usize, usize, usize) {
+    let (mut ones_runs, mut zeros_runs) = (0, 0);
+    let (mut longest_ones, mut longest_zeros) = (0, 0);
+    let mut current = 0;
+    for i in 0..b.len() {
+        if i == 0 || b[i] != b[i - 1] {
+            if b[i] {
+                ones_runs += 1;
+            } else {
+                zeros_runs += 1;
+            }
+            current = 0;
+        }
+        current += 1;
+        if b[i] {
+            longest_ones = longest_ones.max(current);
+        } else {
+            longest_zeros = longest_zeros.max(current);
+        }
+    }
+    (ones_runs, zeros_runs, longest_ones, longest_zeros)
+}
+
+#[test]
+fn test_runs() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 2, 63, 64, 65, 127, 128, 129, 1000, 10_000] {
+        let mut vectors = vec![
+            BitVec::new(len),
+            BitVec::with_value(len, true),
+            (0..len).map(|i| i % 2 == 0).collect::<BitVec>(),
+            (0..len).map(|i| i % 2 == 1).collect::<BitVec>(),
+            (0..len).map(|i| (i / 64) % 2 == 0).collect::<BitVec>(),
+            (0..len).map(|i| (i / 100) % 3 == 0).collect::<BitVec>(),
+        ];
+        for _ in 0..10 {
+            let density = rng.next_u64() % 100;
+            vectors.push((0..len).map(|_| rng.next_u64() % 100 < density).collect());
+        }
+
+        for b in vectors {
+            let (ones_runs, zeros_runs, longest_ones, longest_zeros) = brute_force_runs(&b);
+            assert_eq!(b.count_runs(), (ones_runs, zeros_runs));
+            assert_eq!(b.longest_run(true), longest_ones);
+            assert_eq!(b.longest_run(false), longest_zeros);
+        }
+    }
+
+    assert_eq!(BitVec::with_value(1000, true).count_runs(), (1, 0));
+    assert_eq!(BitVec::new(1000).count_runs(), (0, 1));
+    assert_eq!(BitVec::new(0).count_runs(), (0, 0));
+    let alternating: BitVec = (0..1000).map(|i| i % 2 == 0).collect();
+    assert_eq!(alternating.count_runs(), (500, 500));
+    assert_eq!(alternating.longest_run(true), 1);
+
+    // Dirty bits beyond the length are ignored
+    let ones = [usize::MAX; 2];
+    let b = unsafe { BitVec::from_raw_parts(&ones, 70) };
+    assert_eq!(b.count_runs(), (1, 0));
+    assert_eq!(b.longest_run(true), 70);
+    assert_eq!(b.longest_run(false), 0);
+}
+
+#[test]
+fn test_rle() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 2, 63, 64, 65, 127, 128, 129, 1000, 10_000] {
+        let mut vectors = vec![
+            BitVec::new(len),
+            BitVec::with_value(len, true),
+            (0..len).map(|i| i % 2 == 0).collect::<BitVec>(),
+            (0..len).map(|i| i % 2 == 1).collect::<BitVec>(),
+            (0..len).map(|i| (i / 64) % 2 == 0).collect::<BitVec>(),
+            (0..len).map(|i| (i / 100) % 3 == 0).collect::<BitVec>(),
+        ];
+        for _ in 0..10 {
+            let density = rng.next_u64() % 100;
+            vectors.push((0..len).map(|_| rng.next_u64() % 100 < density).collect());
+        }
+
+        for b in vectors {
+            let runs = b.to_rle();
+            assert_eq!(runs.iter().sum::<usize>(), len);
+            assert!(runs.iter().skip(1).all(|&run| run > 0));
+            let (ones_runs, zeros_runs) = b.count_runs();
+            assert_eq!(
+                runs.len(),
+                ones_runs + zeros_runs + (len != 0 && b[0]) as usize
+            );
+            assert_eq!(BitVec::from_rle(&runs), b);
+        }
+    }
+
+    // A leading one yields an initial empty run of zeros
+    let b: BitVec = [true, false, false, true, true, true].into_iter().collect();
+    assert_eq!(b.to_rle(), vec![0, 1, 2, 3]);
+    assert_eq!(BitVec::from_rle(&[0, 1, 2, 3]), b);
+    assert!(BitVec::new(0).to_rle().is_empty());
+    assert_eq!(BitVec::from_rle(&[]).len(), 0);
+
+    // Empty runs are allowed anywhere when decoding
+    assert_eq!(
+        BitVec::from_rle(&[2, 0, 0, 3, 1]),
+        BitVec::from_rle(&[2, 3, 1])
+    );
+
+    // Dirty bits beyond the length are ignored
+    let ones = [usize::MAX; 2];
+    let b = unsafe { BitVec::from_raw_parts(&ones, 70) };
+    assert_eq!(b.to_rle(), vec![0, 70]);
+}
+
+#[test]
+fn test_diff() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 10_000] {
+        let base: BitVec = (0..len).map(|_| rng.next_u64() % 2 == 0).collect();
+        assert!(base.diff(&base).is_empty());
+        assert!(base.diff(&base.clone()).is_empty());
+
+        for changes in [1, 10, 100] {
+            let mut modified = base.clone();
+            if len != 0 {
+                for _ in 0..changes {
+                    let i = rng.next_u64() as usize % len;
+                    modified.set(i, !modified[i]);
+                }
+            }
+            let diff = modified.diff(&base);
+            assert!(diff.len() <= changes);
+            assert!(diff.windows(2).all(|w| w[0].0 < w[1].0));
+
+            let mut patched = base.clone();
+            patched.apply_diff(&diff);
+            assert_eq!(patched, modified);
+            assert!(patched.diff(&modified).is_empty());
+
+            // The reverse difference restores the base
+            patched.apply_diff(&base.diff(&modified));
+            assert_eq!(patched, base);
+        }
+    }
+
+    // Bits beyond the length are neither compared nor modified
+    let mut words = [usize::MAX, 0];
+    let base = unsafe { BitVec::from_raw_parts(&[0, 0][..], 70) };
+    let mut b = unsafe { BitVec::from_raw_parts(&mut words[..], 70) };
+    assert_eq!(b.diff(&base), vec![(0, usize::MAX)]);
+    b.apply_diff(&[(1, usize::MAX)]);
+    assert_eq!(words, [usize::MAX, 0x3f]);
+}
+
+#[test]
+#[should_panic]
+fn test_diff_different_lengths() {
+    BitVec::new(10).diff(&BitVec::new(11));
+}
+
+#[test]
+fn test_count_flips() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 10_000] {
+        let old: BitVec = (0..len).map(|_| rng.next_u64() % 2 == 0).collect();
+        assert_eq!(old.count_flips(&old), (0, 0));
+
+        let mut new = old.clone();
+        let (mut zeros_to_ones, mut ones_to_zeros) = (0, 0);
+        for i in 0..len {
+            if rng.next_u64() % 4 == 0 {
+                if new[i] {
+                    ones_to_zeros += 1;
+                } else {
+                    zeros_to_ones += 1;
+                }
+                new.set(i, !new[i]);
+            }
+        }
+        assert_eq!(old.count_flips(&new), (zeros_to_ones, ones_to_zeros));
+        assert_eq!(new.count_flips(&old), (ones_to_zeros, zeros_to_ones));
+    }
+
+    // Bits beyond the length are ignored
+    let old = unsafe { BitVec::from_raw_parts(&[0b1010, usize::MAX][..], 70) };
+    let new = unsafe { BitVec::from_raw_parts(&[0b0110, 0b11 | 0xf0][..], 70) };
+    assert_eq!(old.count_flips(&new), (1, 3));
+    assert_eq!(new.count_flips(&old), (3, 1));
+}
+
+#[test]
+#[should_panic]
+fn test_count_flips_different_lengths() {
+    BitVec::new(10).count_flips(&BitVec::new(11));
+}
+
+#[test]
+fn test_atomic_estimate_count_ones() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1 << 22;
+    for density in [1, 10, 30, 50, 90] {
+        let b: BitVec = (0..len).map(|_| rng.next_u64() % 100 < density).collect();
+        let c: AtomicBitVec<Vec<AtomicUsize>> = b.into();
+        let count = c.count_ones();
+
+        for samples in [10_000, 30_000] {
+            let estimate = c.estimate_count_ones(samples);
+            let error = (estimate as f64 - count as f64).abs() / count as f64;
+            assert!(
+                error < 0.1,
+                "density = {}%, samples = {}: {} vs. {}",
+                density,
+                samples,
+                estimate,
+                count
+            );
+        }
+        // Sampling all words gives the exact count
+        assert_eq!(c.estimate_count_ones(len / 64), count);
+        assert_eq!(c.estimate_count_ones(usize::MAX), count);
+    }
+
+    // Partial last word
+    for len in [0, 1, 63, 65, 1000] {
+        let c = AtomicBitVec::with_value(len, true);
+        assert_eq!(c.estimate_count_ones(1000), len);
+        assert_eq!(c.estimate_count_ones(1), len);
+    }
+}
+
+#[test]
+fn test_sanitize() {
+    for len in [0, 1, 10, 63, 64, 65, 100, 128, 1000] {
+        let mut b = BitVec::new(len);
+        for i in (0..len).step_by(3) {
+            b.set(i, true);
+        }
+        let expected = (0..len).step_by(3).count();
+        let num_words = len.div_ceil(64);
+        let padding_bits = num_words * 64 - len;
+        if padding_bits != 0 {
+            // Set the padding bits through raw word access
+            b.as_mut()[num_words - 1] |= !(usize::MAX >> padding_bits);
+        }
+        b.sanitize();
+        assert_eq!(b.count_ones(), expected);
+        let raw_ones: u32 = b.as_ref()[..num_words].iter().map(|w| w.count_ones()).sum();
+        assert_eq!(raw_ones as usize, expected);
+    }
+
+    // pop and shrinking resize clear the bits left beyond the length
+    let mut b = BitVec::with_value(100, true);
+    b.pop();
+    b.resize(70, true);
+    assert_eq!(b.as_ref()[1], (1 << 6) - 1);
+    assert_eq!(b.count_ones(), 70);
+}
+
+#[test]
+fn test_build_select_index() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+        for density in [0.001, 0.1, 0.5, 0.9, 1.0] {
+            let b: BitVec = (0..len).map(|_| rng.gen_bool(density)).collect();
+            let ones = b.iter_ones().collect::<Vec<_>>();
+            for log2_ones_per_sample in [0, 1, 3, 8, 20, 63] {
+                let select = b.build_select_index(log2_ones_per_sample);
+                assert_eq!(select.num_ones(), ones.len());
+                for (rank, &pos) in ones.iter().enumerate() {
+                    assert_eq!(select.select(rank), Some(pos));
+                }
+                assert_eq!(select.select(ones.len()), None);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_build_select_index_too_large() {
+    bit_vec![0, 1, 0, 1].build_select_index(usize::BITS as usize);
+}
@@ -281,3 +281,752 @@ fn test_convenience_methods() {
         assert_eq!(ef.succ(i * 10).unwrap(), (i, i * 10));
     }
 }
+
+#[test]
+fn test_sample() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [(1, 10), (100, 1000), (1000, 100), (1000, 1 << 20)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq();
+        for step in [1, 2, 3, 7, 64, 1000, 2000] {
+            let sample = ef.sample(step);
+            assert_eq!(sample.len(), n.div_ceil(step));
+            for (i, &v) in sample.iter().enumerate() {
+                assert_eq!(v, ef.get(i * step));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sample_zero_step() {
+    let mut efb = EliasFanoBuilder::new(1, 10);
+    efb.push(5);
+    let ef = efb.build_with_seq();
+    ef.sample(0);
+}
+
+#[test]
+fn test_iter_with_gaps() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [(1, 10), (100, 1000), (100, 100), (1000, 100)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq();
+
+        let mut value = 0;
+        let mut count = 0;
+        for (i, (v, gap)) in ef.iter_with_gaps().enumerate() {
+            value += gap;
+            assert_eq!(value, v);
+            assert_eq!(value, ef.get(i));
+            count += 1;
+        }
+        assert_eq!(count, n);
+
+        let max_gap = values
+            .iter()
+            .scan(0, |prev, &v| Some(v - std::mem::replace(prev, v)))
+            .max();
+        assert_eq!(ef.iter_with_gaps().map(|(_, gap)| gap).max(), max_gap);
+    }
+}
+
+#[test]
+fn test_accessors() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [(1, 10), (100, 1000), (100, 100), (1000, 100)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq();
+
+        assert_eq!(ef.u(), u);
+        assert_eq!(ef.low_bits().bit_width(), ef.l());
+        assert_eq!(ef.low_bits().len(), n);
+        assert_eq!(ef.high_bits().count_ones(), n);
+        for i in 0..n {
+            let (high, low) = ef.decompose(i);
+            assert!(low < 1 << ef.l());
+            assert_eq!((high << ef.l()) | low, ef.get(i));
+        }
+    }
+}
+
+#[test]
+fn test_select_complement() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (1, 1),
+        (1, 10),
+        (10, 10),
+        (10, 11),
+        (10, 100),
+        (50, 60),
+        (100, 10000),
+    ] {
+        for _ in 0..10 {
+            let mut values = rand::seq::index::sample(&mut rng, u + 1, n).into_vec();
+            values.sort();
+            let mut efb = EliasFanoBuilder::new(n, u);
+            efb.extend(values.iter().copied());
+            let ef = efb.build_with_seq();
+
+            let complement = (0..u)
+                .filter(|x| values.binary_search(x).is_err())
+                .collect::<Vec<_>>();
+            for (k, &hole) in complement.iter().enumerate() {
+                assert_eq!(ef.select_complement(k), Some(hole));
+            }
+            assert_eq!(ef.select_complement(complement.len()), None);
+            assert_eq!(ef.select_complement(usize::MAX), None);
+        }
+    }
+}
+
+#[test]
+fn test_zero_low_bits() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [(1, 0), (10, 10), (100, 199), (1000, 100), (1000, 1)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        values.sort();
+
+        let mut efb = EliasFanoBuilder::new(n, u);
+        for &value in &values {
+            efb.push(value);
+        }
+        let ef = efb.build_with_seq();
+        assert_eq!(ef.l(), 0);
+        assert_eq!(ef.low_bits().bit_width(), 0);
+        // The low bits are all zero, so values are given by the high bits only
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(unsafe { ef.low_bits().get_unchecked(i) }, 0);
+            assert_eq!(ef.get(i), value);
+        }
+        assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq_and_dict();
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), value);
+        }
+        // With repeated values the returned index is implementation-dependent
+        for value in 0..=u {
+            let succ = values.iter().copied().find(|&x| x >= value);
+            let res = ef.succ(value);
+            assert_eq!(res.map(|(_, x)| x), succ);
+            if let Some((i, x)) = res {
+                assert_eq!(values[i], x);
+            }
+            let pred = values.iter().copied().rev().find(|&x| x <= value);
+            let res = ef.pred(value);
+            assert_eq!(res.map(|(_, x)| x), pred);
+            if let Some((i, x)) = res {
+                assert_eq!(values[i], x);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_from_lender() -> Result<()> {
+    use sux::utils::LineLender;
+    let values = std::fs::read_to_string("tests/data/ints.100")?
+        .lines()
+        .map(|line| line.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+    let u = *values.last().unwrap();
+
+    let ef = EliasFano::from_lender(
+        LineLender::from_path("tests/data/ints.100")?,
+        values.len(),
+        u,
+    )?;
+    assert_eq!(ef.len(), values.len());
+    assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+    let ef = unsafe { ef.map_high_bits(SelectAdaptConst::<_, _, 12, 3>::new) };
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(ef.get(i), value);
+    }
+
+    let from_lender = |s: &'static str, n, u| {
+        EliasFano::from_lender(LineLender::new(std::io::Cursor::new(s)), n, u)
+    };
+
+    // Whitespace is ignored
+    assert_eq!(
+        from_lender(" 1\n2 \n\t3\n", 3, 3)?
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    // Malformed line
+    let err = from_lender("1\n2\nthree\n4\n", 4, 10).unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{}", err);
+    // Non-monotone values
+    assert!(from_lender("1\n3\n2\n", 3, 10).is_err());
+    // Value too large
+    assert!(from_lender("1\n2\n11\n", 3, 10).is_err());
+    // Too many or too few values
+    assert!(from_lender("1\n2\n3\n", 2, 10).is_err());
+    assert!(from_lender("1\n2\n3\n", 4, 10).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_symmetric_difference() {
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, m, u) in [
+        (0, 0, 10),
+        (0, 10, 100),
+        (100, 1000, 1000),
+        (1000, 100, 100),
+        (500, 500, 600),
+    ] {
+        let a = (0..n).map(|_| rng.gen_range(0..u)).collect::<BTreeSet<_>>();
+        let b = (0..m).map(|_| rng.gen_range(0..u)).collect::<BTreeSet<_>>();
+        let ef_a: EliasFano = a.iter().copied().collect::<Vec<_>>().into();
+        let ef_b: EliasFano = b.iter().copied().collect::<Vec<_>>().into();
+
+        let expected = a.symmetric_difference(&b).copied().collect::<BTreeSet<_>>();
+        let diff = EliasFano::symmetric_difference(&ef_a, &ef_b).collect::<Vec<_>>();
+        assert_eq!(diff, expected.into_iter().collect::<Vec<_>>());
+        assert_eq!(
+            EliasFano::symmetric_difference(&ef_b, &ef_a).collect::<Vec<_>>(),
+            diff
+        );
+        assert_eq!(EliasFano::symmetric_difference(&ef_a, &ef_a).count(), 0);
+    }
+
+    // Repeated values are treated as multisets
+    for (n, m, u) in [(100, 100, 10), (1000, 10, 50)] {
+        let mut a = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        let mut b = (0..m).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        a.sort();
+        b.sort();
+        let mut counts = BTreeMap::<usize, isize>::new();
+        a.iter().for_each(|&x| *counts.entry(x).or_default() += 1);
+        b.iter().for_each(|&x| *counts.entry(x).or_default() -= 1);
+        let expected = counts
+            .into_iter()
+            .flat_map(|(x, c)| std::iter::repeat_n(x, c.unsigned_abs()))
+            .collect::<Vec<_>>();
+
+        let ef_a: EliasFano = a.into();
+        let ef_b: EliasFano = b.into();
+        assert_eq!(
+            EliasFano::symmetric_difference(&ef_a, &ef_b).collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_map_monotone() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 10),
+        (100, 1000),
+        (1000, 100),
+        (10_000, 1 << 40),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let ef: EliasFano = values.clone().into();
+        let new_u = ef.u() * 2 + 5;
+        let ef = ef.map_monotone(|x| x * 2 + 5, new_u);
+
+        let mut efb = EliasFanoBuilder::new(n, new_u);
+        efb.extend(values.iter().map(|x| x * 2 + 5));
+        let expected = efb.build();
+
+        assert_eq!(ef.len(), expected.len());
+        assert_eq!(ef.u(), expected.u());
+        assert_eq!(ef.l(), expected.l());
+        assert_eq!(ef.high_bits(), expected.high_bits());
+        assert_eq!(
+            ef.iter().collect::<Vec<_>>(),
+            values.iter().map(|x| x * 2 + 5).collect::<Vec<_>>()
+        );
+
+        // Adding a selection structure works as usual
+        let ef = unsafe { ef.map_high_bits(SelectAdaptConst::<_, _, 12, 3>::new) };
+        for (i, &x) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), x * 2 + 5);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "not monotone")]
+fn test_map_monotone_not_monotone() {
+    let ef: EliasFano = vec![0, 2, 8, 10].into();
+    ef.map_monotone(|x| 10 - x, 10);
+}
+
+#[test]
+#[should_panic(expected = "Value too large")]
+fn test_map_monotone_too_large() {
+    let ef: EliasFano = vec![0, 2, 8, 10].into();
+    ef.map_monotone(|x| x * 2, 10);
+}
+
+#[test]
+fn test_enumerate_values() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 10),
+        (100, 1000),
+        (1000, 100),
+        (10_000, 1 << 40),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq();
+
+        assert_eq!(
+            ef.enumerate_values().collect::<Vec<_>>(),
+            (0..ef.len()).map(|i| (i, ef.get(i))).collect::<Vec<_>>()
+        );
+        assert_eq!(ef.enumerate_values().count(), n);
+
+        // Default implementation
+        let seq = SliceSeq::new(&values);
+        assert_eq!(
+            seq.enumerate_values().collect::<Vec<_>>(),
+            ef.enumerate_values().collect::<Vec<_>>()
+        );
+
+        // IndexedSeq is still usable as a trait object
+        let dyn_seq: &dyn IndexedSeq<Input = usize, Output = usize> = &ef;
+        assert_eq!(dyn_seq.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(dyn_seq.get(i), value);
+        }
+    }
+}
+
+#[test]
+fn test_build_indexed() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 10),
+        (4096, 4096),
+        (4097, 100),
+        (10_000, 1000),
+        (100_000, 1_000_000),
+        (100_000, 1 << 40),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let indexed = efb.build_indexed();
+
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq();
+
+        for (i, &v) in values.iter().enumerate() {
+            assert_eq!(indexed.get(i), v);
+            assert_eq!(indexed.get(i), ef.get(i));
+        }
+        // The selection structures are identical
+        assert_eq!(
+            indexed.high_bits().clone().into_raw_parts(),
+            ef.high_bits().clone().into_raw_parts()
+        );
+    }
+}
+
+#[test]
+fn test_neighbors() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 10),
+        (10, 10),
+        (100, 1000),
+        (1000, 100),
+        (1000, 1_000_000),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq_and_dict();
+
+        let queries = (0..2000)
+            .map(|_| rng.gen_range(0..u + 10))
+            .chain(values.iter().copied())
+            .chain([0, u, usize::MAX]);
+        for value in queries {
+            let (pred, succ) = ef.neighbors(value);
+            let pos = values.partition_point(|&x| x <= value);
+            let expected_pred = pos.checked_sub(1).map(|i| values[i]);
+            let expected_succ = values[values.partition_point(|&x| x < value)..]
+                .first()
+                .copied();
+            assert_eq!(pred, expected_pred, "value = {}", value);
+            assert_eq!(succ, expected_succ, "value = {}", value);
+            if values.binary_search(&value).is_ok() {
+                assert_eq!((pred, succ), (Some(value), Some(value)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_iter_range_values() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 10),
+        (10, 10),
+        (100, 1000),
+        (1000, 100),
+        (1000, 1_000_000),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq_and_dict();
+
+        for _ in 0..1000 {
+            let lo = rng.gen_range(0..u + 10);
+            let hi = rng.gen_range(0..u + 10);
+            let expected = values
+                .iter()
+                .copied()
+                .filter(|&x| lo <= x && x < hi)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                ef.iter_range_values(lo, hi).collect::<Vec<_>>(),
+                expected,
+                "range = [{}..{})",
+                lo,
+                hi
+            );
+        }
+        // Full range and ranges beyond the upper bound
+        assert_eq!(
+            ef.iter_range_values(0, usize::MAX).collect::<Vec<_>>(),
+            values
+        );
+        assert_eq!(ef.iter_range_values(u, usize::MAX).count(), 0);
+        assert_eq!(ef.iter_range_values(5, 5).count(), 0);
+    }
+}
+
+#[test]
+fn test_high_select_zero() {
+    // Upper parts are 0, 0, 2, 5 with l = 2: the high bits are 110 0 10 0 0 10
+    let mut efb = EliasFanoBuilder::new(4, 23);
+    efb.extend([0, 3, 8, 21]);
+    let ef = efb.build_with_dict();
+    assert_eq!(ef.l(), 2);
+    let zeros = (0..6).map(|k| ef.high_select_zero(k)).collect::<Vec<_>>();
+    assert_eq!(
+        zeros,
+        vec![Some(2), Some(3), Some(5), Some(6), Some(7), Some(9)]
+    );
+    assert_eq!(ef.high_select_zero(6), None);
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [(0, 10), (1, 10), (10, 10), (100, 1000), (1000, 100)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq_and_dict();
+        let l = ef.l();
+        for k in 0..=u >> l {
+            // The k-th zero follows the values whose upper part is at most k
+            let before = values.iter().filter(|&&x| x >> l <= k).count();
+            assert_eq!(ef.high_select_zero(k), Some(k + before));
+        }
+        assert_eq!(ef.high_select_zero((u >> l) + 1), None);
+    }
+}
+
+#[test]
+fn test_with_l() {
+    use mem_dbg::*;
+    let mut rng = SmallRng::seed_from_u64(0);
+    let (n, u) = (1000, 1_000_000);
+    let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+    values.sort();
+
+    let default_l = EliasFanoBuilder::new(n, u).build().l();
+    let mut sizes = vec![];
+    for l in [0, 3, default_l, default_l + 3, 20] {
+        let mut efb = EliasFanoBuilder::with_l(n, u, l);
+        efb.extend(values.iter().copied());
+        let ef = efb.build_with_seq_and_dict();
+        assert_eq!(ef.l(), l);
+        assert_eq!(ef.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), value);
+        }
+        assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+        for &value in values.iter().step_by(10) {
+            assert_eq!(ef.succ(value).map(|(_, x)| x), Some(value));
+        }
+        sizes.push(ef.mem_size(SizeFlags::default()));
+    }
+    // Different numbers of lower bits change the space occupancy
+    let default_size = sizes[2];
+    assert!(sizes[0] > default_size);
+    assert!(sizes[4] > default_size);
+}
+
+#[test]
+#[should_panic]
+fn test_with_l_too_large() {
+    EliasFanoBuilder::with_l(10, 100, 8);
+}
+
+#[test]
+fn test_new_extreme_l() {
+    let ef = EliasFanoBuilder::new(0, 100).build();
+    assert_eq!(ef.l(), 0);
+    assert_eq!(ef.len(), 0);
+
+    let mut efb = EliasFanoBuilder::new(1, usize::MAX);
+    efb.push(usize::MAX);
+    let ef = efb.build_with_seq();
+    assert_eq!(ef.l(), usize::BITS as usize - 1);
+    assert_eq!(ef.get(0), usize::MAX);
+}
+
+#[test]
+fn test_predecessor_join() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n_a, n_b, u) in [
+        (0, 10, 100),
+        (10, 0, 100),
+        (1, 1, 10),
+        (100, 100, 1000),
+        (1000, 10, 100_000),
+        (10, 1000, 100_000),
+        (1000, 1000, 100),
+    ] {
+        let mut a = (0..n_a).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        a.sort();
+        // Values of b are at least u / 2, so many values of a have no
+        // predecessor
+        let mut b = (0..n_b)
+            .map(|_| rng.gen_range(u / 2..=u))
+            .collect::<Vec<_>>();
+        b.sort();
+
+        let mut efb = EliasFanoBuilder::new(n_a, u);
+        efb.extend(a.iter().copied());
+        let ef_a = efb.build_with_seq_and_dict();
+        let mut efb = EliasFanoBuilder::new(n_b, u);
+        efb.extend(b.iter().copied());
+        let ef_b = efb.build_with_seq_and_dict();
+
+        let mut out = vec![Some(0); 3];
+        EliasFano::predecessor_join(&ef_a, &ef_b, &mut out);
+        assert_eq!(out.len(), n_a);
+        for (&x, &index) in a.iter().zip(&out) {
+            // The index of the last value smaller than or equal to x
+            let expected = b.partition_point(|&y| y <= x).checked_sub(1);
+            assert_eq!(index, expected);
+            assert_eq!(index.map(|i| b[i]), ef_b.pred(x).map(|(_, y)| y));
+        }
+    }
+}
+
+#[test]
+fn test_merge_iter() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n_a, n_b, u) in [
+        (0, 0, 10),
+        (0, 10, 100),
+        (10, 0, 100),
+        (100, 100, 1000),
+        (1000, 10, 100_000),
+        (10, 1000, 100_000),
+        (1000, 1000, 100),
+    ] {
+        let mut a = (0..n_a).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        a.sort();
+        let mut b = (0..n_b).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        b.sort();
+
+        let mut efb = EliasFanoBuilder::new(n_a, u);
+        efb.extend(a.iter().copied());
+        let ef = efb.build();
+
+        let merged = ef.merge_iter(b.iter().copied()).collect::<Vec<_>>();
+        let mut expected = a.clone();
+        expected.extend_from_slice(&b);
+        expected.sort();
+        assert_eq!(merged, expected);
+    }
+}
+
+#[test]
+fn test_elias_fano_as_select() -> Result<()> {
+    use sux::dict::elias_fano::EliasFanoBits;
+    use sux::rank_sel::SelectAdaptConst;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (len, density) in [
+        (10, 0.5),
+        (1, 1.0),
+        (100, 0.0),
+        (1000, 0.1),
+        (1000, 0.5),
+        (10000, 0.9),
+    ] {
+        let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+        let ones = bits.iter_ones().collect::<Vec<_>>();
+        let zeros = (0..len).filter(|&i| !bits[i]).collect::<Vec<_>>();
+
+        // The view has length u + 1
+        let mut efb = EliasFanoBuilder::new(ones.len(), len - 1);
+        efb.extend(ones.iter().copied());
+        let ef = EliasFanoBits::new(efb.build_with_seq())?;
+        let sel = SelectAdaptConst::<_, _>::new(AddNumBits::from(bits));
+
+        assert_eq!(BitLength::len(&ef), len);
+        assert_eq!(ef.num_ones(), sel.num_ones());
+        assert_eq!(ef.num_zeros(), zeros.len());
+        for rank in 0..=ones.len() {
+            assert_eq!(ef.select(rank), sel.select(rank));
+        }
+        for (rank, &pos) in zeros.iter().enumerate() {
+            assert_eq!(ef.select_zero(rank), Some(pos));
+        }
+        assert_eq!(ef.select_zero(zeros.len()), None);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_elias_fano_as_select_upper_bound() -> Result<()> {
+    use sux::dict::elias_fano::EliasFanoBits;
+    use sux::rank_sel::SelectAdaptConst;
+
+    // The upper bound is the maximum value, which is thus a one
+    for values in [vec![0, 1, 2], vec![2], vec![1, 4, 9], vec![0]] {
+        let ef: EliasFano = values.clone().into();
+        let ef = EliasFanoBits::new(unsafe { ef.map_high_bits(SelectAdaptConst::<_, _>::new) })?;
+        let u = *values.last().unwrap();
+        let zeros = (0..=u).filter(|x| !values.contains(x)).collect::<Vec<_>>();
+
+        assert_eq!(BitLength::len(&ef), u + 1);
+        assert_eq!(ef.num_ones(), values.len());
+        assert_eq!(ef.num_zeros(), zeros.len());
+        for (rank, &value) in values.iter().enumerate() {
+            assert_eq!(ef.select(rank), Some(value));
+        }
+        assert_eq!(ef.select(values.len()), None);
+        for (rank, &pos) in zeros.iter().enumerate() {
+            assert_eq!(ef.select_zero(rank), Some(pos));
+        }
+        assert_eq!(ef.select_zero(zeros.len()), None);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_elias_fano_as_select_duplicates() {
+    use sux::dict::elias_fano::EliasFanoBits;
+    use sux::rank_sel::SelectAdaptConst;
+
+    for values in [
+        vec![0, 0],
+        vec![1, 1, 1],
+        vec![0, 1, 2, 2],
+        vec![3, 5, 5, 7],
+    ] {
+        let ef: EliasFano = values.into();
+        let ef = unsafe { ef.map_high_bits(SelectAdaptConst::<_, _>::new) };
+        assert!(EliasFanoBits::new(ef).is_err());
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Index out of bounds")]
+fn test_elias_fano_concurrent_out_of_bounds() {
+    use sux::dict::elias_fano::EliasFanoConcurrentBuilder;
+    let efcb = EliasFanoConcurrentBuilder::new(10, 100);
+    unsafe { efcb.set(10, 5) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "upper bound")]
+fn test_elias_fano_concurrent_value_too_large() {
+    use sux::dict::elias_fano::EliasFanoConcurrentBuilder;
+    let efcb = EliasFanoConcurrentBuilder::new(10, 100);
+    unsafe { efcb.set(0, 101) };
+}
+
+#[test]
+fn test_delta_vbyte() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, u) in [
+        (0, 10),
+        (1, 0),
+        (10, 1000),
+        (1000, 1000),
+        (1000, 1 << 20),
+        (100, usize::MAX),
+    ] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..=u)).collect::<Vec<_>>();
+        values.sort();
+        let mut efb = EliasFanoBuilder::new(n, u);
+        efb.extend(values.iter().copied());
+        let ef = efb.build();
+
+        let bytes = ef.to_delta_vbyte();
+        let decoded = EliasFano::from_delta_vbyte(&bytes, n, u)?;
+        assert_eq!(decoded.len(), n);
+        assert_eq!(decoded.u(), u);
+        assert!(decoded.iter().eq(values.iter().copied()));
+
+        if n != 0 {
+            // Wrong number of values
+            assert!(EliasFano::from_delta_vbyte(&bytes, n - 1, u).is_err());
+            assert!(EliasFano::from_delta_vbyte(&bytes, n + 1, u).is_err());
+            assert!(EliasFano::from_delta_vbyte(&bytes[..bytes.len() - 1], n, u).is_err());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_delta_vbyte_size() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    // Dense and moderately sparse monotone data
+    for (n, u) in [(10_000, 100_000), (10_000, 10_000_000)] {
+        let mut values = (0..n).map(|_| rng.gen_range(0..u)).collect::<Vec<_>>();
+        values.sort();
+        let ef: EliasFano = values.into();
+        assert!(ef.to_delta_vbyte().len() < n * 8);
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::dict::elias_fano_of::{EliasFanoOf, EliasFanoOfBuilder, ValueRangeError};
+use sux::prelude::*;
+
+#[test]
+fn test_elias_fano_of_u32() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for n in [1, 10, 100, 1000, 10000] {
+        let mut values = (0..n).map(|_| rng.gen::<u32>()).collect::<Vec<_>>();
+        values.sort();
+        let u = *values.last().unwrap();
+
+        let mut efb = EliasFanoOfBuilder::<u32>::new(n, u)?;
+        for &value in &values {
+            efb.push(value)?;
+        }
+        let ef = efb.build();
+
+        assert_eq!(ef.len(), n);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i), value);
+        }
+        assert_eq!(ef.iter().collect::<Vec<_>>(), values);
+        for &value in &values {
+            let index = ef.index_of(value).unwrap();
+            assert_eq!(ef.get(index), value);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_elias_fano_of_errors() -> Result<()> {
+    // Negative values cannot be represented as a usize
+    assert!(matches!(
+        EliasFanoOfBuilder::<i32>::new(2, -1),
+        Err(ValueRangeError::NotUsize { .. })
+    ));
+    let mut efb = EliasFanoOfBuilder::<i32>::new(2, 100)?;
+    assert!(matches!(
+        efb.push(-5),
+        Err(ValueRangeError::NotUsize { .. })
+    ));
+    efb.push(5)?;
+    efb.push(100)?;
+    let ef = efb.build();
+    assert_eq!(ef.get(1), 100);
+    // Values that are not usize cannot be found
+    assert_eq!(ef.index_of(-5_i32), None);
+
+    // The upper bound must fit the value type
+    let mut efb = EliasFanoBuilder::new(2, 1 << 20);
+    efb.push(0);
+    efb.push(1 << 20);
+    let ef = efb.build_with_seq();
+    let err = EliasFanoOf::<u16, _>::try_from(ef.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ValueRangeError::UpperBoundTooLarge {
+            u: 1 << 20,
+            value_type: "u16"
+        }
+    );
+    assert!(!err.to_string().is_empty());
+
+    let ef = EliasFanoOf::<u32, _>::try_from(ef)?;
+    assert_eq!(ef.get(1), 1 << 20);
+    Ok(())
+}
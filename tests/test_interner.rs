@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::io::prelude::*;
+use std::io::BufReader;
+use sux::dict::Interner;
+
+#[test]
+fn test_interner() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    // A shuffled stream in which every word appears three times
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut stream = words
+        .iter()
+        .chain(&words)
+        .chain(&words)
+        .cloned()
+        .collect::<Vec<_>>();
+    stream.shuffle(&mut rng);
+
+    for k in [1, 4, 16] {
+        let interner = Interner::with_block_size(&stream, k);
+        assert_eq!(interner.len(), words.len());
+
+        // Identifiers are assigned in order of first appearance
+        let mut next_id = 0;
+        let mut seen = std::collections::HashSet::new();
+        for s in &stream {
+            let id = interner.id_of(s).unwrap();
+            if seen.insert(s) {
+                assert_eq!(id, next_id);
+                next_id += 1;
+            }
+            assert_eq!(interner.string_of(id), *s);
+        }
+
+        for id in 0..interner.len() as u32 {
+            assert_eq!(interner.id_of(interner.string_of(id)), Some(id));
+        }
+        assert_eq!(interner.id_of("IT'S HIGHLY IMPROBABLE"), None);
+    }
+
+    let empty = Interner::new(Vec::<String>::new());
+    assert!(empty.is_empty());
+    assert_eq!(empty.id_of("a"), None);
+    Ok(())
+}
+
+#[test]
+fn test_interner_nul() {
+    let stream = ["a\0b", "a", "a\0", "", "a\0b", "\0", "a"];
+    let interner = Interner::with_block_size(stream, 2);
+    assert_eq!(interner.len(), 5);
+    for (id, s) in ["a\0b", "a", "a\0", "", "\0"].iter().enumerate() {
+        assert_eq!(interner.id_of(s), Some(id as u32));
+        assert_eq!(interner.string_of(id as u32), *s);
+    }
+    assert_eq!(interner.id_of("a\0c"), None);
+    assert_eq!(interner.id_of("b"), None);
+}
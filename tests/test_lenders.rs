@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use lender::*;
+use std::io::Cursor;
+use sux::utils::{DelimLender, DelimStrLender, RewindableIoLender};
+
+fn check_delim(data: &[u8], delim: u8) -> Result<()> {
+    // Reference: split, dropping the empty record after a final delimiter
+    let mut expected = data.split(|&c| c == delim).collect::<Vec<_>>();
+    if data.is_empty() || data.last() == Some(&delim) {
+        expected.pop();
+    }
+
+    let mut lender = DelimLender::new(Cursor::new(data), delim);
+    for _ in 0..2 {
+        let mut records = vec![];
+        while let Some(record) = lender.next() {
+            records.push(record?.to_vec());
+        }
+        assert_eq!(records, expected);
+        lender = lender.rewind()?;
+    }
+
+    let mut lender = DelimStrLender::new(Cursor::new(data), delim);
+    let mut records = vec![];
+    while let Some(record) = lender.next() {
+        records.push(record?.to_owned());
+    }
+    assert_eq!(
+        records,
+        expected
+            .iter()
+            .map(|r| std::str::from_utf8(r).unwrap())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_delim_lender() -> Result<()> {
+    check_delim(b"./a\0./b c\0./d\ne\0", 0)?;
+    check_delim(b"./a\0./b c\0\0./d\ne", 0)?;
+    check_delim(b"a,b,,c,", b',')?;
+    check_delim(b"a,b,,c", b',')?;
+    check_delim(b",", b',')?;
+    check_delim(b"", b',')?;
+    Ok(())
+}
+
+#[test]
+fn test_delim_str_lender_invalid_utf8() {
+    let mut lender = DelimStrLender::new(Cursor::new(b"ok,\xff\xfe,ok"), b',');
+    assert_eq!(lender.next().unwrap().unwrap(), "ok");
+    assert_eq!(
+        lender.next().unwrap().unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+    assert_eq!(lender.next().unwrap().unwrap(), "ok");
+    assert!(lender.next().is_none());
+}
+
+#[test]
+fn test_read_lines_chunked() -> Result<()> {
+    use std::io::BufRead;
+    use sux::utils::read_lines_chunked;
+
+    let path = "tests/data/wordlist.10000";
+    let lines = std::io::BufReader::new(std::fs::File::open(path)?)
+        .lines()
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    for chunk in [1, 7, 100, 10_000, 20_000] {
+        let chunks = read_lines_chunked(std::io::BufReader::new(std::fs::File::open(path)?), chunk)
+            .collect::<std::io::Result<Vec<_>>>()?;
+        assert_eq!(chunks.len(), lines.len().div_ceil(chunk));
+        assert!(chunks.iter().all(|c| !c.is_empty() && c.len() <= chunk));
+        assert_eq!(chunks.concat(), lines);
+    }
+
+    // Chunks can be moved to other threads
+    let handles = read_lines_chunked(std::io::BufReader::new(std::fs::File::open(path)?), 100)
+        .map(|chunk| {
+            let chunk = chunk.unwrap();
+            std::thread::spawn(move || chunk.iter().map(|s| s.len()).sum::<usize>())
+        })
+        .collect::<Vec<_>>();
+    let total = handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .sum::<usize>();
+    assert_eq!(total, lines.iter().map(|s| s.len()).sum::<usize>());
+
+    // Empty input
+    assert_eq!(read_lines_chunked(Cursor::new(""), 10).count(), 0);
+
+    // Errors end the iteration
+    let mut iter = read_lines_chunked(Cursor::new(b"a\nb\n\xff\nc\n".as_slice()), 3);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+    Ok(())
+}
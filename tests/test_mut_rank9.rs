@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sux::prelude::*;
+
+#[test]
+fn test_mut_rank9() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 63, 64, 65, 511, 512, 513, 1000, 10000] {
+        let mut bits = (0..len).map(|_| rng.gen_bool(0.5)).collect::<BitVec>();
+        let mut rank9 = MutRank9::new(bits.clone());
+
+        for _ in 0..1000 {
+            let pos = rng.gen_range(0..len);
+            let value = rng.gen_bool(0.5);
+            bits.set(pos, value);
+            rank9.set(pos, value);
+
+            for _ in 0..10 {
+                let pos = rng.gen_range(0..=len);
+                let expected = (0..pos).filter(|&i| bits[i]).count();
+                assert_eq!(rank9.rank(pos), expected);
+                assert_eq!(rank9.rank_zero(pos), pos - expected);
+            }
+            assert_eq!(rank9.num_ones(), bits.count_ones());
+        }
+
+        // The counters must be identical to those of a static structure
+        let static_rank9 = Rank9::new(bits.clone());
+        let rank9 = rank9.into_rank9();
+        for pos in 0..=len {
+            assert_eq!(rank9.rank(pos), static_rank9.rank(pos));
+        }
+        assert_eq!(rank9.into_inner(), bits);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_mut_rank9_out_of_bounds() {
+    let mut rank9 = MutRank9::new(bit_vec![0, 1, 0]);
+    rank9.set(3, true);
+}
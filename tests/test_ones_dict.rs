@@ -0,0 +1,33 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::dict::OnesDict;
+use sux::prelude::*;
+
+#[test]
+fn test_ones_dict() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 100, 1000, 10000, 100000] {
+        for density in [0.1, 0.5, 0.9] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let ones = bits.iter_ones().collect::<Vec<_>>();
+            let count_ones = bits.count_ones();
+            let dict = OnesDict::new(bits.clone());
+
+            assert_eq!(dict.len(), count_ones);
+            for (i, &pos) in ones.iter().enumerate() {
+                assert_eq!(dict.get(i), pos);
+                assert_eq!(dict.index_of(pos), Some(i));
+            }
+            for pos in 0..len + 10 {
+                assert_eq!(dict.contains(pos), pos < len && bits[pos]);
+            }
+        }
+    }
+}
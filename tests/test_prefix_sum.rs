@@ -0,0 +1,55 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+#[test]
+fn test_prefix_sum() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (n, max) in [
+        (0, 10),
+        (1, 10),
+        (10, 1),
+        (100, 10),
+        (1000, 1000),
+        (1000, 3),
+    ] {
+        let values = (0..n)
+            .map(|_| rng.gen_range(0..max))
+            .collect::<Vec<usize>>();
+        let ps = PrefixSum::new(&values);
+
+        assert_eq!(ps.len(), n);
+        assert_eq!(ps.is_empty(), n == 0);
+        let total = values.iter().sum::<usize>();
+        assert_eq!(ps.total(), total);
+
+        let mut sums = vec![0];
+        for (i, &x) in values.iter().enumerate() {
+            assert_eq!(ps.element(i), x);
+            sums.push(sums[i] + x);
+        }
+        for (i, &s) in sums.iter().enumerate() {
+            assert_eq!(ps.prefix_sum(i), s);
+        }
+
+        for value in 0..=total + 1 {
+            // Linear reference
+            let expected = sums.iter().position(|&s| s >= value);
+            assert_eq!(ps.search(value), expected, "value: {}", value);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_prefix_sum_element_out_of_bounds() {
+    let ps = PrefixSum::new([1, 2, 3]);
+    ps.element(3);
+}
@@ -80,3 +80,68 @@ fn test_empty() {
     let inner = rank9.into_inner();
     assert_eq!(inner.len(), 0);
 }
+
+#[test]
+fn test_blocks() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0_usize, 1, 511, 512, 513, 1000, 10_000, 100_000] {
+        let bits = (0..len).map(|_| rng.gen_bool(0.5)).collect::<BitVec>();
+        let rank9: Rank9 = Rank9::new(bits.clone());
+
+        let num_blocks = len.div_ceil(512);
+        assert_eq!(rank9.blocks().len(), num_blocks + 1);
+        assert_eq!(rank9.blocks().last().unwrap().absolute(), bits.count_ones());
+        assert_eq!(rank9.block_rank(num_blocks), rank9.num_ones());
+
+        for pos in 0..len {
+            let block = pos / 512;
+            assert!(rank9.block_rank(block) <= rank9.rank(pos));
+            // The block rank and the relative counter give the rank of the word
+            let word_rank = rank9.block_rank(block) + rank9.blocks()[block].rel((pos / 64) % 8);
+            assert_eq!(word_rank, rank9.rank(pos - pos % 64));
+            if pos % 512 == 0 {
+                assert_eq!(rank9.block_rank(block), rank9.rank(pos));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_new_in() {
+    use sux::rank_sel::BlockCounters;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 511, 512, 513, 1000, 100_000] {
+        for density in [0.0, 0.1, 0.5, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let rank9 = Rank9::new(bits.clone());
+
+            // Garbage in the buffer must be overwritten
+            let mut garbage = BlockCounters::default();
+            garbage.set_rel(3, 100);
+            let mut counts = vec![garbage; Rank9::expected_counts(&bits)];
+            let rank9_in = Rank9::new_in(&bits, counts.as_mut_slice());
+
+            assert_eq!(rank9_in.blocks().len(), rank9.blocks().len());
+            for (a, b) in rank9_in.blocks().iter().zip(rank9.blocks()) {
+                assert_eq!(a.absolute(), b.absolute());
+                assert_eq!(
+                    (0..8).map(|w| a.rel(w)).collect::<Vec<_>>(),
+                    (0..8).map(|w| b.rel(w)).collect::<Vec<_>>()
+                );
+            }
+            assert_eq!(rank9_in.num_ones(), rank9.num_ones());
+            for pos in 0..=len {
+                assert_eq!(rank9_in.rank(pos), rank9.rank(pos));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_new_in_wrong_len() {
+    let bits = bit_vec![0; 1000];
+    let mut counts = vec![sux::rank_sel::BlockCounters::default(); 2];
+    Rank9::new_in(&bits, counts.as_mut_slice());
+}
@@ -32,3 +32,442 @@ fn test_rank_sel_add_num_bits() {
     assert_eq!(b, bits);
     assert_eq!(c, 5);
 }
+
+#[test]
+fn test_rank_sel_add_num_bits_verify_count() {
+    let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0, 0, 1];
+    let a: AddNumBits<_> = bits.clone().into();
+    assert_eq!(a.cached_count(), 5);
+    assert_eq!(a.recount(), 5);
+    assert!(a.verify_count());
+
+    // Mutate the bits without updating the count
+    let (mut b, c) = a.into_raw_parts();
+    b.set(0, true);
+    b.set(1, false);
+    let a = unsafe { AddNumBits::from_raw_parts(b, c) };
+    // The number of ones is the same
+    assert!(a.verify_count());
+
+    let (mut b, c) = a.into_raw_parts();
+    b.set(2, true);
+    let a = unsafe { AddNumBits::from_raw_parts(b, c) };
+    assert_eq!(a.cached_count(), 5);
+    assert_eq!(a.num_ones(), 5);
+    assert_eq!(a.recount(), 6);
+    assert!(!a.verify_count());
+}
+
+#[test]
+fn test_rank_sel_add_num_bits_with_count() {
+    let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0, 0, 1];
+    let a: AddNumBits<_> = bits.clone().into();
+    let b = unsafe { AddNumBits::with_count(bits, 5) };
+    assert_eq!(a.num_ones(), b.num_ones());
+    assert!(b.verify_count());
+    assert_eq!(a.into_raw_parts(), b.into_raw_parts());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn test_rank_sel_add_num_bits_with_wrong_count() {
+    let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0, 0, 1];
+    let _ = unsafe { AddNumBits::with_count(bits, 4) };
+}
+
+/// A bit vector counting the number of times its ones are counted.
+struct CountingBits {
+    bits: BitVec,
+    scans: std::cell::Cell<usize>,
+}
+
+impl BitLength for CountingBits {
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+impl BitCount for CountingBits {
+    fn count_ones(&self) -> usize {
+        self.scans.set(self.scans.get() + 1);
+        self.bits.count_ones()
+    }
+}
+
+#[test]
+fn test_rank_sel_add_lazy_num_bits() {
+    let bits: BitVec = (0..10_000).map(|i| i % 3 == 0).collect();
+    let expected = bits.count_ones();
+
+    let lazy = AddLazyNumBits::new(CountingBits {
+        bits: bits.clone(),
+        scans: std::cell::Cell::new(0),
+    });
+    // No scan at construction time
+    assert_eq!(lazy.cached_count(), None);
+    assert_eq!(lazy.into_inner().scans.get(), 0);
+
+    let lazy = AddLazyNumBits::new(CountingBits {
+        bits: bits.clone(),
+        scans: std::cell::Cell::new(0),
+    });
+    assert_eq!(lazy.num_ones(), expected);
+    assert_eq!(lazy.cached_count(), Some(expected));
+    assert_eq!(lazy.count_ones(), expected);
+    assert_eq!(lazy.num_ones(), expected);
+    assert_eq!(lazy.num_zeros(), 10_000 - expected);
+    // Only the first call scanned the bit vector
+    assert_eq!(lazy.into_inner().scans.get(), 1);
+
+    // Borrowed bits, as in the case of memory mapping
+    let (words, len) = bits.clone().into_raw_parts();
+    let borrowed = unsafe { BitVec::from_raw_parts(words.as_slice(), len) };
+    let lazy = AddLazyNumBits::new(borrowed);
+    let eager: AddNumBits<_> = unsafe { BitVec::from_raw_parts(words.as_slice(), len) }.into();
+    assert_eq!(lazy.num_ones(), eager.num_ones());
+    let converted = lazy.into_add_num_bits();
+    assert_eq!(converted.cached_count(), expected);
+
+    // Rank and selection structures can be built on top
+    let select = SelectAdapt::new(AddLazyNumBits::new(bits.clone()), 3);
+    assert_eq!(select.num_ones(), expected);
+    for i in 0..expected {
+        assert_eq!(select.select(i), Some(3 * i));
+    }
+    let rank9 = Rank9::new(AddLazyNumBits::new(bits));
+    assert_eq!(rank9.rank(3_000), 1_000);
+}
+
+#[test]
+fn test_rank_sel_try_get_bit() {
+    use std::sync::atomic::Ordering;
+
+    fn check<B: BitAccess + ?Sized>(b: &B, bits: &BitVec) {
+        assert_eq!(b.try_get_bit(b.len()), None);
+        assert_eq!(b.try_get_bit(usize::MAX), None);
+        for i in 0..b.len() {
+            assert_eq!(b.try_get_bit(i), Some(b[i]));
+            assert_eq!(b.try_get_bit(i), Some(bits[i]));
+        }
+    }
+
+    let bits = bit_vec![0, 1, 0, 1, 1, 0, 1, 0, 0, 1];
+    check(&bits, &bits);
+    let atomic: AtomicBitVec = bits.clone().into();
+    assert_eq!(
+        atomic.try_get_bit(1),
+        Some(atomic.get(1, Ordering::Relaxed))
+    );
+    check(&atomic, &bits);
+    let add_num_bits: AddNumBits<_> = bits.clone().into();
+    check(&add_num_bits, &bits);
+    check(&Rank9::new(bits.clone()), &bits);
+    check(&rank_small![0; bits.clone()], &bits);
+    check(&SelectAdapt::new(add_num_bits.clone(), 3), &bits);
+    check(&SelectAdaptConst::<_, _>::new(add_num_bits.clone()), &bits);
+    check(&SelectZeroAdapt::new(add_num_bits, 3), &bits);
+}
+
+#[test]
+fn test_rank_sel_auto() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use sux::rank_sel::auto::*;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 100, 1000, 10000] {
+        for density in [0.0, 0.001, 0.01, 0.1, 0.5, 0.9, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let ones = bits.iter_ones().collect::<Vec<_>>();
+            for target in [SpaceSpeed::Space, SpaceSpeed::Speed] {
+                let select = build_select(bits.clone(), target);
+                assert_eq!(select.len(), len);
+                assert_eq!(select.num_ones(), ones.len());
+                for (rank, &pos) in ones.iter().enumerate() {
+                    assert_eq!(select.select(rank), Some(pos));
+                }
+                assert_eq!(select.select(ones.len()), None);
+
+                let rank = build_rank(bits.clone(), target);
+                assert_eq!(rank.num_ones(), ones.len());
+                let mut expected = 0;
+                for pos in 0..len {
+                    assert_eq!(rank.rank(pos), expected);
+                    expected += bits[pos] as usize;
+                }
+                assert_eq!(rank.rank(len), ones.len());
+            }
+        }
+    }
+
+    // Extreme densities
+    for target in [SpaceSpeed::Space, SpaceSpeed::Speed] {
+        assert_eq!(select_kind(1 << 20, 1, target), SelectKind::EliasFano);
+        assert_eq!(select_kind(1 << 20, 1 << 14, target), SelectKind::EliasFano);
+        assert_ne!(select_kind(1 << 20, 0, target), SelectKind::EliasFano);
+        assert_ne!(select_kind(1 << 20, 1 << 20, target), SelectKind::EliasFano);
+    }
+    assert_eq!(
+        select_kind(1 << 20, 1 << 19, SpaceSpeed::Speed),
+        SelectKind::SelectAdaptConst
+    );
+    assert_eq!(
+        select_kind(1 << 20, 1 << 20, SpaceSpeed::Speed),
+        SelectKind::SelectAdapt(3)
+    );
+    assert_eq!(
+        select_kind(1 << 20, 1 << 19, SpaceSpeed::Space),
+        SelectKind::SelectAdapt(1)
+    );
+}
+
+#[test]
+fn test_rank_sel_any_rank_small() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use sux::rank_sel::auto::*;
+
+    let check = |rank: &AnyRankSmall, bits: &BitVec| {
+        let mut expected = 0;
+        for pos in 0..bits.len() {
+            assert_eq!(rank.rank(pos), expected, "params = {:?}", rank.params());
+            assert_eq!(rank.rank_zero(pos), pos - expected);
+            expected += bits[pos] as usize;
+        }
+        assert_eq!(rank.rank(bits.len()), expected);
+        assert_eq!(rank.num_ones(), expected);
+        assert_eq!(rank.len(), bits.len());
+    };
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 100, 1000, 100_000] {
+        for density in [0.0, 0.01, 0.5, 0.99, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let params = rank_small_params(len, density);
+            check(&AnyRankSmall::new(bits.clone(), params), &bits);
+            for params in [(2, 9), (1, 9), (1, 10), (1, 11), (3, 13)] {
+                let rank = AnyRankSmall::new(bits.clone(), params);
+                assert_eq!(rank.params(), params);
+                check(&rank, &bits);
+                assert_eq!(rank.into_inner(), bits);
+            }
+        }
+    }
+
+    assert_eq!(rank_small_params(1000, 0.5), (2, 9));
+    assert_eq!(rank_small_params(1 << 24, 0.5), (1, 10));
+    assert_eq!(rank_small_params(1 << 30, 0.5), (1, 11));
+    assert_eq!(rank_small_params(1 << 30, 0.001), (3, 13));
+    assert_eq!(rank_small_params(1 << 30, 0.999), (3, 13));
+}
+
+#[test]
+#[should_panic]
+fn test_rank_sel_any_rank_small_unsupported() {
+    sux::rank_sel::auto::AnyRankSmall::new(bit_vec![0; 10], (2, 10));
+}
+
+#[test]
+fn test_rank_sel_elias_fano_select_zero() {
+    use mem_dbg::*;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use sux::rank_sel::auto::*;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1_000_000;
+    for density in [0.9, 0.95, 0.99, 0.995, 0.999] {
+        let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+        let zeros = bits.iter_zeros().collect::<Vec<_>>();
+        let select_zero = EliasFanoSelectZero::new(Rank9::new(bits.clone()));
+
+        assert_eq!(select_zero.len(), len);
+        assert_eq!(select_zero.num_zeros(), zeros.len());
+        for (rank, &pos) in zeros.iter().enumerate() {
+            assert_eq!(select_zero.select_zero(rank), Some(pos));
+        }
+        assert_eq!(select_zero.select_zero(zeros.len()), None);
+
+        // Rank and selection of ones are forwarded
+        let rank9 = Rank9::new(bits.clone());
+        for pos in (0..len).step_by(997) {
+            assert_eq!(select_zero.rank(pos), rank9.rank(pos));
+            assert_eq!(select_zero[pos], bits[pos]);
+        }
+
+        // The automatic choice agrees
+        let select_zero = build_select_zero(bits.clone(), SpaceSpeed::Speed);
+        for (rank, &pos) in zeros.iter().enumerate() {
+            assert_eq!(select_zero.select_zero(rank), Some(pos));
+        }
+    }
+
+    // When chosen, the representation of the zeros is smaller than the
+    // inventory of a selection structure for zeros on the same bits
+    let len = 1 << 22;
+    for density in [0.998, 0.999, 0.9995] {
+        let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+        assert_eq!(
+            select_zero_kind(len, bits.count_ones(), SpaceSpeed::Space),
+            SelectZeroKind::EliasFano
+        );
+        let select_zero = EliasFanoSelectZero::new(Rank9::new(bits.clone()));
+        let adapt_const = SelectZeroAdaptConst::<_, _>::new(AddNumBits::from(bits.clone()));
+        assert!(
+            select_zero.zeros().mem_size(SizeFlags::default())
+                < adapt_const.mem_size(SizeFlags::default()) - bits.mem_size(SizeFlags::default())
+        );
+    }
+
+    for target in [SpaceSpeed::Space, SpaceSpeed::Speed] {
+        assert_eq!(
+            select_zero_kind(1 << 20, (1 << 20) - 1, target),
+            SelectZeroKind::EliasFano
+        );
+        assert_ne!(
+            select_zero_kind(1 << 20, 1 << 20, target),
+            SelectZeroKind::EliasFano
+        );
+        assert_ne!(
+            select_zero_kind(1 << 20, 0, target),
+            SelectZeroKind::EliasFano
+        );
+    }
+    assert_eq!(
+        select_zero_kind(1 << 20, 1 << 19, SpaceSpeed::Speed),
+        SelectZeroKind::SelectZeroAdaptConst
+    );
+
+    // Automatic choice on all densities
+    for len in [1, 100, 1000, 10000] {
+        for density in [0.0, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let zeros = bits.iter_zeros().collect::<Vec<_>>();
+            for target in [SpaceSpeed::Space, SpaceSpeed::Speed] {
+                let select_zero = build_select_zero(bits.clone(), target);
+                assert_eq!(select_zero.num_zeros(), zeros.len());
+                for (rank, &pos) in zeros.iter().enumerate() {
+                    assert_eq!(select_zero.select_zero(rank), Some(pos));
+                }
+                assert_eq!(select_zero.select_zero(zeros.len()), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rank_sel_into_bit_vec() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let bits = (0..10000).map(|_| rng.gen_bool(0.5)).collect::<BitVec>();
+
+    let sel = SelectAdaptConst::<_, _>::new(Rank9::new(bits.clone()));
+    assert_eq!(sel.into_bit_vec(), bits);
+
+    let sel = SelectZeroAdapt::new(SelectAdapt::new(AddNumBits::from(bits.clone()), 3), 3);
+    assert_eq!(sel.into_bit_vec(), bits);
+
+    let sel = Select9::new(Rank9::new(bits.clone()));
+    assert_eq!(sel.into_bit_vec(), bits);
+
+    let sel = SelectSmall::<2, 9, _>::new(rank_small![0; bits.clone()]);
+    assert_eq!(sel.into_bit_vec(), bits);
+
+    assert_eq!(bits.clone().into_bit_vec(), bits);
+}
+
+#[test]
+fn test_rank_sel_num_words() {
+    fn check<B: BitLength + AsRef<[usize]> + ?Sized>(b: &B, len: usize) {
+        let bits = usize::BITS as usize;
+        assert_eq!(b.len(), len);
+        assert_eq!(b.num_words(), len.div_ceil(bits));
+        assert_eq!(b.num_words() * bits - b.len(), b.padding_bits());
+        assert!(b.padding_bits() < bits);
+        assert!(b.as_ref().len() >= b.num_words());
+    }
+
+    for len in (0..300).chain([1000, 1023, 1024, 1025, 100_000]) {
+        let bits = BitVec::with_value(len, true);
+        assert_eq!(bits.count_ones(), len);
+        check(&bits, len);
+        let atomic = sux::bits::AtomicBitVec::with_value(len, true);
+        assert_eq!(atomic.count_ones(), len);
+        assert_eq!(atomic.len(), len);
+        assert_eq!(atomic.num_words(), bits.num_words());
+        assert_eq!(atomic.padding_bits(), bits.padding_bits());
+        let rank9 = Rank9::new(bits.clone());
+        check(&rank9, len);
+        let select = SelectAdaptConst::<_, _>::new(rank9);
+        check(&select, len);
+        assert_eq!(select.select(len.saturating_sub(1)), len.checked_sub(1));
+        let add_num_bits: AddNumBits<_> = bits.into();
+        check(&add_num_bits, len);
+    }
+}
+
+#[test]
+fn test_rank_sel_rank_via_select() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 100, 1000, 10000] {
+        for density in [0.0, 0.001, 0.01, 0.1, 0.5, 0.9, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+
+            let select9 = Select9::new(Rank9::new(bits.clone()));
+            let select_small = SelectSmall::<2, 9, _>::new(RankSmall::<2, 9>::new(bits.clone()));
+            let add_num_bits: AddNumBits<_> = bits.clone().into();
+            let select_adapt = SelectAdaptConst::<_, _>::new(add_num_bits);
+            for pos in 0..len + 2 {
+                let rank = select9.rank(pos);
+                assert_eq!(rank_via_select(&select9, pos), rank);
+                assert_eq!(rank_via_select(&select_small, pos), select_small.rank(pos));
+                assert_eq!(rank_via_select(&select_adapt, pos), rank);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rank_sel_shared_bits() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use std::sync::Arc;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 10_000;
+    let bits = (0..len).map(|_| rng.gen_bool(0.3)).collect::<BitVec>();
+    let ones = bits.iter_ones().collect::<Vec<_>>();
+
+    let shared: BitVec<Arc<[usize]>> = bits.into();
+    let rank9 = Rank9::new(shared.clone());
+    let rank_small = rank_small![0; shared.clone()];
+    let select = SelectAdaptConst::<_, _>::new(AddNumBits::from(shared.clone()));
+
+    // All structures point to the same words
+    let words: &[usize] = shared.as_ref();
+    assert_eq!(AsRef::<[usize]>::as_ref(&rank9).as_ptr(), words.as_ptr());
+    assert_eq!(
+        AsRef::<[usize]>::as_ref(&rank_small).as_ptr(),
+        words.as_ptr()
+    );
+    assert_eq!(AsRef::<[usize]>::as_ref(&select).as_ptr(), words.as_ptr());
+
+    let mut rank = 0;
+    for pos in 0..=len {
+        assert_eq!(rank9.rank(pos), rank);
+        assert_eq!(rank_small.rank(pos), rank);
+        if pos < len && shared[pos] {
+            rank += 1;
+        }
+    }
+    for (rank, &pos) in ones.iter().enumerate() {
+        assert_eq!(select.select(rank), Some(pos));
+    }
+    assert_eq!(select.select(ones.len()), None);
+}
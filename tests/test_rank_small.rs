@@ -5,6 +5,7 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
 use rand::Rng;
 use rand::SeedableRng;
 use sux::prelude::*;
@@ -113,3 +114,49 @@ fn test_rank_small_large() {
         assert_eq!(rank_small.rank(i), i.div_ceil(5));
     }
 }
+
+macro_rules! test_rank_small_range {
+    ($n: tt) => {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+            for density in [0.1, 0.5, 0.9] {
+                let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+                let rank_small = rank_small![$n; bits.clone()];
+                assert_eq!(rank_small.num_ones(), bits.count_ones());
+
+                let mut ranks = Vec::with_capacity(len + 1);
+                ranks.push(0);
+                for bit in &bits {
+                    ranks.push(ranks.last().unwrap() + bit as usize);
+                }
+
+                for _ in 0..1000 {
+                    let start = rng.gen_range(0..=len);
+                    // Short ranges within a word or a block, and long ranges
+                    // across many blocks
+                    let max_len = *[1, 64, 512, 2048, len + 1].choose(&mut rng).unwrap();
+                    let end = (start + rng.gen_range(0..=max_len)).min(len);
+                    assert_eq!(
+                        rank_small.rank_range(start, end),
+                        ranks[end] - ranks[start],
+                        "start = {}, end = {}, len = {}",
+                        start,
+                        end,
+                        len
+                    );
+                }
+                assert_eq!(rank_small.rank_range(0, len + 100), ranks[len]);
+                assert_eq!(rank_small.rank_range(len / 2, len / 4), 0);
+            }
+        }
+    };
+}
+
+#[test]
+fn test_rank_small_range() {
+    test_rank_small_range![0];
+    test_rank_small_range![1];
+    test_rank_small_range![2];
+    test_rank_small_range![3];
+    test_rank_small_range![4];
+}
@@ -22,6 +22,386 @@ fn test_rear_coded_list_100() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_rear_coded_list_histograms() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 3, 4, 8, 1000] {
+        let mut rcab = <RearCodedListBuilder>::new(k);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rca = rcab.build();
+        let num_blocks = words.len().div_ceil(k);
+
+        let fill = rca.block_fill_histogram();
+        assert_eq!(fill.len(), k + 1);
+        assert_eq!(fill.iter().sum::<usize>(), num_blocks);
+        assert_eq!(
+            fill.iter().enumerate().map(|(i, c)| i * c).sum::<usize>(),
+            words.len()
+        );
+
+        let lcp = rca.lcp_histogram();
+        assert_eq!(lcp.iter().sum::<usize>(), words.len() - num_blocks);
+        let mut expected = vec![0; lcp.len()];
+        for i in (0..words.len()).filter(|i| i % k != 0) {
+            let (a, b) = (words[i - 1].as_bytes(), words[i].as_bytes());
+            let len = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+            expected[len] += 1;
+        }
+        assert_eq!(lcp, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_length_prefixed() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    // Strings on a small alphabet containing \0, so that many strings share
+    // prefixes and many contain \0
+    let mut words = (0..1000)
+        .map(|_| {
+            (0..rng.gen_range(0..10))
+                .map(|_| ['\0', 'a', 'b'][rng.gen_range(0..3)])
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    for sorted in [false, true] {
+        if sorted {
+            words.sort();
+            words.dedup();
+        }
+        for k in [1, 3, 8] {
+            let mut rcab = RearCodedListBuilder::new(k).length_prefixed(true);
+            rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+            let rca = rcab.build();
+            assert!(rca.is_length_prefixed());
+            assert_eq!(rca.len(), words.len());
+
+            let mut buffer = Vec::new();
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(rca.get(i), *word);
+                rca.get_in_place(i, &mut buffer);
+                assert_eq!(buffer, word.as_bytes());
+            }
+            assert_eq!(rca.iter().collect::<Vec<_>>(), words);
+            assert_eq!(
+                rca.iter_from(words.len() / 2).collect::<Vec<_>>(),
+                words[words.len() / 2..]
+            );
+            let mut lender = rca.lend();
+            let mut i = 0;
+            while let Some(s) = lender.next() {
+                assert_eq!(s, words[i]);
+                i += 1;
+            }
+            assert_eq!(i, words.len());
+
+            for word in &words {
+                assert!(rca.contains(word.as_str()));
+                assert_eq!(rca.get(rca.index_of(word.as_str()).unwrap()), *word);
+            }
+            for missing in ["\0\0\0\0\0\0\0\0\0\0", "c", "a\0c", "bbbbbbbbbbb"] {
+                assert!(!rca.contains(missing));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_try_extend() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    let mut rcab = RearCodedListBuilder::new(4);
+    rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+    let rca = rcab.build();
+
+    let mut rcab = RearCodedListBuilder::new(4);
+    rcab.try_extend(sux::utils::LineLender::from_path(
+        "tests/data/wordlist.100",
+    )?)?;
+    let rcb = rcab.build();
+
+    assert_eq!(rcb.len(), rca.len());
+    assert_eq!(
+        rcb.iter().collect::<Vec<_>>(),
+        rca.iter().collect::<Vec<_>>()
+    );
+    for block in rca.debug_blocks() {
+        assert_eq!(rcb.raw_block(block.index), rca.raw_block(block.index));
+    }
+
+    // Errors are propagated, and the strings before the error are kept
+    let mut rcab = RearCodedListBuilder::new(4);
+    let lines = std::io::Cursor::new(b"a\nb\nc\n\xff\nd\n");
+    let err = rcab
+        .try_extend(sux::utils::LineLender::new(lines))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(rcab.build().iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_get_block_in_place() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for length_prefixed in [false, true] {
+        for k in [1, 3, 4, 8, 200] {
+            let mut rcab = RearCodedListBuilder::new(k).length_prefixed(length_prefixed);
+            rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+            let rca = rcab.build();
+
+            let mut buffer = Vec::new();
+            let mut offsets = Vec::new();
+            for block in 0..rca.len().div_ceil(k) {
+                buffer.clear();
+                offsets.clear();
+                rca.get_block_in_place(block, &mut buffer, &mut offsets);
+                assert_eq!(offsets.len(), k.min(rca.len() - block * k));
+                for (i, &start) in offsets.iter().enumerate() {
+                    let end = offsets
+                        .as_slice()
+                        .get(i + 1)
+                        .copied()
+                        .unwrap_or(buffer.len());
+                    assert_eq!(
+                        std::str::from_utf8(&buffer[start..end])?,
+                        rca.get(block * k + i)
+                    );
+                }
+            }
+
+            // Appending all blocks reconstructs the whole list
+            buffer.clear();
+            offsets.clear();
+            for block in 0..rca.len().div_ceil(k) {
+                rca.get_block_in_place(block, &mut buffer, &mut offsets);
+            }
+            assert_eq!(offsets.len(), words.len());
+            offsets.push(buffer.len());
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(&buffer[offsets[i]..offsets[i + 1]], word.as_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_append_raw_block() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for length_prefixed in [false, true] {
+        for k in [1, 3, 4, 8] {
+            let builder = || RearCodedListBuilder::new(k).length_prefixed(length_prefixed);
+            let mut rcab = builder();
+            rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+            let rca = rcab.build();
+
+            // Each worker encodes a shard of 2k strings
+            let mut rcab = builder();
+            for shard in words.chunks(2 * k) {
+                let mut worker = builder();
+                shard.iter().for_each(|s| worker.push(s));
+                let worker = worker.build();
+                for block in worker.debug_blocks() {
+                    unsafe {
+                        rcab.append_raw_block(
+                            worker.raw_block(block.index),
+                            block.strings,
+                            worker.get(block.index * k).as_bytes(),
+                        )
+                    };
+                }
+            }
+            let merged = rcab.build();
+
+            assert_eq!(
+                merged.debug_blocks().collect::<Vec<_>>(),
+                rca.debug_blocks().collect::<Vec<_>>()
+            );
+            for block in rca.debug_blocks() {
+                assert_eq!(merged.raw_block(block.index), rca.raw_block(block.index));
+            }
+            assert_eq!(merged.iter().collect::<Vec<_>>(), words);
+            for word in &words {
+                assert_eq!(merged.index_of(word.as_str()), rca.index_of(word.as_str()));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_sample() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 4, 8] {
+        let mut rcab = <RearCodedListBuilder>::new(k);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rca = rcab.build();
+        for step in [1, 2, 3, 5, 50, 100, 1000] {
+            let sample = rca.sample(step);
+            assert_eq!(sample.len(), words.len().div_ceil(step));
+            for (i, s) in sample.iter().enumerate() {
+                assert_eq!(*s, rca.get(i * step));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_binary_search_by() -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| {
+            // Randomly change the case of the characters
+            line.unwrap()
+                .chars()
+                .map(|c| {
+                    if rng.gen_bool(0.5) {
+                        c.to_ascii_uppercase()
+                    } else {
+                        c
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    words.sort_by_key(|s| s.to_ascii_lowercase());
+    words.dedup_by_key(|s| s.to_ascii_lowercase());
+
+    let cmp = |s: &[u8], key: &str| {
+        s.to_ascii_lowercase()
+            .cmp(&key.as_bytes().to_ascii_lowercase())
+    };
+
+    for k in [1, 2, 4, 8, 1000] {
+        let mut rcab = <RearCodedListBuilder>::new(k);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rca = rcab.build();
+
+        for (i, word) in words.iter().enumerate() {
+            for key in [
+                word.clone(),
+                word.to_ascii_lowercase(),
+                word.to_ascii_uppercase(),
+            ] {
+                assert_eq!(rca.binary_search_by(|s| cmp(s, &key)), Ok(i));
+            }
+            // Keys that are not in the list
+            for key in [
+                word.to_ascii_lowercase() + "~",
+                word[..word.len() - 1].to_owned() + "\0",
+            ] {
+                assert_eq!(
+                    rca.binary_search_by(|s| cmp(s, &key)),
+                    words.binary_search_by(|s| cmp(s.as_bytes(), &key))
+                );
+            }
+        }
+        assert_eq!(rca.binary_search_by(|s| cmp(s, "")), Err(0));
+    }
+
+    let rca = <RearCodedListBuilder>::new(4).build();
+    assert_eq!(rca.binary_search_by(|s| cmp(s, "a")), Err(0));
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_compare_stats() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    let build = |k| {
+        let mut rcab = <RearCodedListBuilder>::new(k);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        rcab.build()
+    };
+    let (rca4, rca16) = (build(4), build(16));
+
+    let diff = rca4.compare_stats(&rca16);
+    // Larger blocks mean fewer pointers and fewer uncompressed strings
+    assert!(diff.data_bytes < 0);
+    assert!(diff.pointers_bytes < 0);
+    assert!(diff.compression_ratio < 0.0);
+    assert_eq!(
+        diff.compression_ratio,
+        rca16.compression_ratio() - rca4.compression_ratio()
+    );
+
+    // The comparison is antisymmetric
+    let rev = rca16.compare_stats(&rca4);
+    assert_eq!(rev.data_bytes, -diff.data_bytes);
+    assert_eq!(rev.pointers_bytes, -diff.pointers_bytes);
+
+    let same = rca4.compare_stats(&rca4);
+    assert_eq!(same.data_bytes, 0);
+    assert_eq!(same.pointers_bytes, 0);
+    assert_eq!(same.compression_ratio, 0.0);
+
+    let display = diff.to_string();
+    assert!(display.contains("data_bytes"));
+    assert!(display.contains(&format!("{:+}", diff.pointers_bytes)));
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_rear_coded_list_par_contains() -> Result<()> {
+    let mut words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    // Half of the queries are in the list, half are not
+    let mut queries = words.clone();
+    queries.extend(words.iter().map(|w| format!("{}!", w)));
+    let mut rng = SmallRng::seed_from_u64(0);
+    queries.shuffle(&mut rng);
+    let queries = queries.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+    for sorted in [true, false] {
+        if !sorted {
+            words.shuffle(&mut rng);
+            // Linear scans are slow
+            words.truncate(500);
+        }
+        let mut rcab = <RearCodedListBuilder>::new(8);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rca = rcab.build();
+
+        let par = rca.par_contains(&queries);
+        assert_eq!(par.len(), queries.len());
+        for (query, found) in queries.iter().zip(par) {
+            assert_eq!(found, rca.contains(*query), "{:?}", query);
+        }
+    }
+    Ok(())
+}
+
 fn test_rear_coded_list(path: impl AsRef<str>) -> Result<()> {
     let words = BufReader::new(std::fs::File::open(path.as_ref()).unwrap())
         .lines()
@@ -150,3 +530,688 @@ fn test_rear_coded_list(path: impl AsRef<str>) -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_rear_coded_list_version() -> Result<()> {
+    let words = ["aa", "aab", "abc", "abdd", "abde", "abdf", "b", "ba", "bab"];
+    let mut rclb = RearCodedListBuilder::new(4);
+    for word in words {
+        rclb.push(word);
+    }
+    let rcl = rclb.build();
+    assert_eq!(
+        rcl.version(),
+        RearCodedList::<Box<[u8]>, Box<[usize]>>::VERSION
+    );
+    assert!(rcl.check_version().is_ok());
+
+    let tmp_file = std::env::temp_dir().join("test_rcl_version.bin");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp_file)?);
+    let schema = rcl.serialize_with_schema(&mut file)?;
+    drop(file);
+
+    let c = <RearCodedList>::load_full_checked(&tmp_file)?;
+    assert_eq!(c.iter().collect::<Vec<_>>(), words);
+    let c = <RearCodedList>::mmap_checked(&tmp_file, epserde::deser::Flags::empty())?;
+    assert_eq!(c.iter().collect::<Vec<_>>(), words);
+
+    // Patch the version in the serialized file
+    let row = schema
+        .0
+        .iter()
+        .find(|row| row.field.ends_with("version"))
+        .unwrap();
+    assert_eq!(row.size, 4);
+    let mut bytes = std::fs::read(&tmp_file)?;
+    let version = RearCodedList::<Box<[u8]>, Box<[usize]>>::VERSION;
+    assert_eq!(bytes[row.offset..row.offset + 4], version.to_ne_bytes());
+    bytes[row.offset..row.offset + 4].copy_from_slice(&(version + 1).to_ne_bytes());
+    std::fs::write(&tmp_file, bytes)?;
+
+    let err = <RearCodedList>::load_full_checked(&tmp_file).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<VersionMismatch>(),
+        Some(&VersionMismatch {
+            found: version + 1,
+            expected: version
+        })
+    );
+    let err = <RearCodedList>::mmap_checked(&tmp_file, epserde::deser::Flags::empty()).unwrap_err();
+    assert!(err.downcast_ref::<VersionMismatch>().is_some());
+    assert!(err.to_string().contains("version"));
+
+    std::fs::remove_file(&tmp_file)?;
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_k_for_latency() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for max_scan in [0, 16, 64, 256, 1024] {
+        let k = RearCodedListBuilder::k_for_latency(&words, max_scan);
+        assert!(k >= 1);
+        let mut rcab = RearCodedListBuilder::new(k);
+        for word in &words {
+            rcab.push(word);
+        }
+        let rca = rcab.build();
+        let max_block = (0..rca.len().div_ceil(k))
+            .map(|block| rca.raw_block(block).len())
+            .max()
+            .unwrap();
+        if k > 1 {
+            assert!(
+                max_block <= max_scan,
+                "k = {}: {} > {}",
+                k,
+                max_block,
+                max_scan
+            );
+        }
+    }
+
+    // Larger budgets never yield smaller blocks
+    assert!(
+        RearCodedListBuilder::k_for_latency(&words, 64)
+            <= RearCodedListBuilder::k_for_latency(&words, 1024)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_peek_seek() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for k in [1, 3, 4, 8, 16] {
+        let mut rcab = RearCodedListBuilder::new(k);
+        for word in &words {
+            rcab.push(word);
+        }
+        let rca = rcab.build();
+
+        // Interleaving peek and next yields the same sequence as iter
+        let mut iter = rca.iter();
+        let mut lend = rca.lend();
+        for (i, word) in rca.iter().enumerate() {
+            for _ in 0..rng.gen_range(0..3) {
+                assert_eq!(iter.peek(), Some(word.as_str()));
+                assert_eq!(lend.peek(), Some(word.as_str()));
+            }
+            assert_eq!(iter.len(), words.len() - i);
+            assert_eq!(lend.len(), words.len() - i);
+            assert_eq!(iter.next(), Some(word.clone()));
+            assert_eq!(lend.next(), Some(word.as_str()));
+        }
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(lend.peek(), None);
+        assert_eq!(lend.next(), None);
+
+        // Seeking forward
+        let mut iter = rca.iter();
+        let mut index = 0;
+        while index < words.len() {
+            if rng.gen_bool(0.5) {
+                assert_eq!(iter.peek(), Some(words[index].as_str()));
+            }
+            iter.seek(index);
+            assert_eq!(iter.peek(), Some(words[index].as_str()));
+            assert_eq!(iter.len(), words.len() - index);
+            index += rng.gen_range(0..2 * k + 2);
+            iter.seek(index);
+            if index < words.len() {
+                assert_eq!(iter.next(), Some(words[index].clone()));
+                index += 1;
+            }
+        }
+        iter.seek(words.len());
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.len(), 0);
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_rear_coded_list_seek_backwards() {
+    let mut rcab = RearCodedListBuilder::new(4);
+    for word in ["a", "b", "c", "d", "e", "f"] {
+        rcab.push(word);
+    }
+    let rca = rcab.build();
+    let mut iter = rca.iter();
+    iter.seek(5);
+    iter.seek(2);
+}
+
+#[test]
+fn test_rear_coded_list_is_sorted() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 4, 16] {
+        let mut sorted = words.clone();
+        sorted.sort();
+        let mut rcab = RearCodedListBuilder::new(k);
+        assert!(rcab.is_sorted());
+        for word in &sorted {
+            rcab.push(word);
+            assert!(rcab.is_sorted());
+        }
+        // Duplicates do not break sortedness
+        rcab.push(sorted.last().unwrap());
+        assert!(rcab.is_sorted());
+        let rca = rcab.build();
+        assert!(rca.is_sorted());
+        for word in &sorted {
+            assert!(rca.contains(word.as_str()));
+        }
+
+        let mut unsorted = sorted.clone();
+        unsorted.swap(10, 20);
+        let mut rcab = RearCodedListBuilder::new(k);
+        for (i, word) in unsorted.iter().enumerate() {
+            rcab.push(word);
+            // The first inversion is between positions 10 and 11
+            assert_eq!(rcab.is_sorted(), i < 11);
+        }
+        let rca = rcab.build();
+        assert!(!rca.is_sorted());
+        // Lookups fall back to a linear scan
+        for (i, word) in unsorted.iter().enumerate() {
+            assert_eq!(rca.index_of(word.as_str()), Some(i));
+        }
+        assert!(!rca.contains("not a word"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_enumerate_values() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 3, 4, 8, 200] {
+        let mut rcab = RearCodedListBuilder::new(k);
+        for word in &words {
+            rcab.push(word);
+        }
+        let rca = rcab.build();
+        assert_eq!(
+            rca.enumerate_values().collect::<Vec<_>>(),
+            (0..rca.len()).map(|i| (i, rca.get(i))).collect::<Vec<_>>()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_contains_bounds() {
+    let words = ["b", "bc", "bcd", "d", "de", "f", "g"];
+    for length_prefixed in [false, true] {
+        for k in [1, 2, 3, 4, 7, 8] {
+            let mut rcab = RearCodedListBuilder::new(k).length_prefixed(length_prefixed);
+            rcab.extend(words.iter().copied().into_lender());
+            let rca = rcab.build();
+
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(rca.index_of(*word), Some(i), "k = {}", k);
+            }
+            // Strictly before the first string
+            assert!(!rca.contains(""));
+            assert!(!rca.contains("a"));
+            assert!(!rca.contains("azzz"));
+            // Strictly after the last string
+            assert!(!rca.contains("ga"));
+            assert!(!rca.contains("h"));
+            assert!(!rca.contains("zzz"));
+            // Equal to the last string, and between strings of the last block
+            assert!(rca.contains("g"));
+            assert!(!rca.contains("ff"));
+            assert!(!rca.contains("bb"));
+            assert!(!rca.contains("c"));
+        }
+    }
+}
+
+#[test]
+fn test_rear_coded_list_separate_codes() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for length_prefixed in [false, true] {
+        for k in [1, 3, 4, 8, 200] {
+            let mut rcab = RearCodedListBuilder::new(k).length_prefixed(length_prefixed);
+            rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+            let interleaved = rcab.build();
+
+            let mut rcab = RearCodedListBuilder::new(k)
+                .length_prefixed(length_prefixed)
+                .separate_codes(true);
+            rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+            let separate = rcab.build();
+
+            assert!(!interleaved.has_separate_codes());
+            assert!(separate.has_separate_codes());
+            assert_eq!(separate.len(), interleaved.len());
+            assert_eq!(separate.is_sorted(), interleaved.is_sorted());
+
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(separate.get(i), interleaved.get(i));
+                assert_eq!(separate.index_of(word.as_str()), Some(i));
+                assert_eq!(
+                    separate.binary_search_by(|s| s.cmp(word.as_bytes())),
+                    interleaved.binary_search_by(|s| s.cmp(word.as_bytes()))
+                );
+                let mut absent = word.clone();
+                absent.push('\u{1}');
+                assert!(!separate.contains(absent.as_str()));
+            }
+            assert!(separate.iter().eq(interleaved.iter()));
+            assert_eq!(separate.lcp_histogram(), interleaved.lcp_histogram());
+
+            let mut buffer = Vec::new();
+            let mut offsets = Vec::new();
+            for block in 0..separate.len().div_ceil(k) {
+                separate.get_block_in_place(block, &mut buffer, &mut offsets);
+            }
+            offsets.push(buffer.len());
+            for (i, word) in words.iter().enumerate() {
+                assert_eq!(&buffer[offsets[i]..offsets[i + 1]], word.as_bytes());
+            }
+
+            let mut lend = separate.lend();
+            lend.seek(1);
+            assert_eq!(lend.next(), Some(words[1].as_str()));
+            lend.seek(words.len() / 2);
+            assert_eq!(lend.next(), Some(words[words.len() / 2].as_str()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_rear_coded_list_separate_codes_raw_block() {
+    let mut rcab = RearCodedListBuilder::new(4).separate_codes(true);
+    rcab.push("a");
+    rcab.build().raw_block(0);
+}
+
+#[test]
+fn test_rear_coded_list_lend_bytes() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for k in [1, 3, 4, 8, 200] {
+        let mut rcab = RearCodedListBuilder::new(k);
+        rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+        let rca = rcab.build();
+
+        let mut lender = rca.lend_bytes();
+        for (i, word) in rca.iter().enumerate() {
+            assert_eq!(lender.len(), words.len() - i);
+            assert_eq!(lender.peek(), Some(word.as_bytes()));
+            assert_eq!(lender.next(), Some(word.as_bytes()));
+        }
+        assert_eq!(lender.next(), None);
+
+        let from = words.len() / 3;
+        let mut lender = rca.lend_bytes_from(from);
+        let mut iter = rca.iter_from(from);
+        while let Some(bytes) = lender.next() {
+            assert_eq!(Some(bytes), iter.next().as_deref().map(str::as_bytes));
+        }
+        assert_eq!(iter.next(), None);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_index_of_many() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let mut shuffled = words.clone();
+    let mut rng = SmallRng::seed_from_u64(0);
+    shuffled.shuffle(&mut rng);
+
+    for list in [&words, &shuffled] {
+        for k in [1, 3, 4, 8, 16] {
+            let mut rcab = RearCodedListBuilder::new(k);
+            rcab.extend(list.iter().map(|s| s.as_str()).into_lender());
+            let rca = rcab.build();
+
+            for num_queries in [0, 1, 10, 100, 500] {
+                // Half of the queries are present, half are absent
+                let queries = (0..num_queries)
+                    .map(|i| {
+                        let mut s = list[rng.gen_range(0..list.len())].clone();
+                        if i % 2 == 0 {
+                            s.push(*['\u{1}', 'a', 'z'].choose(&mut rng).unwrap());
+                        }
+                        s
+                    })
+                    .collect::<Vec<_>>();
+                let queries = queries.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                let expected = queries
+                    .iter()
+                    .map(|&query| rca.index_of(query))
+                    .collect::<Vec<_>>();
+                assert_eq!(rca.index_of_many(&queries), expected);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_trailing_terminator() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    // The last string is a block head, an empty string, or a string in a block
+    let mut with_empty = words.clone();
+    with_empty.sort();
+    with_empty.insert(0, String::new());
+    let mut unsorted = words.clone();
+    unsorted.push(String::new());
+    for list in [&words, &with_empty, &unsorted] {
+        for separate_codes in [false, true] {
+            for k in [1, 3, 4, 8, 200] {
+                let build = |trailing_terminator| {
+                    let mut rcab = RearCodedListBuilder::new(k)
+                        .separate_codes(separate_codes)
+                        .trailing_terminator(trailing_terminator);
+                    rcab.extend(list.iter().map(|s| s.as_str()).into_lender());
+                    rcab.build()
+                };
+                let terminated = build(true);
+                let rca = build(false);
+                assert_eq!(rca.len(), list.len());
+                assert_eq!(
+                    rca.debug_blocks().map(|b| b.bytes).sum::<usize>() + 1,
+                    terminated.debug_blocks().map(|b| b.bytes).sum::<usize>()
+                );
+
+                for (i, word) in list.iter().enumerate() {
+                    assert_eq!(&rca.get(i), word);
+                    assert!(rca.contains(word.as_str()));
+                    assert_eq!(
+                        rca.index_of(word.as_str()),
+                        terminated.index_of(word.as_str())
+                    );
+                    let mut absent = word.clone();
+                    absent.push('\u{1}');
+                    assert!(!rca.contains(absent.as_str()));
+                }
+                assert!(rca.iter().eq(list.iter().cloned()));
+                let mut lend = rca.lend_from(list.len() - 1);
+                assert_eq!(lend.next(), Some(list[list.len() - 1].as_str()));
+                assert_eq!(lend.next(), None);
+                if rca.is_sorted() {
+                    for word in list {
+                        assert_eq!(
+                            rca.binary_search_by(|s| s.cmp(word.as_bytes())),
+                            terminated.binary_search_by(|s| s.cmp(word.as_bytes()))
+                        );
+                    }
+                }
+
+                let mut buffer = Vec::new();
+                let mut offsets = Vec::new();
+                for block in 0..rca.len().div_ceil(k) {
+                    rca.get_block_in_place(block, &mut buffer, &mut offsets);
+                }
+                offsets.push(buffer.len());
+                for (i, word) in list.iter().enumerate() {
+                    assert_eq!(&buffer[offsets[i]..offsets[i + 1]], word.as_bytes());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_into_byte_iter() {
+    // Keys with NUL bytes and multi-byte characters
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut keys = (0..1000)
+        .map(|_| {
+            (0..rng.gen_range(0..10))
+                .map(|_| {
+                    *['\0', '\u{1}', 'a', 'b', 'é', '\u{10ffff}']
+                        .choose(&mut rng)
+                        .unwrap()
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    keys.sort();
+
+    for separate_codes in [false, true] {
+        for k in [1, 3, 4, 8, 16] {
+            let mut rcab = RearCodedListBuilder::new(k)
+                .length_prefixed(true)
+                .separate_codes(separate_codes);
+            rcab.extend(keys.iter().map(|s| s.as_str()).into_lender());
+            let rca = rcab.build();
+
+            let mut iter = rca.into_byte_iter();
+            assert_eq!(iter.len(), keys.len());
+            for (i, key) in keys.iter().enumerate() {
+                assert_eq!(iter.next(), Some(key.as_bytes().to_vec()));
+                assert_eq!(iter.len(), keys.len() - i - 1);
+            }
+            assert_eq!(iter.next(), None);
+        }
+    }
+
+    // Without length prefixes
+    let words = ["a", "ab", "abc", "b", "ba", "é", "éé"];
+    let mut rcab = RearCodedListBuilder::new(2);
+    rcab.extend(words.iter().copied().into_lender());
+    let rca = rcab.build();
+    assert!(rca
+        .into_byte_iter()
+        .eq(words.iter().map(|w| w.as_bytes().to_vec())));
+}
+
+#[test]
+fn test_rear_coded_list_rear_length_at() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for length_prefixed in [false, true] {
+        for separate_codes in [false, true] {
+            for k in [1, 3, 4, 8, 16] {
+                let mut rcab = RearCodedListBuilder::new(k)
+                    .length_prefixed(length_prefixed)
+                    .separate_codes(separate_codes);
+                rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+                let rca = rcab.build();
+
+                for (i, word) in words.iter().enumerate() {
+                    let rear_length = rca.rear_length_at(i);
+                    if i % k == 0 {
+                        assert_eq!(rear_length, 0);
+                        continue;
+                    }
+                    let prev = &words[i - 1];
+                    let lcp = prev
+                        .bytes()
+                        .zip(word.bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    assert_eq!(prev.len() - rear_length, lcp, "i = {}, k = {}", i, k);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_rear_coded_list_rear_length_at_out_of_bounds() {
+    let mut rcab = RearCodedListBuilder::new(4);
+    rcab.push("a");
+    rcab.build().rear_length_at(1);
+}
+
+#[test]
+fn test_rear_coded_list_padding() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let mut unsorted = words.clone();
+    unsorted.reverse();
+
+    for list in [&words, &unsorted] {
+        for length_prefixed in [false, true] {
+            for trailing_terminator in [false, true] {
+                for k in [1, 3, 4, 8, 200] {
+                    let build = |padding| {
+                        let mut rcab = RearCodedListBuilder::new(k)
+                            .length_prefixed(length_prefixed)
+                            .trailing_terminator(trailing_terminator)
+                            .padding(padding);
+                        rcab.extend(list.iter().map(|s| s.as_str()).into_lender());
+                        rcab.build()
+                    };
+                    let plain = build(0);
+                    let rca = build(32);
+                    assert_eq!(plain.padding(), 0);
+                    assert_eq!(rca.padding(), 32);
+                    assert_eq!(rca.len(), list.len());
+                    assert_eq!(plain.compare_stats(&rca).data_bytes, 32);
+                    assert_eq!(plain.compare_stats(&rca).pointers_bytes, 0);
+
+                    for (i, word) in list.iter().enumerate() {
+                        assert_eq!(&rca.get(i), word);
+                        assert_eq!(rca.index_of(word.as_str()), plain.index_of(word.as_str()));
+                        let mut absent = word.clone();
+                        absent.push('\u{1}');
+                        assert!(!rca.contains(absent.as_str()));
+                    }
+                    assert!(rca.iter().eq(list.iter().cloned()));
+                    assert_eq!(rca.lend().count(), list.len());
+                    assert!(rca
+                        .into_byte_iter()
+                        .eq(list.iter().map(|s| s.as_bytes().to_vec())));
+
+                    let rca = build(32);
+                    assert!(rca.debug_blocks().eq(plain.debug_blocks()));
+                    for block in 0..list.len().div_ceil(k) {
+                        assert_eq!(rca.raw_block(block), plain.raw_block(block));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_code_len_histogram() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let mut unsorted = words.clone();
+    unsorted.reverse();
+
+    for list in [&words, &unsorted] {
+        for separate_codes in [false, true] {
+            for k in [1, 3, 4, 8, 16] {
+                let mut rcab = RearCodedListBuilder::new(k).separate_codes(separate_codes);
+                rcab.extend(list.iter().map(|s| s.as_str()).into_lender());
+                let histogram = rcab.code_len_histogram();
+                assert_eq!(
+                    histogram.iter().sum::<usize>(),
+                    list.len() - list.len().div_ceil(k)
+                );
+                if k == 1 {
+                    assert_eq!(histogram, [0; 9]);
+                }
+            }
+        }
+
+        // Appending raw blocks yields the same histogram
+        let mut rcab = RearCodedListBuilder::new(8);
+        rcab.extend(list.iter().map(|s| s.as_str()).into_lender());
+        let expected = rcab.code_len_histogram();
+        let rca = rcab.build();
+        let mut appended = RearCodedListBuilder::new(8);
+        for block in rca.debug_blocks() {
+            unsafe {
+                appended.append_raw_block(
+                    rca.raw_block(block.index),
+                    block.strings,
+                    rca.get(block.index * 8).as_bytes(),
+                )
+            };
+        }
+        assert_eq!(appended.code_len_histogram(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rear_coded_list_block_of_offset() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.100")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    for separate_codes in [false, true] {
+        for padding in [0, 8] {
+            for k in [1, 3, 4, 8, 200] {
+                let mut rcab = RearCodedListBuilder::new(k)
+                    .separate_codes(separate_codes)
+                    .padding(padding);
+                rcab.extend(words.iter().map(|s| s.as_str()).into_lender());
+                let rca = rcab.build();
+
+                let mut end = 0;
+                for block in rca.debug_blocks() {
+                    assert_eq!(rca.block_of_offset(block.offset), Some(block.index));
+                    for offset in block.offset..block.offset + block.bytes {
+                        assert_eq!(rca.block_of_offset(offset), Some(block.index));
+                    }
+                    end = block.offset + block.bytes;
+                }
+                for offset in end..end + padding + 10 {
+                    assert_eq!(rca.block_of_offset(offset), None);
+                }
+            }
+        }
+    }
+
+    let rca = RearCodedListBuilder::new(4).build();
+    assert_eq!(rca.block_of_offset(0), None);
+    Ok(())
+}
@@ -204,3 +204,20 @@ fn test_rank() {
         assert_eq!(select9.rank(len + 1), select9.count_ones());
     }
 }
+
+#[test]
+fn test_select_or_len() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 1000, 100_000] {
+        let bits = (0..len).map(|_| rng.gen_bool(0.5)).collect::<BitVec>();
+        let select9 = Select9::new(Rank9::new(bits.clone()));
+        let ones = bits.count_ones();
+
+        for (i, p) in bits.iter_ones().enumerate() {
+            assert_eq!(select9.select_or_len(i), p);
+        }
+        assert_eq!(select9.select_or_len(ones), len);
+        assert_eq!(select9.select_or_len(ones + 1), len);
+        assert_eq!(select9.select_or_len(usize::MAX), len);
+    }
+}
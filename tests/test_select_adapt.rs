@@ -301,3 +301,56 @@ fn test_sub32s_last_small() {
         assert_eq!(simple.select(ones + 1), None);
     }
 }
+
+#[test]
+fn test_with_recommended() {
+    use mem_dbg::*;
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 100, 10_000, 1_000_000] {
+        for density in [0.0, 0.0001, 0.001, 0.01, 0.1, 0.5, 0.9, 1.0] {
+            let bits: AddNumBits<_> = (0..len)
+                .map(|_| rng.gen_bool(density))
+                .collect::<BitVec>()
+                .into();
+            let num_ones = bits.num_ones();
+            let (log2_ones_per_inventory, log2_u64_per_subinventory) =
+                recommend_params(num_ones, len);
+            assert!(log2_ones_per_inventory >= 2);
+            assert!(log2_u64_per_subinventory <= 3);
+            assert!(4 << log2_u64_per_subinventory <= 1 << log2_ones_per_inventory);
+
+            let bits_size = bits.mem_size(SizeFlags::default());
+            let select = SelectAdapt::with_recommended(bits.clone());
+            let mut rank = 0;
+            for i in 0..len {
+                if bits[i] {
+                    assert_eq!(select.select(rank), Some(i));
+                    rank += 1;
+                }
+            }
+            assert_eq!(select.select(num_ones), None);
+
+            // The inventory is a small fraction of the bit vector
+            let overhead = select.mem_size(SizeFlags::default()) - bits_size;
+            if len >= 1_000_000 {
+                assert!(
+                    overhead * 5 <= bits_size,
+                    "len = {}, density = {}: {} > {} / 5",
+                    len,
+                    density,
+                    overhead,
+                    bits_size
+                );
+            }
+        }
+    }
+
+    // The default parameters of SelectAdaptConst are recommended for
+    // half-dense vectors
+    let bits: AddNumBits<_> = (0..1_000_000)
+        .map(|_| rng.gen_bool(0.5))
+        .collect::<BitVec>()
+        .into();
+    assert_eq!(recommend_params(bits.num_ones(), bits.len()), (12, 3));
+    assert!(SelectAdaptConst::<_, _, 12, 3>::new_checked(bits).is_ok());
+}
@@ -293,3 +293,349 @@ fn test_sub32s_last_small() {
         assert_eq!(simple.select(ones + 1), None);
     }
 }
+
+#[test]
+fn test_select_iter() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+        for density in [0.0, 0.001, 0.1, 0.5, 0.9, 1.0] {
+            let bits: AddNumBits<BitVec> = (0..len)
+                .map(|_| rng.gen_bool(density))
+                .collect::<BitVec>()
+                .into();
+            let select = SelectAdaptConst::<_, _>::new(bits);
+            let ones = select.num_ones();
+
+            for start_rank in [0, 1, ones / 2, ones.saturating_sub(1), ones, ones + 1] {
+                let expected = (start_rank..ones)
+                    .map(|r| select.select(r).unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(select.select_iter(start_rank).collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_raw_parts() {
+    // Writes a slice of words as a length followed by the words
+    fn write(words: &[usize], buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&words.len().to_ne_bytes());
+        words
+            .iter()
+            .for_each(|w| buffer.extend_from_slice(&w.to_ne_bytes()));
+    }
+    // Reads a slice written by write
+    fn read(buffer: &mut &[u8]) -> Box<[usize]> {
+        let mut next = || {
+            let (word, rest) = buffer.split_at(std::mem::size_of::<usize>());
+            *buffer = rest;
+            usize::from_ne_bytes(word.try_into().unwrap())
+        };
+        let len = next();
+        (0..len).map(|_| next()).collect()
+    }
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 1000, 100_000] {
+        for density in [0.001, 0.5, 0.999] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let select: SelectAdaptConst<AddNumBits<BitVec>> =
+                SelectAdaptConst::<_, _>::new(bits.clone().into());
+            let (bits, inventory, spill) = select.clone().into_raw_parts();
+            let (words, bit_len) = bits.into_inner().into_raw_parts();
+
+            let mut buffer = vec![];
+            write(&[bit_len], &mut buffer);
+            write(&words, &mut buffer);
+            write(&inventory, &mut buffer);
+            write(&spill, &mut buffer);
+
+            let mut data = buffer.as_slice();
+            let bit_len = read(&mut data)[0];
+            let words = read(&mut data).into_vec();
+            let inventory = read(&mut data);
+            let spill = read(&mut data);
+            assert!(data.is_empty());
+
+            let bits: AddNumBits<_> = unsafe { BitVec::from_raw_parts(words, bit_len) }.into();
+            let rebuilt =
+                SelectAdaptConst::<_, _>::try_from_parts(bits.clone(), inventory.clone(), spill)
+                    .unwrap();
+            for i in 0..=select.num_ones() {
+                assert_eq!(rebuilt.select(i), select.select(i));
+            }
+
+            // A corrupted inventory must be rejected
+            let mut corrupted = inventory.clone();
+            corrupted[0] ^= 1;
+            assert!(SelectAdaptConst::<_, _>::try_from_parts(
+                bits,
+                corrupted,
+                rebuilt.into_raw_parts().2
+            )
+            .is_none());
+        }
+    }
+}
+
+#[test]
+fn test_select_or_len() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 10, 1000, 100_000] {
+        for density in [0.0, 0.1, 0.5, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let select = SelectAdaptConst::<_, _>::new(AddNumBits::from(bits.clone()));
+            let ones = select.num_ones();
+
+            for (i, p) in bits.iter_ones().enumerate() {
+                assert_eq!(select.select_or_len(i), p);
+            }
+            assert_eq!(select.select_or_len(ones), len);
+            assert_eq!(select.select_or_len(ones + 1), len);
+        }
+    }
+}
+
+#[test]
+fn test_new_checked() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1_000_000;
+
+    // Well-matched constants
+    let bits: AddNumBits<_> = (0..len)
+        .map(|_| rng.gen_bool(0.5))
+        .collect::<BitVec>()
+        .into();
+    let select = SelectAdaptConst::<_, _>::new_checked(bits.clone()).unwrap();
+    let (_, inventory, _) = select.into_raw_parts();
+    assert_eq!(
+        inventory.len(),
+        SelectAdaptConst::<AddNumBits<BitVec>>::expected_inventory_words(bits.count_ones())
+    );
+    assert!(SelectAdaptConst::<_, _, INV, SUB>::new_checked(bits.clone()).is_ok());
+
+    // Too many ones per inventory for a sparse vector
+    let sparse: AddNumBits<_> = (0..len)
+        .map(|_| rng.gen_bool(0.001))
+        .collect::<BitVec>()
+        .into();
+    assert!(matches!(
+        SelectAdaptConst::<_, _>::new_checked(sparse.clone()),
+        Err(ParamsMismatch::SpanTooLarge { .. })
+    ));
+    assert!(SelectAdaptConst::<_, _, 5, 0>::new_checked(sparse).is_ok());
+
+    // Subinventory larger than the number of ones per inventory
+    assert!(matches!(
+        SelectAdaptConst::<_, _, 2, 3>::new_checked(bits),
+        Err(ParamsMismatch::SubinventoryTooLarge {
+            entries: 32,
+            ones_per_inventory: 4
+        })
+    ));
+}
+
+#[test]
+fn test_expected_inventory_words() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 100, 10_000, 100_000] {
+        let bits: AddNumBits<_> = (0..len)
+            .map(|_| rng.gen_bool(0.3))
+            .collect::<BitVec>()
+            .into();
+        let num_ones = bits.count_ones();
+        let (_, inventory, _) = SelectAdaptConst::<_, _, INV, SUB>::new(bits).into_raw_parts();
+        assert_eq!(
+            inventory.len(),
+            SelectAdaptConst::<AddNumBits<BitVec>, Box<[usize]>, INV, SUB>::expected_inventory_words(
+                num_ones
+            )
+        );
+    }
+}
+
+#[test]
+fn test_select_zero_by_inventory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 64, 65, 1000, 100_000, 1_000_000] {
+        for density in [0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let zeros = bits.iter_zeros().collect::<Vec<_>>();
+
+            let select = SelectAdaptConst::<_, _>::new(Rank9::new(bits.clone()));
+            for (rank, &pos) in zeros.iter().enumerate() {
+                assert_eq!(select.select_zero_by_inventory(rank), Some(pos));
+            }
+            assert_eq!(select.select_zero_by_inventory(zeros.len()), None);
+
+            let select = SelectAdaptConst::<_, _, INV, SUB>::new(Rank9::new(bits));
+            for (rank, &pos) in zeros.iter().enumerate() {
+                assert_eq!(select.select_zero_by_inventory(rank), Some(pos));
+            }
+            assert_eq!(select.select_zero_by_inventory(zeros.len()), None);
+        }
+    }
+}
+
+#[test]
+fn test_overhead() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let len = 1_000_000;
+    for density in [0.1, 0.25, 0.5, 0.9, 1.0] {
+        let bits: AddNumBits<_> = (0..len)
+            .map(|_| rng.gen_bool(density))
+            .collect::<BitVec>()
+            .into();
+        let select = SelectAdaptConst::<_, _>::new(bits);
+        let inventory_bytes = select.inventory_bytes();
+        let fraction = select.overhead_fraction();
+
+        let (bits, inventory, spill) = select.into_raw_parts();
+        assert!(spill.is_empty());
+        assert_eq!(
+            inventory_bytes,
+            inventory.len() * core::mem::size_of::<usize>()
+        );
+        assert_eq!(
+            fraction,
+            inventory_bytes as f64 / core::mem::size_of_val(bits.as_ref()) as f64
+        );
+        // Within the documented bound, plus the final sentinel word
+        assert!(
+            fraction <= 0.15 * density + 1E-3,
+            "density = {}: {}",
+            density,
+            fraction
+        );
+    }
+
+    let select = SelectAdaptConst::<_, _>::new(AddNumBits::from(BitVec::new(0)));
+    assert_eq!(select.overhead_fraction(), 0.0);
+}
+
+#[test]
+fn test_new_in() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for (len, density) in [
+        (0, 0.5),
+        (1000, 0.5),
+        (100_000, 0.0),
+        (100_000, 0.5),
+        (100_000, 1.0),
+        (1 << 22, 0.0001),
+        (1 << 22, 0.001),
+        (1 << 22, 0.9),
+    ] {
+        let bits: AddNumBits<_> = (0..len)
+            .map(|_| rng.gen_bool(density))
+            .collect::<BitVec>()
+            .into();
+        let select = SelectAdaptConst::<_, _, INV, SUB>::new(bits.clone());
+
+        // A single preallocated region, filled with garbage
+        let inventory_words = SelectAdaptConst::<AddNumBits<BitVec>, Box<[usize]>, INV, SUB>::expected_inventory_words(bits.count_ones());
+        let spill_words = SelectAdaptConst::<_, _, INV, SUB>::expected_spill_words(&bits);
+        let mut region = vec![usize::MAX; inventory_words + spill_words];
+        let (inventory, spill) = region.split_at_mut(inventory_words);
+        let select_in = SelectAdaptConst::<_, _, INV, SUB>::new_in(&bits, inventory, spill);
+
+        for rank in 0..bits.count_ones() {
+            assert_eq!(select_in.select(rank), select.select(rank));
+        }
+        assert_eq!(select_in.select(bits.count_ones()), None);
+
+        let (_, inventory, spill) = select_in.into_raw_parts();
+        let (_, expected_inventory, expected_spill) = select.into_raw_parts();
+        assert_eq!(inventory, &*expected_inventory);
+        assert_eq!(spill, &*expected_spill);
+    }
+}
+
+#[test]
+fn test_select_as_zero() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+        for density in [0.1, 0.5, 0.9] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let mut complement = bits.clone();
+            complement.flip();
+            let complement: AddNumBits<_> = complement.into();
+            let select = SelectAsZero::new(SelectAdaptConst::<_, _, INV, SUB>::new(complement));
+
+            let zeros = (0..len).filter(|&i| !bits[i]).collect::<Vec<_>>();
+            assert_eq!(select.len(), len);
+            assert_eq!(select.num_zeros(), zeros.len());
+            assert_eq!(select.num_ones(), len - zeros.len());
+            for (i, &p) in zeros.iter().enumerate() {
+                assert_eq!(select.select_zero(i), Some(p));
+            }
+            assert_eq!(select.select_zero(zeros.len()), None);
+            for i in 0..len {
+                assert_eq!(select[i], bits[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_u16_subinventory_layout() {
+    // With INV = 12 and SUB = 2, each inventory entry is followed by four
+    // words containing 16 u16 entries, each recording the offset of the
+    // position of the one of rank multiple of 2^8 from the position of the
+    // one of the inventory entry. The entries are packed in native byte
+    // order, so they must decode correctly using u16::from_ne_bytes.
+    let mut rng = SmallRng::seed_from_u64(0);
+    let bits: AddNumBits<_> = (0..100_000)
+        .map(|_| rng.gen_bool(0.5))
+        .collect::<BitVec>()
+        .into();
+    let num_ones = bits.num_ones();
+    let select = SelectAdaptConst::<_, _, INV, SUB>::new(bits);
+    let positions = (0..num_ones)
+        .map(|rank| select.select(rank).unwrap())
+        .collect::<Vec<_>>();
+    let (_, inventory, _) = select.into_raw_parts();
+
+    let words_per_inventory = 1 + (1 << SUB);
+    for i in 0..num_ones >> INV {
+        let start = inventory[i * words_per_inventory];
+        // Spans are smaller than 2^16, so the highest bit is clear
+        assert_eq!(start >> 63, 0);
+        assert_eq!(start, positions[i << INV]);
+        let sub16 = inventory[i * words_per_inventory + 1..(i + 1) * words_per_inventory]
+            .iter()
+            .flat_map(|word| word.to_ne_bytes())
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]) as usize)
+            .collect::<Vec<_>>();
+        assert_eq!(sub16.len(), 1 << (SUB + 2));
+        for (j, &offset) in sub16.iter().enumerate() {
+            let rank = (i << INV) + (j << (INV - SUB - 2));
+            assert_eq!(start + offset, positions[rank]);
+        }
+    }
+}
+
+#[test]
+fn test_select_with_gap() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [0, 1, 63, 64, 65, 1000, 100_000] {
+        for density in [0.001, 0.1, 0.5, 0.9, 1.0] {
+            let bits: AddNumBits<_> = (0..len)
+                .map(|_| rng.gen_bool(density))
+                .collect::<BitVec>()
+                .into();
+            let select = SelectAdaptConst::<_, _, INV, SUB>::new(bits);
+            let ones = select.num_ones();
+            for i in 0..ones {
+                let pos = select.select(i).unwrap();
+                let next = select.select(i + 1).unwrap_or(len);
+                assert_eq!(select.select_with_gap(i), Some((pos, next - pos)));
+            }
+            assert_eq!(select.select_with_gap(ones), None);
+            assert_eq!(select.select_with_gap(ones + 1), None);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use sux::prelude::*;
+
+#[test]
+fn test() {
+    let lens = (1..100)
+        .step_by(10)
+        .chain((100_000..1_100_000).step_by(200_000));
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in lens {
+        for density in [0.001, 0.1, 0.5, 0.9, 0.999] {
+            let bits: AddNumBits<_> = (0..len)
+                .map(|_| rng.gen_bool(density))
+                .collect::<BitVec>()
+                .into();
+
+            let select = SelectDualAdaptConst::<_, _, 10, 2>::new(bits.clone());
+            assert_eq!(select.len(), len);
+
+            let (ones, zeros): (Vec<usize>, Vec<usize>) = (0..len).partition(|&i| bits[i]);
+            assert_eq!(select.num_ones(), ones.len());
+            assert_eq!(select.num_zeros(), zeros.len());
+
+            for (i, &p) in ones.iter().enumerate() {
+                assert_eq!(select.select(i), Some(p));
+            }
+            assert_eq!(select.select(ones.len()), None);
+
+            for (i, &p) in zeros.iter().enumerate() {
+                assert_eq!(select.select_zero(i), Some(p));
+            }
+            assert_eq!(select.select_zero(zeros.len()), None);
+        }
+    }
+}
+
+#[test]
+fn test_from_bit_vec() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let bits = (0..10_000).map(|_| rng.gen_bool(0.3)).collect::<BitVec>();
+
+    // Without NumBits we get only the unchecked versions
+    let select = SelectDualAdaptConst::<_, _>::new(bits.clone());
+    let nested = SelectZeroAdaptConst::<_, _>::new(SelectAdaptConst::<_, _>::new(bits.clone()));
+    for (i, p) in bits.iter_ones().enumerate() {
+        assert_eq!(unsafe { select.select_unchecked(i) }, p);
+        assert_eq!(unsafe { nested.select_unchecked(i) }, p);
+    }
+    for (i, p) in bits.iter_zeros().enumerate() {
+        assert_eq!(unsafe { select.select_zero_unchecked(i) }, p);
+        assert_eq!(unsafe { nested.select_zero_unchecked(i) }, p);
+    }
+
+    assert_eq!(select.into_bit_vec(), bits);
+}
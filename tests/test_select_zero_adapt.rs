@@ -316,3 +316,40 @@ fn test_sub32s_last_small() {
         assert_eq!(simple.select_zero(zeros + 1), None);
     }
 }
+
+// SelectZeroAdapt is the runtime-parameterized sibling of
+// SelectZeroAdaptConst: with the same quantum, they must answer identically.
+fn test_runtime_quantum_param<const LOG2_ZEROS_PER_INVENTORY: usize>(bits: &AddNumBits<BitVec>) {
+    let select = SelectZeroAdapt::with_inv(bits.clone(), LOG2_ZEROS_PER_INVENTORY, 3);
+    let select_const = SelectZeroAdaptConst::<_, _, LOG2_ZEROS_PER_INVENTORY, 3>::new(bits.clone());
+    let zeros = bits.num_zeros();
+    assert_eq!(select.num_zeros(), zeros);
+    for rank in 0..=zeros {
+        assert_eq!(select.select_zero(rank), select_const.select_zero(rank));
+    }
+}
+
+#[test]
+fn test_runtime_quantum() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for len in [1, 10, 1000, 100_000] {
+        for density in [0.01, 0.5, 0.99] {
+            let bits = (0..len).map(|_| rng.gen_bool(density)).collect::<BitVec>();
+            let zeros = bits.iter_zeros().collect::<Vec<_>>();
+            let bits: AddNumBits<_> = bits.into();
+
+            for log2_zeros_per_inventory in [1, 2, 5, 8, 10, 12] {
+                let select = SelectZeroAdapt::with_inv(bits.clone(), log2_zeros_per_inventory, 3);
+                for (rank, &pos) in zeros.iter().enumerate() {
+                    assert_eq!(select.select_zero(rank), Some(pos));
+                }
+                assert_eq!(select.select_zero(zeros.len()), None);
+            }
+
+            test_runtime_quantum_param::<2>(&bits);
+            test_runtime_quantum_param::<5>(&bits);
+            test_runtime_quantum_param::<8>(&bits);
+            test_runtime_quantum_param::<12>(&bits);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::io::prelude::*;
+use std::io::BufReader;
+use sux::dict::StringIntMapBuilder;
+
+#[test]
+fn test_string_int_map() -> Result<()> {
+    let words = BufReader::new(std::fs::File::open("tests/data/wordlist.10000")?)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for max_value in [1, 1000, u64::MAX] {
+        // Random pairs on half of the words, pushed in random order
+        let mut keys = words
+            .iter()
+            .filter(|_| rng.gen_bool(0.5))
+            .collect::<Vec<_>>();
+        keys.shuffle(&mut rng);
+        let pairs = keys
+            .iter()
+            .map(|&key| (key.clone(), rng.gen_range(0..=max_value)))
+            .collect::<HashMap<_, _>>();
+
+        for k in [1, 4, 16] {
+            let mut builder = StringIntMapBuilder::new(k);
+            builder.extend(keys.iter().map(|&key| (key, pairs[key])));
+            let map = builder.build();
+            assert_eq!(map.len(), pairs.len());
+            assert!(map.keys().is_sorted());
+
+            for word in &words {
+                assert_eq!(map.get(word), pairs.get(word).copied());
+            }
+            assert_eq!(map.get(""), None);
+            assert_eq!(map.get("\u{10ffff}"), None);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_string_int_map_empty() {
+    let map = StringIntMapBuilder::new(4).build();
+    assert!(map.is_empty());
+    assert_eq!(map.get("a"), None);
+}
+
+#[test]
+#[should_panic]
+fn test_string_int_map_duplicate_key() {
+    let mut builder = StringIntMapBuilder::new(4);
+    builder.push("a", 0);
+    builder.push("b", 1);
+    builder.push("a", 2);
+    builder.build();
+}
+
+#[test]
+fn test_string_int_map_nul() {
+    let pairs = [
+        ("a\0b", 0),
+        ("a", 1),
+        ("a\0", 2),
+        ("", 3),
+        ("\0", 4),
+        ("b", 5),
+    ];
+    let mut builder = StringIntMapBuilder::new(2);
+    for (key, value) in pairs {
+        builder.push(key, value);
+    }
+    let map = builder.build();
+    assert_eq!(map.len(), pairs.len());
+    for (key, value) in pairs {
+        assert_eq!(map.get(key), Some(value));
+    }
+    assert_eq!(map.get("a\0c"), None);
+    assert_eq!(map.get("\0\0"), None);
+}
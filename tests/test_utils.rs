@@ -0,0 +1,89 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use sux::utils::{first_non_monotone, is_monotone};
+
+#[test]
+fn test_monotone() {
+    assert!(is_monotone(&[]));
+    assert!(is_monotone(&[42]));
+    assert_eq!(first_non_monotone(&[]), None);
+    assert_eq!(first_non_monotone(&[42]), None);
+
+    let sorted = (0..1000).map(|x| x / 3).collect::<Vec<_>>();
+    assert!(is_monotone(&sorted));
+    assert_eq!(first_non_monotone(&sorted), None);
+
+    let reversed = sorted.iter().rev().copied().collect::<Vec<_>>();
+    assert!(!is_monotone(&reversed));
+    assert_eq!(first_non_monotone(&reversed), Some(1));
+
+    for dip in [1, 2, 500, 999] {
+        let mut values = (10..1010).collect::<Vec<_>>();
+        values[dip] = values[dip - 1] - 1;
+        assert!(!is_monotone(&values));
+        assert_eq!(first_non_monotone(&values), Some(dip));
+    }
+
+    // A dip in the first element
+    let mut values = (0..1000).collect::<Vec<_>>();
+    values[0] = 2;
+    assert_eq!(first_non_monotone(&values), Some(1));
+}
+
+#[test]
+fn test_entropy() {
+    use sux::utils::entropy_bits_per_element;
+
+    assert_eq!(entropy_bits_per_element(0.5), 1.0);
+    assert_eq!(entropy_bits_per_element(0.0), 0.0);
+    assert_eq!(entropy_bits_per_element(1.0), 0.0);
+    // H(1/4) = 2 - 3/4 log₂ 3
+    let h = 2.0 - 0.75 * 3_f64.log2();
+    assert!((entropy_bits_per_element(0.25) - h).abs() < 1E-12);
+    // Symmetry
+    for p in [0.01, 0.1, 0.3] {
+        assert!((entropy_bits_per_element(p) - entropy_bits_per_element(1.0 - p)).abs() < 1E-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_entropy_out_of_range() {
+    sux::utils::entropy_bits_per_element(1.5);
+}
+
+#[test]
+fn test_select_in_slice() {
+    use sux::utils::select_in_slice;
+
+    assert_eq!(select_in_slice(&[], 0), None);
+    assert_eq!(select_in_slice(&[0, 0], 0), None);
+
+    // Exhaustive over all slices of two 4-bit patterns placed at both ends
+    // of the words
+    for a in 0..16_u64 {
+        for b in 0..16_u64 {
+            for shift in [0, 30, 60] {
+                let words = [a << shift, b << shift];
+                let ones = (0..128)
+                    .filter(|&i| words[i / 64] & (1 << (i % 64)) != 0)
+                    .collect::<Vec<_>>();
+                for (rank, &pos) in ones.iter().enumerate() {
+                    assert_eq!(select_in_slice(&words, rank), Some(pos));
+                }
+                assert_eq!(select_in_slice(&words, ones.len()), None);
+            }
+        }
+    }
+
+    // All words full
+    let words = [u64::MAX; 3];
+    for rank in 0..192 {
+        assert_eq!(select_in_slice(&words, rank), Some(rank));
+    }
+    assert_eq!(select_in_slice(&words, 192), None);
+}